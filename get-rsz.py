@@ -1,10 +1,33 @@
 import urllib.request
 import datetime
+import json
+
+REPO = 'dtlnor/RE_RSZ'
+BRANCH = 'MHWilds'
+FILE = 'rszmhwilds.json'
+
+def latest_commit():
+    url = f'https://api.github.com/repos/{REPO}/commits?sha={BRANCH}&path={FILE}&per_page=1'
+
+    try:
+        with urllib.request.urlopen(url) as response:
+            return json.load(response)[0]['sha']
+    except Exception:
+        return None
 
 print("Downloading file... ", end = '')
-urllib.request.urlretrieve('https://raw.githubusercontent.com/dtlnor/RE_RSZ/refs/heads/MHWilds/rszmhwilds.json', 'rszmhwilds.json')
 
-with open('rszmhwilds.json.version', 'w') as f:
-    f.write(datetime.datetime.now().isoformat())
+# Download from the commit itself, so the file always matches the version that's recorded.
+commit = latest_commit()
+ref = commit or f'refs/heads/{BRANCH}'
+urllib.request.urlretrieve(f'https://raw.githubusercontent.com/{REPO}/{ref}/{FILE}', FILE)
+
+with open(f'{FILE}.version', 'w') as f:
+    version = datetime.datetime.now().isoformat()
+
+    if commit:
+        version = f'{commit[:12]} {version}'
+
+    f.write(version)
 
 print("done!")
\ No newline at end of file
@@ -0,0 +1 @@
+cargo run --manifest-path tools\mhdb\Cargo.toml -- %*
\ No newline at end of file
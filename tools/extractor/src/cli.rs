@@ -1,22 +1,46 @@
-use crate::targets::TargetKind;
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use extractor::plan::Format;
+use extractor::Options;
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(version, about)]
 pub struct Cli {
-    #[arg(short, long)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[arg(short, long, global = true)]
     pub config: Option<PathBuf>,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     pub cwd: Option<PathBuf>,
 
-    #[arg(long)]
-    pub skip_data: bool,
+    #[command(flatten)]
+    pub options: Options,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Prints the files each target would extract, where they would be read from, and where they
+    /// would be written to, along with any likely mistakes in the config file. Nothing is
+    /// extracted.
+    Plan {
+        #[command(flatten)]
+        options: Options,
+
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
 
-    #[arg(long)]
-    pub skip_translations: bool,
+    /// Lists the RSZ fields used by each `user` target that aren't in the schema baseline, such as
+    /// fields added by a game update. Nothing is extracted.
+    Schema {
+        #[command(flatten)]
+        options: Options,
 
-    #[arg(long, short)]
-    pub force: Vec<TargetKind>,
+        /// Replaces the baseline for the selected targets with the fields found now.
+        /// Can't be combined with `--only` or `--for-processor`.
+        #[arg(long)]
+        update: bool,
+    },
 }
@@ -0,0 +1,539 @@
+use crate::manifest::{Manifest, Origin, Tracker, MANIFEST};
+use crate::targets::{Candidate, Source, Sources, TargetKind};
+use anyhow::Context;
+use anyhow::{bail, Result};
+use clap::Args;
+use console::Style;
+use indicatif::ProgressBar;
+use rayon::iter::ParallelIterator;
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator};
+use rayon::ThreadPoolBuilder;
+use rslib::config::{Config, Files, Target, Transform};
+use rslib::dump::DumpFormat;
+use rslib::layouts::{Layouts, Problem};
+use rslib::processors::Processor;
+use rslib::tools::{hash_file, Extractor, MsgExtractor, UserExtractor};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wax::{Glob, Pattern};
+
+pub mod manifest;
+pub mod plan;
+pub mod schema;
+pub mod targets;
+
+/// The directory that files read from `.pak` files are unpacked to, relative to the configured
+/// output directory.
+pub const PAK_CACHE: &str = ".pak-cache";
+
+#[derive(Debug, Args, Default)]
+pub struct Options {
+    #[arg(long)]
+    pub skip_data: bool,
+
+    #[arg(long)]
+    pub skip_translations: bool,
+
+    /// Re-extracts files even if the manifest says their dumps are up to date.
+    #[arg(long, short)]
+    pub force: Vec<TargetKind>,
+
+    /// Only runs the targets with this name (set with `name = "..."` in the config file).
+    #[arg(long)]
+    pub target: Vec<String>,
+
+    /// Only extracts files whose path matches this glob. Paths are relative to the section's
+    /// `input_prefix`, e.g. `Common/Equip/*.user.3`.
+    #[arg(long)]
+    pub only: Vec<String>,
+
+    /// Only extracts the files read by this merger processor. Processors that fill in `<REF>`
+    /// placeholders also select every msg file, since a reference can point at an entry in any of
+    /// them.
+    #[arg(long)]
+    pub for_processor: Vec<Processor>,
+
+    /// Extracts `user` files even if their types don't match the RSZ layouts file.
+    #[arg(long)]
+    pub skip_layout_check: bool,
+
+    /// The number of files to extract at once. Each worker holds one parsed file in memory, so
+    /// lowering this reduces peak memory use. Defaults to the number of CPU cores.
+    #[arg(long, short)]
+    pub jobs: Option<usize>,
+}
+
+/// Extracts every `user` and `msg` target in `config` into the configured output directory, then
+/// updates the manifest. Files are skipped if their source and dump still match the hashes in the
+/// manifest.
+pub fn run(config: &Config, options: &Options) -> Result<()> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(options.jobs.unwrap_or_default())
+        .build()?;
+
+    pool.install(|| run_all(config, options))
+}
+
+fn run_all(config: &Config, options: &Options) -> Result<()> {
+    let style = Style::new().bold().dim();
+    let selection = Selection::new(config, options)?;
+    let sources = load_sources(config)?;
+
+    let manifest_path = config.io.output.join(MANIFEST);
+    let tracker = Tracker::new(&config.io.output, Manifest::load(&manifest_path)?);
+
+    if !options.skip_data {
+        println!("{} Running `user` targets...", style.apply_to("[1/2]"));
+        run_targets(
+            options,
+            config,
+            &sources,
+            &selection,
+            &tracker,
+            &config.user,
+            ExtractorKind::User,
+        )?;
+    } else {
+        println!("{} Skipping `user` targets.", style.apply_to("[1/2]"));
+    }
+
+    if !options.skip_translations {
+        println!("{} Running `msg` targets...", style.apply_to("[2/2]"));
+        run_targets(
+            options,
+            config,
+            &sources,
+            &selection,
+            &tracker,
+            &config.msg,
+            ExtractorKind::Msg,
+        )?;
+    } else {
+        println!("{} Skipping `msg` targets.", style.apply_to("[2/2]"));
+    }
+
+    tracker.into_inner().save(&manifest_path)?;
+
+    Ok(())
+}
+
+fn load_sources(config: &Config) -> Result<Sources> {
+    let sources = expand_path_strings(&config.io.data)?
+        .into_iter()
+        .map(Source::new)
+        .collect::<Result<Vec<_>>>()?;
+
+    Sources::new(
+        sources,
+        config.tools.pak_list.as_deref(),
+        config.io.output.join(PAK_CACHE),
+    )
+}
+
+/// Narrows down which files are extracted, based on the `--target`, `--only`, and
+/// `--for-processor` options. A file is only extracted if it passes every option that was given.
+struct Selection {
+    targets: Vec<String>,
+    only: Vec<Glob<'static>>,
+
+    /// Globs matching the processors' inputs, relative to the output directory.
+    outputs: Vec<Glob<'static>>,
+}
+
+impl Selection {
+    fn new(config: &Config, options: &Options) -> Result<Self> {
+        for name in &options.target {
+            let exists = config
+                .user
+                .targets
+                .iter()
+                .chain(&config.msg.targets)
+                .any(|v| v.name.as_ref() == Some(name));
+
+            if !exists {
+                bail!("No target named `{name}` in the config file");
+            }
+        }
+
+        let only = options
+            .only
+            .iter()
+            .map(|v| Ok(Glob::new(v)?.into_owned()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let outputs = options
+            .for_processor
+            .iter()
+            .flat_map(|v| v.inputs())
+            .map(|v| Ok(Glob::new(&v)?.into_owned()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            targets: options.target.clone(),
+            only,
+            outputs,
+        })
+    }
+
+    fn includes_target(&self, target: &Target) -> bool {
+        self.targets.is_empty()
+            || target
+                .name
+                .as_ref()
+                .is_some_and(|v| self.targets.contains(v))
+    }
+
+    /// Returns `true` if a file should be extracted. `outputs` are the paths the file would be
+    /// extracted to, relative to the output directory.
+    fn includes_file(&self, key: &str, outputs: &[PathBuf]) -> bool {
+        let only = self.only.is_empty() || self.only.iter().any(|v| v.is_match(key));
+
+        let outputs = self.outputs.is_empty()
+            || outputs.iter().any(|path| {
+                let path = path.to_string_lossy().replace('\\', "/");
+                self.outputs.iter().any(|v| v.is_match(path.as_str()))
+            });
+
+        only && outputs
+    }
+}
+
+/// The RSZ layouts shared between a `user` extractor and the checks run before extraction.
+type LayoutsRef = Option<Arc<Layouts>>;
+
+enum ExtractorKind {
+    User,
+    Msg,
+}
+
+impl ExtractorKind {
+    /// Creates the extractor for this kind of file, along with the RSZ layouts it uses (if any).
+    /// Extractors always overwrite their output, since the manifest decides which files are skipped.
+    fn create(&self, config: &Config) -> Result<(Box<dyn Extractor>, LayoutsRef)> {
+        Ok(match self {
+            Self::User => {
+                let path = &config.tools.rsz_layouts;
+                let layouts = Layouts::load(path)
+                    .with_context(|| format!("Could not load {}", path.display()))?;
+
+                let layouts = Arc::new(layouts);
+
+                let extractor = UserExtractor::from_layouts(layouts.clone())
+                    .with_force(true)
+                    .with_format(config.io.format);
+
+                (Box::new(extractor), Some(layouts))
+            }
+            Self::Msg => {
+                let extractor = MsgExtractor::create(config.tools.msg.as_deref(), None)
+                    .with_force(true)
+                    .with_format(config.io.format);
+
+                (Box::new(extractor), None)
+            }
+        })
+    }
+
+    /// Returns the paths a file would be extracted to, without creating an extractor.
+    fn outputs(&self, out_path: &Path, transform: Option<&Transform>) -> Result<Vec<PathBuf>> {
+        Ok(match self {
+            Self::User => UserExtractor::output_paths(out_path, transform)?,
+            Self::Msg => vec![out_path.to_owned()],
+        })
+    }
+
+    /// Returns the name of the tool used to decode `msg` files, as recorded in the manifest.
+    fn msg_tool(&self, config: &Config) -> Option<&'static str> {
+        match self {
+            Self::User => None,
+            Self::Msg if config.tools.msg.is_some() => Some("REMSG_Converter"),
+            Self::Msg => Some("native"),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Msg => "msg",
+        }
+    }
+
+    fn get_output_prefix(&self) -> &Path {
+        match self {
+            Self::User => Path::new("user"),
+            Self::Msg => Path::new("msg"),
+        }
+    }
+}
+
+impl From<&ExtractorKind> for TargetKind {
+    fn from(value: &ExtractorKind) -> Self {
+        use ExtractorKind::*;
+
+        match value {
+            User => Self::Usr,
+            Msg => Self::Msg,
+        }
+    }
+}
+
+fn run_targets(
+    options: &Options,
+    config: &Config,
+    sources: &Sources,
+    selection: &Selection,
+    tracker: &Tracker,
+    section: &Files,
+    extractor_kind: ExtractorKind,
+) -> Result<()> {
+    let out_dir = config.io.output.join(extractor_kind.get_output_prefix());
+
+    if !fs::exists(&out_dir)? {
+        fs::create_dir_all(&out_dir)?;
+    }
+
+    let (extractor, layouts) = extractor_kind.create(config)?;
+    let rsz_version = layouts.as_ref().map(|v| v.version().to_owned());
+    let msg_tool = extractor_kind.msg_tool(config).map(str::to_owned);
+
+    let target_kind: TargetKind = (&extractor_kind).into();
+    let force = options.force.iter().any(|v| v.matches(target_kind));
+
+    let mut targets = get_candidate_targets(
+        sources,
+        selection,
+        section.input_prefix.as_deref(),
+        &section.targets,
+    )?;
+
+    select_files(config, selection, &extractor_kind, &mut targets)?;
+
+    if let Some(layouts) = &layouts {
+        if !options.skip_layout_check {
+            check_layouts(layouts, sources, &targets)?;
+        }
+    }
+
+    let progress = ProgressBar::new(targets.len_all_files() as u64);
+
+    targets
+        .into_par_iter()
+        .try_for_each(|ExpandedTarget { target, files, .. }| -> Result<()> {
+            files
+                .into_par_iter()
+                .try_for_each(|candidate| -> Result<()> {
+                    progress.inc(1);
+
+                    let out_path = get_output_path(&out_dir, target, &candidate, config.io.format)?;
+                    let out_dir = out_path.parent().context("output path has no parent")?;
+
+                    if !fs::exists(out_dir)? {
+                        fs::create_dir_all(out_dir)?;
+                    }
+
+                    let in_path = sources.resolve(&candidate)?;
+                    let transform = target.find_transform(&candidate.key);
+                    let outputs = extractor.outputs(&out_path, transform)?;
+
+                    let origin = Origin {
+                        source: candidate.key.clone(),
+                        root: sources
+                            .root(&candidate)
+                            .to_string_lossy()
+                            .replace('\\', "/"),
+                        source_hash: hash_file(&in_path)?,
+                        rsz_version: rsz_version.clone(),
+                        msg_tool: msg_tool.clone(),
+                        transform: transform.map(serde_json::to_string).transpose()?,
+                    };
+
+                    if !force && tracker.is_current(&outputs, &origin)? {
+                        tracker.update_origin(&outputs, &origin);
+                        return Ok(());
+                    }
+
+                    for output in extractor.extract(&in_path, &out_path, transform)? {
+                        tracker.record(&output, origin.clone())?;
+                    }
+
+                    Ok(())
+                })
+        })?;
+
+    progress.finish_and_clear();
+
+    Ok(())
+}
+
+/// Checks every file in `targets` against the RSZ layouts before anything is extracted, and fails
+/// with a report of every mismatched type if the layouts are out of date.
+fn check_layouts(layouts: &Layouts, sources: &Sources, targets: &[ExpandedTarget]) -> Result<()> {
+    let mut problems: Vec<(String, Vec<Problem>)> = targets
+        .par_iter()
+        .flat_map(|v| &v.files)
+        .map(|candidate| -> Result<_> {
+            let path = sources.resolve(candidate)?;
+            let problems = layouts
+                .check(&path)
+                .with_context(|| format!("Could not read {}", candidate.key))?;
+
+            Ok((candidate.key.clone(), problems))
+        })
+        .filter(|v| !matches!(v, Ok((_, problems)) if problems.is_empty()))
+        .collect::<Result<_>>()?;
+
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    problems.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (key, problems) in &problems {
+        eprintln!("{key}:");
+
+        for problem in problems {
+            eprintln!("    {problem}");
+        }
+    }
+
+    bail!(
+        "{} file(s) don't match the RSZ layouts (version {}). Run get-rsz.py to update them, or \
+        pass --skip-layout-check to extract anyway",
+        problems.len(),
+        layouts.version()
+    )
+}
+
+/// Drops the files in `targets` that `selection` doesn't include.
+fn select_files(
+    config: &Config,
+    selection: &Selection,
+    kind: &ExtractorKind,
+    targets: &mut [ExpandedTarget],
+) -> Result<()> {
+    let out_dir = config.io.output.join(kind.get_output_prefix());
+
+    for ExpandedTarget { target, files, .. } in targets {
+        let mut selected = Vec::with_capacity(files.len());
+
+        for candidate in files.drain(..) {
+            let out_path = get_output_path(&out_dir, target, &candidate, config.io.format)?;
+            let outputs: Vec<PathBuf> = kind
+                .outputs(&out_path, target.find_transform(&candidate.key))?
+                .into_iter()
+                .map(|v| v.strip_prefix(&config.io.output).unwrap_or(&v).to_owned())
+                .collect();
+
+            if selection.includes_file(&candidate.key, &outputs) {
+                selected.push(candidate);
+            }
+        }
+
+        *files = selected;
+    }
+
+    Ok(())
+}
+
+/// Names a target in reports: its `name`, or its section and position in the config file if it
+/// doesn't have one (e.g. `user #3`).
+fn target_label(target: &Target, index: usize, kind: &ExtractorKind) -> String {
+    match &target.name {
+        Some(name) => name.clone(),
+        None => format!("{} #{index}", kind.name()),
+    }
+}
+
+fn get_output_path(
+    out_dir: &Path,
+    target: &Target,
+    candidate: &Candidate,
+    format: DumpFormat,
+) -> Result<PathBuf> {
+    let file_name = Path::new(&candidate.key)
+        .file_name()
+        .context("could not extract file name from candidate")?;
+
+    Ok(out_dir
+        .join_opt(target.output_prefix.as_ref())
+        .join(file_name)
+        .with_extension("")
+        .with_extension(format.extension()))
+}
+
+#[derive(Debug)]
+struct ExpandedTarget<'a> {
+    target: &'a Target,
+    index: usize,
+    files: Vec<Candidate>,
+}
+
+fn get_candidate_targets<'a>(
+    sources: &Sources,
+    selection: &Selection,
+    prefix: Option<&Path>,
+    targets: &'a [Target],
+) -> Result<Vec<ExpandedTarget<'a>>> {
+    targets
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| selection.includes_target(v))
+        .map(|(index, v)| -> Result<ExpandedTarget<'a>> {
+            let mut paths = sources.find(prefix, &v.files)?;
+
+            // Enforce a stable path order, this is mostly used for debugging.
+            paths.sort_by(|a, b| a.key.cmp(&b.key));
+
+            Ok(ExpandedTarget {
+                target: v,
+                index,
+                files: paths,
+            })
+        })
+        .collect()
+}
+
+fn expand_path_strings(paths: &[String]) -> Result<Vec<PathBuf>> {
+    Ok(paths
+        .iter()
+        .map(|path| expand_path_string(path))
+        .collect::<Result<Vec<Vec<_>>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn expand_path_string(pattern: &str) -> Result<Vec<PathBuf>> {
+    let Ok(glob) = Glob::new(pattern) else {
+        return Ok(vec![PathBuf::from(pattern)]);
+    };
+
+    let (base, glob) = glob.partition();
+
+    // Since we're dealing with expanded PAKs, ensure we never descend more than one directory down.
+    // If we try to match the glob against the entire PAK tree, it's gonna take a really long time.
+    glob.walk_with_behavior(base, 1)
+        .map(|v| v.map(|v| v.into_path()).map_err(|e| e.into()))
+        .collect::<Result<Vec<_>>>()
+}
+
+trait ExpandedTargetExt {
+    fn len_all_files(&self) -> usize;
+}
+
+impl ExpandedTargetExt for Vec<ExpandedTarget<'_>> {
+    fn len_all_files(&self) -> usize {
+        self.iter().fold(0, |counter, v| counter + v.files.len())
+    }
+}
+
+trait PathExt {
+    fn join_opt<P: AsRef<Path>>(&self, opt: Option<P>) -> PathBuf;
+}
+
+impl PathExt for Path {
+    fn join_opt<P: AsRef<Path>>(&self, opt: Option<P>) -> PathBuf {
+        opt.map_or_else(|| self.to_path_buf(), |v| self.join(v))
+    }
+}
@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use rslib::tools::hash_file;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// The name of the extraction manifest, relative to the configured output directory.
+pub const MANIFEST: &str = "manifest.json";
+
+/// Records where every dump in the output directory came from, and the hashes of its source and
+/// output as of the last time it was extracted.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// dump path (relative to the output directory) -> record
+    pub files: BTreeMap<String, Record>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(flatten)]
+    pub origin: Origin,
+
+    /// The SHA-256 hash of the dump.
+    pub output_hash: String,
+}
+
+/// Describes the source file a dump was extracted from.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Origin {
+    /// The path of the source file, relative to its section's `input_prefix`.
+    pub source: String,
+
+    /// The directory or `.pak` file the source file was read from.
+    pub root: String,
+
+    /// The SHA-256 hash of the source file.
+    pub source_hash: String,
+
+    /// The version of the RSZ layouts file used to extract the dump. Always `None` for `msg`
+    /// dumps.
+    pub rsz_version: Option<String>,
+
+    /// The tool used to decode the dump, either `native` or `REMSG_Converter`. Always `None` for
+    /// `user` dumps.
+    #[serde(default)]
+    pub msg_tool: Option<String>,
+
+    /// The transform applied to the dump, serialized to JSON. Changing a target's transforms
+    /// causes the dumps it applies to to be extracted again.
+    #[serde(default)]
+    pub transform: Option<String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Tracks the manifest while extractors run in parallel.
+pub(crate) struct Tracker<'a> {
+    /// Dump paths in the manifest are relative to this directory.
+    root: &'a Path,
+    manifest: Mutex<Manifest>,
+}
+
+impl<'a> Tracker<'a> {
+    pub fn new(root: &'a Path, manifest: Manifest) -> Self {
+        Self {
+            root,
+            manifest: Mutex::new(manifest),
+        }
+    }
+
+    /// Returns the path of `output` relative to the output directory, as it appears in the manifest.
+    pub fn key(&self, output: &Path) -> String {
+        output
+            .strip_prefix(self.root)
+            .unwrap_or(output)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// Returns `true` if every one of `outputs` was last extracted from a source with the same hash,
+    /// RSZ layouts version, `msg` tool, and transform as `origin`, and hasn't changed since.
+    pub fn is_current(&self, outputs: &[PathBuf], origin: &Origin) -> Result<bool> {
+        for output in outputs {
+            let record = {
+                let manifest = self.manifest.lock().unwrap();
+
+                match manifest.files.get(&self.key(output)) {
+                    Some(v) => v.clone(),
+                    None => return Ok(false),
+                }
+            };
+
+            if record.origin.source_hash != origin.source_hash
+                || record.origin.rsz_version != origin.rsz_version
+                || record.origin.msg_tool != origin.msg_tool
+                || record.origin.transform != origin.transform
+                || !output.exists()
+                || hash_file(output)? != record.output_hash
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Points the records for `outputs` at `origin`, without re-hashing anything. Used when a dump is
+    /// current, but its source was found in a different root than last time.
+    pub fn update_origin(&self, outputs: &[PathBuf], origin: &Origin) {
+        let mut manifest = self.manifest.lock().unwrap();
+
+        for output in outputs {
+            if let Some(record) = manifest.files.get_mut(&self.key(output)) {
+                record.origin = origin.clone();
+            }
+        }
+    }
+
+    /// Records a freshly extracted dump. The output hash is read from disk.
+    pub fn record(&self, output: &Path, origin: Origin) -> Result<()> {
+        let record = Record {
+            origin,
+            output_hash: hash_file(output)?,
+        };
+
+        self.manifest
+            .lock()
+            .unwrap()
+            .files
+            .insert(self.key(output), record);
+
+        Ok(())
+    }
+
+    /// Returns the manifest, without the records of dumps that no longer exist.
+    pub fn into_inner(self) -> Manifest {
+        let mut manifest = self.manifest.into_inner().unwrap();
+        manifest.files.retain(|key, _| self.root.join(key).exists());
+
+        manifest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(msg_tool: &str) -> Origin {
+        Origin {
+            source: String::from("natives/STM/GameDesign/Text/Excel_Data/Item.msg.23"),
+            root: String::from("data"),
+            source_hash: String::from("source"),
+            rsz_version: None,
+            msg_tool: Some(msg_tool.to_owned()),
+            transform: None,
+        }
+    }
+
+    #[test]
+    fn switching_msg_tools_marks_dumps_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("Item.json");
+        fs::write(&output, "{}").unwrap();
+
+        let tracker = Tracker::new(dir.path(), Manifest::default());
+        tracker.record(&output, origin("native")).unwrap();
+
+        let outputs = [output];
+        let converter = origin("REMSG_Converter");
+        assert!(tracker.is_current(&outputs, &origin("native")).unwrap());
+        assert!(!tracker.is_current(&outputs, &converter).unwrap());
+    }
+
+    #[test]
+    fn deleted_dumps_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let kept = dir.path().join("Kept.json");
+        let deleted = dir.path().join("Deleted.json");
+        fs::write(&kept, "{}").unwrap();
+        fs::write(&deleted, "{}").unwrap();
+
+        let tracker = Tracker::new(dir.path(), Manifest::default());
+        tracker.record(&kept, origin("native")).unwrap();
+        tracker.record(&deleted, origin("native")).unwrap();
+        fs::remove_file(&deleted).unwrap();
+
+        let manifest = tracker.into_inner();
+        assert_eq!(manifest.files.keys().collect::<Vec<_>>(), ["Kept.json"]);
+    }
+}
@@ -0,0 +1,350 @@
+use crate::targets::{Candidate, Sources};
+use crate::{get_output_path, load_sources, target_label, ExtractorKind, Options, Selection};
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use console::Style;
+use rslib::config::{Config, Files, Target};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, Default, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Everything `extractor` would do with the current config file, without extracting anything.
+#[derive(Debug, Default, Serialize)]
+pub struct Plan {
+    pub targets: Vec<TargetPlan>,
+    pub problems: Vec<Problem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TargetPlan {
+    /// The target's `name`, or its section and position in the config file if it doesn't have one
+    /// (e.g. `user #3`).
+    pub target: String,
+    pub files: Vec<FilePlan>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FilePlan {
+    /// The file's path, relative to the section's `input_prefix`.
+    pub file: String,
+
+    /// The directory or `.pak` file the file will be read from.
+    pub root: String,
+
+    /// Other roots that contain the same file, but lose to `root`.
+    pub overridden: Vec<String>,
+
+    /// The `match` pattern of the transform that applies to the file, if any.
+    pub transform: Option<String>,
+
+    /// Paths the file will be extracted to, relative to the output directory.
+    pub outputs: Vec<String>,
+
+    #[serde(skip)]
+    globs: Vec<String>,
+}
+
+/// A likely mistake in the config file.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Problem {
+    /// A glob in a target's `files` list didn't match anything in any data root.
+    EmptyGlob { target: String, glob: String },
+
+    /// More than one glob in the same target matched a file.
+    OverlappingGlobs {
+        target: String,
+        file: String,
+        globs: Vec<String>,
+    },
+
+    /// More than one target in the same section matched a file, so it would be extracted more than
+    /// once.
+    OverlappingTargets { file: String, targets: Vec<String> },
+
+    /// More than one file would be extracted to the same path, so all but one would be overwritten.
+    OutputCollision { output: String, files: Vec<String> },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyGlob { target, glob } => {
+                write!(f, "{target}: `{glob}` didn't match any files")
+            }
+            Self::OverlappingGlobs {
+                target,
+                file,
+                globs,
+            } => write!(
+                f,
+                "{target}: {file} is matched by more than one glob ({})",
+                globs.join(", ")
+            ),
+            Self::OverlappingTargets { file, targets } => write!(
+                f,
+                "{file} is matched by more than one target ({})",
+                targets.join(", ")
+            ),
+            Self::OutputCollision { output, files } => write!(
+                f,
+                "{output} is written by more than one file ({})",
+                files.join(", ")
+            ),
+        }
+    }
+}
+
+/// Prints the plan for every target selected by `options`. Fails if any problems were found, so
+/// the plan can be used to check the config file in CI.
+pub fn run(config: &Config, options: &Options, format: Format) -> Result<()> {
+    let selection = Selection::new(config, options)?;
+    let sources = load_sources(config)?;
+    let mut plan = Plan::default();
+
+    if !options.skip_data {
+        plan.add_section(
+            config,
+            &sources,
+            &selection,
+            &config.user,
+            ExtractorKind::User,
+        )?;
+    }
+
+    if !options.skip_translations {
+        plan.add_section(
+            config,
+            &sources,
+            &selection,
+            &config.msg,
+            ExtractorKind::Msg,
+        )?;
+    }
+
+    match format {
+        Format::Text => plan.print(),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&plan)?),
+    }
+
+    if !plan.problems.is_empty() {
+        bail!(
+            "Found {} problem(s) in the config file",
+            plan.problems.len()
+        );
+    }
+
+    Ok(())
+}
+
+impl Plan {
+    fn add_section(
+        &mut self,
+        config: &Config,
+        sources: &Sources,
+        selection: &Selection,
+        section: &Files,
+        kind: ExtractorKind,
+    ) -> Result<()> {
+        let out_dir = config.io.output.join(kind.get_output_prefix());
+        let prefix = section.input_prefix.as_deref();
+
+        // file -> targets that matched it
+        let mut matched_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        // output -> files extracted to it
+        let mut written_by: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for (index, target) in section.targets.iter().enumerate() {
+            if !selection.includes_target(target) {
+                continue;
+            }
+
+            let label = target_label(target, index, &kind);
+
+            let mut files: BTreeMap<String, FilePlan> = BTreeMap::new();
+
+            for glob in &target.files {
+                let candidates = sources.find_all(prefix, std::slice::from_ref(glob))?;
+
+                if candidates.is_empty() {
+                    self.problems.push(Problem::EmptyGlob {
+                        target: label.clone(),
+                        glob: glob.clone(),
+                    });
+                }
+
+                for (key, mut candidates) in group_by_key(candidates) {
+                    if let Some(file) = files.get_mut(&key) {
+                        file.globs.push(glob.clone());
+                        continue;
+                    }
+
+                    // Highest priority first, so the winner is at the front.
+                    candidates.sort_by_key(|v| std::cmp::Reverse(v.priority));
+
+                    let file = plan_file(config, sources, target, &kind, &out_dir, &candidates)?;
+                    let file = FilePlan {
+                        globs: vec![glob.clone()],
+                        ..file
+                    };
+
+                    files.insert(key, file);
+                }
+            }
+
+            let files: Vec<FilePlan> = files
+                .into_values()
+                .filter(|v| {
+                    let outputs: Vec<_> = v.outputs.iter().map(Into::into).collect();
+                    selection.includes_file(&v.file, &outputs)
+                })
+                .collect();
+
+            for file in &files {
+                if file.globs.len() > 1 {
+                    self.problems.push(Problem::OverlappingGlobs {
+                        target: label.clone(),
+                        file: file.file.clone(),
+                        globs: file.globs.clone(),
+                    });
+                }
+
+                matched_by
+                    .entry(file.file.clone())
+                    .or_default()
+                    .push(label.clone());
+
+                for output in &file.outputs {
+                    written_by
+                        .entry(output.clone())
+                        .or_default()
+                        .push(file.file.clone());
+                }
+            }
+
+            self.targets.push(TargetPlan {
+                target: label,
+                files,
+            });
+        }
+
+        for (file, targets) in matched_by {
+            if targets.len() > 1 {
+                self.problems
+                    .push(Problem::OverlappingTargets { file, targets });
+            }
+        }
+
+        for (output, mut files) in written_by {
+            files.sort();
+            files.dedup();
+
+            if files.len() > 1 {
+                self.problems
+                    .push(Problem::OutputCollision { output, files });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn print(&self) {
+        let heading = Style::new().bold();
+        let dim = Style::new().dim();
+
+        for target in &self.targets {
+            println!(
+                "{} ({} file(s))",
+                heading.apply_to(&target.target),
+                target.files.len()
+            );
+
+            for file in &target.files {
+                println!(
+                    "  {} {}",
+                    file.file,
+                    dim.apply_to(format!("[{}]", file.root))
+                );
+
+                if !file.overridden.is_empty() {
+                    println!("    overrides: {}", file.overridden.join(", "));
+                }
+
+                if let Some(transform) = &file.transform {
+                    println!("    transform: {transform}");
+                }
+
+                for output in &file.outputs {
+                    println!("    -> {output}");
+                }
+            }
+        }
+
+        if !self.problems.is_empty() {
+            println!();
+            println!("{}", heading.apply_to("Problems"));
+
+            for problem in &self.problems {
+                println!("  {problem}");
+            }
+        }
+    }
+}
+
+/// Groups candidates for the same file together, keyed by the file's path.
+fn group_by_key(candidates: Vec<Candidate>) -> BTreeMap<String, Vec<Candidate>> {
+    let mut groups: BTreeMap<String, Vec<Candidate>> = BTreeMap::new();
+
+    for candidate in candidates {
+        groups
+            .entry(candidate.key.clone())
+            .or_default()
+            .push(candidate);
+    }
+
+    groups
+}
+
+/// Plans a single file. `candidates` must be sorted so that the winning candidate comes first.
+fn plan_file(
+    config: &Config,
+    sources: &Sources,
+    target: &Target,
+    kind: &ExtractorKind,
+    out_dir: &Path,
+    candidates: &[Candidate],
+) -> Result<FilePlan> {
+    let winner = &candidates[0];
+    let root = |candidate: &Candidate| to_display(sources.root(candidate));
+
+    let transform = target.find_transform(&winner.key);
+    let out_path = get_output_path(out_dir, target, winner, config.io.format)?;
+
+    let outputs = kind
+        .outputs(&out_path, transform)?
+        .iter()
+        .map(|v| to_display(v.strip_prefix(&config.io.output).unwrap_or(v)))
+        .collect();
+
+    Ok(FilePlan {
+        file: winner.key.clone(),
+        root: root(winner),
+        overridden: candidates[1..].iter().map(root).collect(),
+        transform: transform.map(|v| v.pattern.to_string()),
+        outputs,
+        globs: Vec::new(),
+    })
+}
+
+fn to_display(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,141 @@
+use crate::{
+    get_candidate_targets, load_sources, select_files, target_label, ExpandedTarget, ExtractorKind,
+    Options, Selection,
+};
+use anyhow::{bail, Context, Result};
+use console::Style;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rslib::config::Config;
+use rslib::layouts::Layouts;
+use rslib::schema::Schema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The RSZ fields used by each `user` target as of the last time the baseline was updated.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    /// target name -> schema
+    pub targets: BTreeMap<String, Schema>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Collects the RSZ fields used by every `user` target selected by `options`, and prints how they
+/// differ from the baseline. If `update` is set, the baseline is replaced for the selected targets.
+///
+/// Updating can't be combined with options that select individual files, since a schema built from
+/// some of a target's files would replace the schema of the whole target.
+pub fn run(config: &Config, options: &Options, update: bool) -> Result<()> {
+    let heading = Style::new().bold();
+    let dim = Style::new().dim();
+
+    if update && (!options.only.is_empty() || !options.for_processor.is_empty()) {
+        bail!(
+            "--update replaces the baseline of whole targets, so it can't be combined with --only \
+            or --for-processor; use --target instead"
+        );
+    }
+
+    let selection = Selection::new(config, options)?;
+    let sources = load_sources(config)?;
+    let layouts = Layouts::load(&config.tools.rsz_layouts)?;
+
+    let baseline_path = &config.tools.rsz_schema;
+    let mut baseline = Baseline::load(baseline_path)?;
+
+    let kind = ExtractorKind::User;
+    let mut targets = get_candidate_targets(
+        &sources,
+        &selection,
+        config.user.input_prefix.as_deref(),
+        &config.user.targets,
+    )?;
+
+    select_files(config, &selection, &kind, &mut targets)?;
+
+    let mut changed = 0;
+    let mut missing = 0;
+
+    for ExpandedTarget {
+        target,
+        index,
+        files,
+    } in targets
+    {
+        let label = target_label(target, index, &kind);
+
+        let schema = files
+            .par_iter()
+            .map(|candidate| -> Result<Schema> {
+                let path = sources.resolve(candidate)?;
+
+                Schema::from_file(&layouts, &path)
+                    .with_context(|| format!("Could not read {}", candidate.key))
+            })
+            .try_reduce(Schema::default, |a, b| Ok(a.merge(b)))?;
+
+        match baseline.targets.get(&label) {
+            None => {
+                missing += 1;
+                println!(
+                    "{} {}",
+                    heading.apply_to(&label),
+                    dim.apply_to(format!("(no baseline, {} type(s) found)", schema.len()))
+                );
+            }
+            Some(old) => {
+                let changes = schema.diff(old);
+
+                if !changes.is_empty() {
+                    changed += 1;
+                    println!("{}", heading.apply_to(&label));
+
+                    for change in changes {
+                        println!("  {change}");
+                    }
+                }
+            }
+        }
+
+        if update {
+            baseline.targets.insert(label, schema);
+        }
+    }
+
+    if update {
+        baseline.save(baseline_path)?;
+        println!("Updated {}", baseline_path.display());
+    } else if changed > 0 || missing > 0 {
+        println!();
+        println!(
+            "{changed} target(s) changed since {} was last updated, and {missing} have no \
+            baseline yet. Document anything new in RESEARCH.md, then run again with --update to \
+            accept the changes.",
+            baseline_path.display()
+        );
+    } else {
+        println!(
+            "No changes since {} was last updated.",
+            baseline_path.display()
+        );
+    }
+
+    Ok(())
+}
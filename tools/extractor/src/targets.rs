@@ -1,42 +1,236 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::ValueEnum;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use rslib::formats::pak::{hash_path, FileList, Pak};
 use std::collections::HashMap;
+use std::fs;
 use std::path::{Path, PathBuf};
-use wax::Glob;
+use std::time::UNIX_EPOCH;
+use wax::{Glob, Pattern};
 
-pub fn find(paths: &[PathBuf], prefix: Option<&Path>, files: &[String]) -> Result<Vec<PathBuf>> {
-    let result = files
-        .par_iter()
-        .map(|file| descend(prefix, paths, file))
-        .collect::<Result<Vec<_>>>()?;
+/// The directory in the root of every `.pak` file that holds the game's files. Directory sources
+/// point at an extracted copy of this directory (e.g. `data/natives`).
+const PAK_ROOT: &str = "natives";
 
-    let mut buckets: HashMap<String, Vec<Candidate>> = HashMap::new();
+/// Appended to the name of each file unpacked to the cache to get the name of its stamp file.
+const STAMP_SUFFIX: &str = ".stamp";
 
-    for candidate in result.into_iter().flatten() {
-        buckets
-            .entry(candidate.key.to_string())
-            .or_default()
-            .push(candidate);
+/// A location to search for files, in order of priority.
+#[derive(Debug)]
+pub enum Source {
+    Directory(PathBuf),
+    Pak(Pak),
+}
+
+impl Source {
+    pub fn new(path: PathBuf) -> Result<Self> {
+        if path.is_file() && path.extension().is_some_and(|v| v == "pak") {
+            let pak =
+                Pak::open(&path).with_context(|| format!("Could not read {}", path.display()))?;
+
+            Ok(Self::Pak(pak))
+        } else {
+            Ok(Self::Directory(path))
+        }
     }
+}
 
-    Ok(buckets
-        .into_values()
-        .map(|v| v.into_iter().max_by_key(|v| v.priority).unwrap().path)
-        .collect())
+/// Every location to search for files, along with what's needed to read files out of `.pak`
+/// sources.
+#[derive(Debug)]
+pub struct Sources {
+    sources: Vec<Source>,
+    list: FileList,
+
+    /// Files read from `.pak` sources are unpacked here before being extracted.
+    cache: PathBuf,
+}
+
+impl Sources {
+    pub fn new(sources: Vec<Source>, list: Option<&Path>, cache: PathBuf) -> Result<Self> {
+        let has_paks = sources.iter().any(|v| matches!(v, Source::Pak(_)));
+
+        let list = match list {
+            Some(path) if has_paks => FileList::load(path)
+                .with_context(|| format!("Could not read {}", path.display()))?,
+            None if has_paks => {
+                bail!("`tools.pak_list` must be set to read files from .pak files")
+            }
+            _ => FileList::default(),
+        };
+
+        Ok(Self {
+            sources,
+            list,
+            cache,
+        })
+    }
+
+    /// Finds every file matching one of `files`. If more than one source contains the same file, the
+    /// one from the source listed first wins. Use [`Sources::resolve()`] to get a path that can be
+    /// read for each candidate.
+    pub fn find(&self, prefix: Option<&Path>, files: &[String]) -> Result<Vec<Candidate>> {
+        let mut buckets: HashMap<String, Vec<Candidate>> = HashMap::new();
+
+        for candidate in self.find_all(prefix, files)? {
+            buckets
+                .entry(candidate.key.to_string())
+                .or_default()
+                .push(candidate);
+        }
+
+        Ok(buckets
+            .into_values()
+            .map(|v| v.into_iter().max_by_key(|v| v.priority).unwrap())
+            .collect())
+    }
+
+    /// Like [`Sources::find()`], but returns a candidate for every source that contains a matching
+    /// file, instead of only the one that wins.
+    pub fn find_all(&self, prefix: Option<&Path>, files: &[String]) -> Result<Vec<Candidate>> {
+        let result = files
+            .par_iter()
+            .map(|file| self.descend(prefix, file))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    fn descend(&self, prefix: Option<&Path>, file: &str) -> Result<Vec<Candidate>> {
+        let result = self
+            .sources
+            .par_iter()
+            .enumerate()
+            .map(|(index, source)| {
+                let priority = self.sources.len() - index;
+
+                match source {
+                    Source::Directory(path) => walk(priority, index, prefix, path, file),
+                    Source::Pak(pak) => self.search(priority, index, prefix, pak, file),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(result.into_iter().flatten().collect())
+    }
+
+    fn search(
+        &self,
+        priority: usize,
+        source: usize,
+        prefix: Option<&Path>,
+        pak: &Pak,
+        glob: &str,
+    ) -> Result<Vec<Candidate>> {
+        let root = match prefix {
+            Some(v) => format!("{PAK_ROOT}/{}/", to_key(v)),
+            None => format!("{PAK_ROOT}/"),
+        };
+
+        let glob = Glob::new(glob)?;
+
+        Ok(pak
+            .files(&self.list)
+            .filter_map(|name| {
+                let key = name.strip_prefix(&root)?;
+
+                glob.is_match(key).then(|| Candidate {
+                    key: key.to_owned(),
+                    source,
+                    location: Location::Pak(name.to_owned()),
+                    priority,
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the path to read `candidate` from. Files that come from a `.pak` source are unpacked to
+    /// a directory in the cache that belongs to that archive. Next to each unpacked file is a stamp
+    /// recording the archive's size and modification time and the file's entry table record, and
+    /// the file is unpacked again if any of those have changed since.
+    pub fn resolve(&self, candidate: &Candidate) -> Result<PathBuf> {
+        let name = match &candidate.location {
+            Location::File(path) => return Ok(path.clone()),
+            Location::Pak(name) => name,
+        };
+
+        let Source::Pak(pak) = &self.sources[candidate.source] else {
+            unreachable!("pak candidates always point at a pak source");
+        };
+
+        let path = self.cache.join(cache_key(pak.path())?).join(name);
+        let stamp_path = path.with_file_name(format!(
+            "{}{STAMP_SUFFIX}",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+
+        let stamp = stamp(pak, name)?;
+        let is_fresh = path.is_file() && fs::read_to_string(&stamp_path).is_ok_and(|v| v == stamp);
+
+        if !is_fresh {
+            pak.unpack(name, &path).with_context(|| {
+                format!("Could not unpack {name} from {}", pak.path().display())
+            })?;
+
+            fs::write(&stamp_path, stamp)?;
+        }
+
+        Ok(path)
+    }
+
+    /// Returns the directory or `.pak` file that `candidate` was found in.
+    pub fn root(&self, candidate: &Candidate) -> &Path {
+        match &self.sources[candidate.source] {
+            Source::Directory(path) => path,
+            Source::Pak(pak) => pak.path(),
+        }
+    }
 }
 
-fn descend(prefix: Option<&Path>, paths: &[PathBuf], file: &str) -> Result<Vec<Candidate>> {
-    let result = paths
-        .par_iter()
-        .enumerate()
-        .map(|(index, path)| walk(paths.len() - index, prefix, path, file))
-        .collect::<Result<Vec<_>>>()?;
+/// Returns the name of the cache directory for files unpacked from the archive at `path`. Patch
+/// archives often hold files with the same name as the base archive, so each archive gets its own
+/// directory, named after the archive and a hash of its full path.
+fn cache_key(path: &Path) -> Result<String> {
+    let path = path.canonicalize()?;
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
 
-    Ok(result.into_iter().flatten().collect())
+    let hash = hash_path(&path.to_string_lossy());
+
+    Ok(format!("{name}-{hash:016x}"))
+}
+
+/// Describes the archive and entry table record a file was unpacked from. If a patch rewrites the
+/// archive, the file's offset, sizes, or checksum change along with it (and the archive's size or
+/// modification time does, too), even if the file itself keeps the same length.
+fn stamp(pak: &Pak, name: &str) -> Result<String> {
+    let metadata = pak.path().metadata()?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+
+    let Some(entry) = pak.entry(name) else {
+        bail!("{name} is not in {}", pak.path().display());
+    };
+
+    Ok(format!(
+        "{} {modified} {} {} {} {:016x}",
+        metadata.len(),
+        entry.offset,
+        entry.compressed_size,
+        entry.size,
+        entry.checksum
+    ))
 }
 
-fn walk(priority: usize, prefix: Option<&Path>, path: &Path, glob: &str) -> Result<Vec<Candidate>> {
+fn walk(
+    priority: usize,
+    source: usize,
+    prefix: Option<&Path>,
+    path: &Path,
+    glob: &str,
+) -> Result<Vec<Candidate>> {
     let path = match prefix {
         Some(v) => &path.join(v),
         None => path,
@@ -58,29 +252,41 @@ fn walk(priority: usize, prefix: Option<&Path>, path: &Path, glob: &str) -> Resu
                 Err(_) => return None,
             };
 
-            let key = item
-                .strip_prefix(path)
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .to_string();
+            let key = to_key(item.strip_prefix(path).unwrap());
 
             Some(Candidate {
                 key,
-                path: item,
+                source,
+                location: Location::File(item),
                 priority,
             })
         })
         .collect())
 }
 
+/// Normalizes path separators so that keys from directories match keys from `.pak` files.
+fn to_key(path: &Path) -> String {
+    path.to_str().unwrap().replace('\\', "/")
+}
+
 #[derive(Debug)]
 pub struct Candidate {
     pub key: String,
-    pub path: PathBuf,
+
+    /// The index of the source the file was found in.
+    pub source: usize,
+    pub location: Location,
     pub priority: usize,
 }
 
+#[derive(Debug)]
+pub enum Location {
+    File(PathBuf),
+
+    /// The name of a file inside of a `.pak` source.
+    Pak(String),
+}
+
 #[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
 pub enum TargetKind {
     All,
@@ -93,3 +299,101 @@ impl TargetKind {
         self == &Self::All || self == &other
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    const NAME: &str = "natives/STM/Fixture/Data.user.3";
+
+    /// Writes a version 4.1 archive holding `contents` as the only, uncompressed, file.
+    fn write_pak(path: &Path, contents: &[u8], checksum: u64) {
+        let size = contents.len() as u64;
+
+        let mut data = b"KPKA".to_vec();
+        data.extend([4, 1, 0, 0]);
+        data.extend(1u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+
+        data.extend(hash_path(NAME).to_le_bytes());
+        data.extend(64u64.to_le_bytes());
+        data.extend(size.to_le_bytes());
+        data.extend(size.to_le_bytes());
+        data.extend(0u64.to_le_bytes());
+        data.extend(checksum.to_le_bytes());
+        data.extend(contents);
+
+        fs::write(path, data).unwrap();
+    }
+
+    fn set_modified(path: &Path, modified: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    /// Opens the archive in `dir` from scratch, like a new extractor run would, and returns the
+    /// contents of the file it resolves to.
+    fn extract(dir: &Path) -> Vec<u8> {
+        let list = dir.join("files.list");
+        fs::write(&list, NAME).unwrap();
+
+        let source = Source::new(dir.join("re_chunk_000.pak")).unwrap();
+        let sources = Sources::new(vec![source], Some(&list), dir.join("cache")).unwrap();
+        let candidates = sources.find(None, &[String::from("**/*")]).unwrap();
+
+        fs::read(sources.resolve(&candidates[0]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn unpacks_entry_again_when_checksum_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("re_chunk_000.pak");
+
+        write_pak(&pak, b"old contents", 1);
+        assert_eq!(extract(dir.path()), b"old contents");
+
+        // Rewrite the entry in place with the same length, and keep the archive's timestamp.
+        let modified = pak.metadata().unwrap().modified().unwrap();
+        write_pak(&pak, b"new contents", 2);
+        set_modified(&pak, modified);
+
+        assert_eq!(extract(dir.path()), b"new contents");
+    }
+
+    #[test]
+    fn unpacks_entry_again_when_archive_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let pak = dir.path().join("re_chunk_000.pak");
+
+        write_pak(&pak, b"old contents", 0);
+        assert_eq!(extract(dir.path()), b"old contents");
+
+        // The entry table record doesn't change at all, only the archive's timestamp does.
+        let modified = pak.metadata().unwrap().modified().unwrap();
+        write_pak(&pak, b"new contents", 0);
+        set_modified(&pak, modified + Duration::from_secs(10));
+
+        assert_eq!(extract(dir.path()), b"new contents");
+    }
+
+    #[test]
+    fn reuses_unchanged_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        write_pak(&dir.path().join("re_chunk_000.pak"), b"contents", 0);
+
+        assert_eq!(extract(dir.path()), b"contents");
+
+        // The cache has a single directory, for the only archive.
+        let cache = fs::read_dir(dir.path().join("cache")).unwrap();
+        let archive_dir = cache.map(|v| v.unwrap().path()).next().unwrap();
+        fs::write(archive_dir.join(NAME), b"tampered").unwrap();
+
+        assert_eq!(extract(dir.path()), b"tampered");
+    }
+}
@@ -0,0 +1,393 @@
+use anyhow::bail;
+use clap::{Args, Subcommand, ValueEnum};
+use rayon::prelude::*;
+use regex::Regex;
+use rslib::config::Config;
+use rslib::formats::msg::{LanguageCode, Msg};
+use rslib::formats::user::User;
+use rslib::tools::{MsgExtractor, UserExtractor};
+use serde::Serialize;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use wax::Glob;
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Searches the English translations (and GUIDs) of `.msg` files.
+    Msg(MsgArgs),
+
+    /// Searches the fields of `.user` files. Every filter that's given must match for a field to be
+    /// included.
+    User(UserArgs),
+
+    /// Lists every field in `.user` files that references a GUID.
+    Refs(RefsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct Targets {
+    /// The target directory to scan.
+    pub target: PathBuf,
+
+    /// The glob to match against.
+    pub glob: String,
+
+    /// Exclude paths that match the pattern
+    #[arg(long, short = 'x')]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct MsgArgs {
+    #[command(flatten)]
+    pub targets: Targets,
+
+    pub pattern: String,
+
+    #[arg(long)]
+    pub regex: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct UserArgs {
+    #[command(flatten)]
+    pub targets: Targets,
+
+    /// The value to search for.
+    pub pattern: Option<String>,
+
+    #[arg(long)]
+    pub regex: bool,
+
+    /// Only match fields whose path matches a glob, e.g. `*._Skill[*]`. `*` matches any number of
+    /// characters, and `?` matches exactly one.
+    #[arg(long)]
+    pub field: Option<String>,
+
+    /// Only match numeric values in an inclusive range, e.g. `10..20`. Either end may be left off.
+    #[arg(long)]
+    pub range: Option<NumericRange>,
+}
+
+#[derive(Debug, Args)]
+pub struct RefsArgs {
+    #[command(flatten)]
+    pub targets: Targets,
+
+    /// The GUID to search for. Case is ignored.
+    pub guid: String,
+}
+
+#[derive(Debug, Args)]
+pub struct Options {
+    /// Hides files that couldn't be read or extracted.
+    #[arg(long, short)]
+    pub quiet: bool,
+
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    pub format: Format,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum Format {
+    /// A JSON array containing each matched file and its matches.
+    Json,
+
+    /// Each matched file followed by `path | value` lines.
+    Text,
+}
+
+/// Searches the files matched by `command` and prints every match to stdout.
+pub fn run(config: Config, command: Command, options: Options) -> anyhow::Result<()> {
+    let groups = match command {
+        Command::Msg(args) => do_msg_extract(config, args, options.quiet),
+        Command::User(args) => {
+            let filter = Filter::try_from(&args)?;
+            do_user_extract(config, &args.targets, &filter, options.quiet)
+        }
+        Command::Refs(args) => {
+            let filter = Filter {
+                value: Some(Matcher::Guid(args.guid.trim().to_owned())),
+                ..Default::default()
+            };
+
+            do_user_extract(config, &args.targets, &filter, options.quiet)
+        }
+    }?;
+
+    match options.format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&groups)?),
+        Format::Text => {
+            for group in groups {
+                println!("{}", group.path);
+
+                for item in group.matches {
+                    println!("{} | {}", item.path, item.value);
+                }
+
+                println!();
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn do_user_extract(
+    config: Config,
+    targets: &Targets,
+    filter: &Filter,
+    quiet: bool,
+) -> anyhow::Result<Vec<MatchGroup>> {
+    let extractor = UserExtractor::new(&config.tools.rsz_layouts)?;
+    let targets = get_targets(targets)?;
+
+    let groups: Result<Vec<_>, _> = targets
+        .into_par_iter()
+        .map(|path| -> anyhow::Result<Option<MatchGroup>> {
+            let out_path = path.with_extension("").with_extension("json");
+            let Ok(result) = extractor.run(&path, &out_path, None, None) else {
+                if !quiet {
+                    eprintln!("Could not read {path:?}");
+                }
+
+                return Ok(None);
+            };
+
+            if !result.exists() {
+                if !quiet {
+                    eprintln!("Could not extract file.");
+                }
+
+                return Ok(None);
+            }
+
+            let user: User = serde_json::from_reader(File::open(&result)?)?;
+
+            let matches: Vec<_> = user
+                .find_fields()
+                .into_par_iter()
+                .filter(|(k, v)| filter.is_match(k, v))
+                .map(|(k, v)| Match { path: k, value: v })
+                .collect();
+
+            Ok(MatchGroup::new(&path, matches))
+        })
+        .collect();
+
+    let mut groups: Vec<_> = groups?.into_iter().flatten().collect();
+    groups.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(groups)
+}
+
+fn get_targets(targets: &Targets) -> anyhow::Result<Vec<PathBuf>> {
+    let glob = Glob::new(&targets.glob)?;
+    let exclude: Vec<&str> = targets.exclude.iter().map(AsRef::as_ref).collect();
+    let targets = glob
+        .walk(std::env::current_dir()?.join(&targets.target))
+        .not(wax::any(exclude))?
+        .flat_map(|v| v.map(|v| v.into_path()))
+        .collect();
+
+    Ok(targets)
+}
+
+fn do_msg_extract(config: Config, args: MsgArgs, quiet: bool) -> anyhow::Result<Vec<MatchGroup>> {
+    let extractor = MsgExtractor::create(config.tools.msg.as_deref(), None);
+    let matcher = Matcher::new(&args.pattern, args.regex)?;
+    let targets = get_targets(&args.targets)?;
+
+    let groups: Result<Vec<_>, _> = targets
+        .into_par_iter()
+        .map(|path| -> anyhow::Result<Option<MatchGroup>> {
+            let result = extractor.run(&path, None::<&Path>)?;
+
+            let msg: Msg = serde_json::from_reader(File::open(&result)?)?;
+            let Some(lang_en) = msg.get_language_index(LanguageCode::English) else {
+                if !quiet {
+                    eprintln!("{path:?} does not contain English translations");
+                }
+
+                return Ok(None);
+            };
+
+            let matches: Vec<_> = msg
+                .entries
+                .into_par_iter()
+                .enumerate()
+                .flat_map(|(index, item)| {
+                    let value = item.get(lang_en)?;
+
+                    if matcher.is_match(&item.guid) || matcher.is_match(value) {
+                        Some(Match {
+                            path: index.to_string(),
+                            value: value.to_owned(),
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            Ok(MatchGroup::new(&path, matches))
+        })
+        .collect();
+
+    let mut groups: Vec<_> = groups?.into_iter().flatten().collect();
+    groups.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(groups)
+}
+
+#[derive(Debug, Serialize)]
+struct MatchGroup {
+    path: String,
+    matches: Vec<Match>,
+}
+
+impl MatchGroup {
+    /// Returns `None` if there's nothing to report for the file.
+    fn new(path: &Path, matches: Vec<Match>) -> Option<Self> {
+        if matches.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            path: path.to_string_lossy().into_owned(),
+            matches,
+        })
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Match {
+    path: String,
+    value: String,
+}
+
+enum Matcher {
+    Regex(Regex),
+    Literal(String),
+    Guid(String),
+}
+
+impl Matcher {
+    fn new(pattern: &str, regex: bool) -> anyhow::Result<Self> {
+        let result = if regex {
+            Self::Regex(Regex::new(pattern)?)
+        } else {
+            Self::Literal(pattern.to_owned())
+        };
+
+        Ok(result)
+    }
+
+    fn is_match(&self, other: &str) -> bool {
+        match self {
+            Self::Regex(regex) => regex.is_match(other),
+            Self::Literal(value) => value == other,
+            Self::Guid(value) => value.eq_ignore_ascii_case(other),
+        }
+    }
+}
+
+/// The conditions a `.user` field must meet to be included in the results.
+#[derive(Default)]
+struct Filter {
+    value: Option<Matcher>,
+    field: Option<Regex>,
+    range: Option<NumericRange>,
+}
+
+impl TryFrom<&UserArgs> for Filter {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &UserArgs) -> anyhow::Result<Self> {
+        if value.pattern.is_none() && value.field.is_none() && value.range.is_none() {
+            bail!("Nothing to search for; provide a pattern, --field, or --range");
+        }
+
+        Ok(Self {
+            value: value
+                .pattern
+                .as_deref()
+                .map(|v| Matcher::new(v, value.regex))
+                .transpose()?,
+            field: value.field.as_deref().map(field_glob).transpose()?,
+            range: value.range,
+        })
+    }
+}
+
+impl Filter {
+    fn is_match(&self, path: &str, value: &str) -> bool {
+        self.value.as_ref().is_none_or(|v| v.is_match(value))
+            && self.field.as_ref().is_none_or(|v| v.is_match(path))
+            && self.range.is_none_or(|v| v.contains(value))
+    }
+}
+
+/// Converts a field path glob into a regex that must match the entire path. Field paths look like
+/// `[0].app.user.ExampleData._Values[3]._Skill[1]`, so brackets and dots are matched literally.
+fn field_glob(glob: &str) -> anyhow::Result<Regex> {
+    let mut pattern = String::from("^");
+
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    pattern.push('$');
+
+    Ok(Regex::new(&pattern)?)
+}
+
+/// An inclusive range of numbers, parsed from `min..max`, `min..`, or `..max`.
+#[derive(Debug, Copy, Clone)]
+pub struct NumericRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl NumericRange {
+    /// Returns `true` if `value` is a number inside the range.
+    fn contains(&self, value: &str) -> bool {
+        let Ok(value) = value.parse::<f64>() else {
+            return false;
+        };
+
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+impl FromStr for NumericRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((min, max)) = s.split_once("..") else {
+            return Err(format!("expected a range like `10..20`, found `{s}`"));
+        };
+
+        let parse = |v: &str| -> Result<Option<f64>, String> {
+            let v = v.trim();
+
+            if v.is_empty() {
+                return Ok(None);
+            }
+
+            v.parse()
+                .map(Some)
+                .map_err(|_| format!("`{v}` is not a number"))
+        };
+
+        Ok(Self {
+            min: parse(min)?,
+            max: parse(max)?,
+        })
+    }
+}
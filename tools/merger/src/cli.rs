@@ -1,10 +1,14 @@
-use crate::processor::Processor;
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
+use merger::Processor;
+use mhdb_model::Language;
 use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(version, about)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     #[arg(short, long)]
     pub config: Option<PathBuf>,
 
@@ -13,4 +17,55 @@ pub struct Cli {
 
     #[arg(long, short)]
     pub filter: Vec<Processor>,
+
+    /// Verifies that the committed JSON Schema files match the ones generated from the current
+    /// output types, without merging anything.
+    #[arg(long)]
+    pub check_schema: bool,
+
+    /// Flattens localized fields to plain strings in a single language (e.g. `en`), overriding the
+    /// `[merger]` section of the config file.
+    #[arg(long)]
+    pub language: Option<Language>,
+
+    /// Exits with an error if any warnings were reported while merging. Errors always cause a
+    /// non-zero exit code, though the merged output is still written.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Checks the merged output for dangling references, duplicate IDs, and objects that are never
+    /// referenced. The report is printed to stdout as JSON.
+    Validate {
+        /// Treat unreferenced objects as errors.
+        #[arg(long)]
+        deny_orphans: bool,
+    },
+
+    /// Compares two `merged/` directories, reporting added, removed, and changed objects in each
+    /// file.
+    Diff {
+        /// The directory containing the older merged output.
+        old: PathBuf,
+
+        /// The directory containing the newer merged output.
+        new: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+
+    /// Exports the merged output to a SQLite database. An existing file at `path` is replaced.
+    ExportSqlite {
+        /// The path of the database file to write.
+        path: PathBuf,
+    },
+}
+
+#[derive(Debug, ValueEnum, Copy, Clone)]
+pub enum DiffFormat {
+    Text,
+    Json,
 }
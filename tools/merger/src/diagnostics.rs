@@ -0,0 +1,232 @@
+use crate::placeholders;
+use console::Style;
+use mhdb_model::Language;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Something was left out of the merged output, such as a row that references an object that
+    /// doesn't exist.
+    Error,
+
+    /// The merged output is complete, but part of it may be wrong, such as a placeholder that
+    /// couldn't be filled in.
+    Warning,
+}
+
+/// A single problem found while merging.
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The extracted file being read when the problem was found, relative to the output directory.
+    pub source: String,
+    /// The ID of the object the problem belongs to, if there is one.
+    pub entity: Option<String>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Attaches the ID of the object the problem belongs to.
+    pub fn entity<E: Display>(&mut self, entity: E) -> &mut Self {
+        self.entity = Some(entity.to_string());
+        self
+    }
+}
+
+/// A placeholder tag with no handler in [`placeholders::HANDLERS`], and where it was found.
+#[derive(Debug)]
+pub struct UnknownPlaceholder {
+    pub tag: String,
+    pub source: String,
+    pub entity: String,
+    pub language: Language,
+}
+
+/// Collects the problems found by processors, so that a single bad row doesn't abort the entire
+/// merge.
+#[derive(Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct Diagnostics {
+    entries: Vec<Diagnostic>,
+
+    #[serde(skip)]
+    unknown_placeholders: Vec<UnknownPlaceholder>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn error<S: AsRef<Path>, M: Display>(&mut self, source: S, message: M) -> &mut Diagnostic {
+        self.push(Severity::Error, source.as_ref(), message)
+    }
+
+    pub fn warning<S: AsRef<Path>, M: Display>(
+        &mut self,
+        source: S,
+        message: M,
+    ) -> &mut Diagnostic {
+        self.push(Severity::Warning, source.as_ref(), message)
+    }
+
+    /// Records each of `messages` as a warning against `entity`.
+    pub fn warn_all<S, E, I>(&mut self, source: S, entity: E, messages: I)
+    where
+        S: AsRef<Path>,
+        E: Display,
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        for message in messages {
+            self.warning(source.as_ref(), message).entity(&entity);
+        }
+    }
+
+    /// Records each of `errors` as a warning against `entity`, like [`Self::warn_all()`]. Any
+    /// unrecognized tags are also collected for the summary printed by [`Self::print_report()`].
+    pub fn warn_placeholders<S, E>(
+        &mut self,
+        source: S,
+        entity: E,
+        errors: Vec<placeholders::Error>,
+    ) where
+        S: AsRef<Path>,
+        E: Display,
+    {
+        for error in &errors {
+            if let placeholders::Error::Unrecognized { tag, language } = error {
+                self.unknown_placeholders.push(UnknownPlaceholder {
+                    tag: tag.to_owned(),
+                    source: display_path(source.as_ref()),
+                    entity: entity.to_string(),
+                    language: *language,
+                });
+            }
+        }
+
+        self.warn_all(source, entity, errors);
+    }
+
+    pub fn entries(&self) -> &[Diagnostic] {
+        &self.entries
+    }
+
+    pub fn unknown_placeholders(&self) -> &[UnknownPlaceholder] {
+        &self.unknown_placeholders
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    /// Returns `true` if the merge should be considered failed. Errors always fail the merge, and
+    /// warnings only do so if `strict` is set.
+    pub fn is_failure(&self, strict: bool) -> bool {
+        self.error_count() > 0 || (strict && self.warning_count() > 0)
+    }
+
+    /// Prints every diagnostic to stderr, followed by a one line summary.
+    pub fn print_report(&self) {
+        if self.is_empty() {
+            return;
+        }
+
+        for entry in &self.entries {
+            eprintln!("{entry}");
+        }
+
+        self.print_unknown_placeholders();
+
+        eprintln!(
+            "Merging finished with {} error(s) and {} warning(s).",
+            self.error_count(),
+            self.warning_count(),
+        );
+    }
+
+    /// Prints every unrecognized tag, grouped by tag and then by the object it was found in.
+    fn print_unknown_placeholders(&self) {
+        if self.unknown_placeholders.is_empty() {
+            return;
+        }
+
+        // tag -> (source, entity) -> languages
+        let mut tags: BTreeMap<&str, BTreeMap<(&str, &str), BTreeSet<Language>>> = BTreeMap::new();
+
+        for unknown in &self.unknown_placeholders {
+            tags.entry(&unknown.tag)
+                .or_default()
+                .entry((&unknown.source, &unknown.entity))
+                .or_default()
+                .insert(unknown.language);
+        }
+
+        eprintln!("Found {} unrecognized placeholder tag(s):", tags.len());
+
+        for (tag, uses) in tags {
+            eprintln!("  {tag}");
+
+            for ((source, entity), languages) in uses {
+                eprintln!("    {source} ({entity}): {languages:?}");
+            }
+        }
+    }
+
+    fn push(
+        &mut self,
+        severity: Severity,
+        source: &Path,
+        message: impl Display,
+    ) -> &mut Diagnostic {
+        self.entries.push(Diagnostic {
+            severity,
+            source: display_path(source),
+            entity: None,
+            message: message.to_string(),
+        });
+
+        self.entries.last_mut().expect("an entry was just pushed")
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.entries
+            .iter()
+            .filter(|v| v.severity == severity)
+            .count()
+    }
+}
+
+/// Formats `path` with forward slashes, so reports read the same on every platform.
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = match self.severity {
+            Severity::Error => Style::new().red().bold().apply_to("error"),
+            Severity::Warning => Style::new().yellow().bold().apply_to("warning"),
+        };
+
+        write!(f, "{label}: {}", self.source)?;
+
+        if let Some(entity) = &self.entity {
+            write!(f, " ({entity})")?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
@@ -0,0 +1,445 @@
+use anyhow::{bail, Context};
+use mhdb_model::Database;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// The fields used to identify an object across two versions of the same file. Most files are
+/// keyed by `game_id`; the ones listed here don't have one (or, in the case of songs, don't have a
+/// unique one).
+const IDENTITY_FIELDS: &[(&str, &[&str])] = &[
+    (Database::ARMOR_UPGRADES, &["rarity"]),
+    (Database::PART_NAMES, &["part"]),
+    (Database::SPECIES, &["kind"]),
+    (Database::SONGS, &["effect_id", "notes"]),
+];
+
+const DEFAULT_IDENTITY_FIELDS: &[&str] = &["game_id"];
+
+/// The differences between two `merged/` directories.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub files: Vec<FileDiff>,
+}
+
+impl Report {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileDiff {
+    pub file: &'static str,
+    pub added: Vec<Entity>,
+    pub removed: Vec<Entity>,
+    pub changed: Vec<EntityDiff>,
+}
+
+/// Identifies a single object in a file.
+#[derive(Debug, Serialize)]
+pub struct Entity {
+    pub id: String,
+    /// The object's English name, if it has one.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntityDiff {
+    #[serde(flatten)]
+    pub entity: Entity,
+    pub changes: Vec<Change>,
+}
+
+/// A single field-level change. Paths use `.` to separate object keys and array indexes, e.g.
+/// `crafting.zenny_cost` or `slots.1`.
+///
+/// Arrays of plain values (numbers, strings, and so on) are compared index by index, since their
+/// position usually matters (such as the level of each decoration slot). Arrays of objects are
+/// compared as collections, so an object gaining a new reward is reported as an [`Change::Added`]
+/// value at the array's path instead of as a change to every element after it, and an array whose
+/// objects only moved around is reported as [`Change::Reordered`].
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Change {
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Reordered {
+        path: String,
+    },
+}
+
+/// Compares every database file in `old` against the same file in `new`. Files that don't exist
+/// in either directory are skipped, and a file missing from only one side is treated as empty.
+///
+/// Returns an error if two objects in the same file share an ID, since they can't be told apart.
+pub fn diff<P: AsRef<Path>>(old: P, new: P) -> anyhow::Result<Report> {
+    let (old, new) = (old.as_ref(), new.as_ref());
+    let mut report = Report::default();
+
+    for file in Database::FILES {
+        let old = read_file(&old.join(file))?;
+        let new = read_file(&new.join(file))?;
+
+        if old.is_none() && new.is_none() {
+            continue;
+        }
+
+        let diff = diff_file(file, old.unwrap_or_default(), new.unwrap_or_default())?;
+
+        if !diff.added.is_empty() || !diff.removed.is_empty() || !diff.changed.is_empty() {
+            report.files.push(diff);
+        }
+    }
+
+    Ok(report)
+}
+
+fn read_file(path: &Path) -> anyhow::Result<Option<Vec<Value>>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let file = BufReader::new(File::open(path)?);
+    let values = serde_json::from_reader(file)
+        .with_context(|| format!("Could not parse {}", path.display()))?;
+
+    Ok(Some(values))
+}
+
+fn diff_file(file: &'static str, old: Vec<Value>, new: Vec<Value>) -> anyhow::Result<FileDiff> {
+    let fields = identity_fields(file);
+
+    let mut old: BTreeMap<String, Value> = index(file, "old", old, fields)?.into_iter().collect();
+    let new = index(file, "new", new, fields)?;
+
+    let mut diff = FileDiff {
+        file,
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    for (id, new) in new {
+        let Some(old) = old.remove(&id) else {
+            diff.added.push(entity(id, &new));
+            continue;
+        };
+
+        let mut changes = Vec::new();
+        diff_value(String::new(), &old, &new, &mut changes);
+
+        if !changes.is_empty() {
+            diff.changed.push(EntityDiff {
+                entity: entity(id, &new),
+                changes,
+            });
+        }
+    }
+
+    diff.removed = old.into_iter().map(|(id, v)| entity(id, &v)).collect();
+
+    Ok(diff)
+}
+
+/// Pairs every object in a file with its ID, keeping the order they appear in the file. `side` is
+/// only used for the error message.
+fn index(
+    file: &str,
+    side: &str,
+    values: Vec<Value>,
+    fields: &[&str],
+) -> anyhow::Result<Vec<(String, Value)>> {
+    let mut seen = BTreeSet::new();
+    let mut indexed = Vec::with_capacity(values.len());
+
+    for value in values {
+        let id = identify(&value, fields);
+
+        if !seen.insert(id.clone()) {
+            bail!("Duplicate ID {id} in the {side} version of {file}");
+        }
+
+        indexed.push((id, value));
+    }
+
+    Ok(indexed)
+}
+
+/// Returns the fields used to identify objects in `file`.
+pub(crate) fn identity_fields(file: &str) -> &'static [&'static str] {
+    IDENTITY_FIELDS
+        .iter()
+        .find(|(name, _)| *name == file)
+        .map_or(DEFAULT_IDENTITY_FIELDS, |(_, fields)| fields)
+}
+
+pub(crate) fn identify(value: &Value, fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| match value.get(field) {
+            Some(Value::String(value)) => value.to_owned(),
+            Some(value) => value.to_string(),
+            None => String::from("?"),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn entity(id: String, value: &Value) -> Entity {
+    let name = value
+        .pointer("/names/en")
+        .and_then(Value::as_str)
+        .map(str::to_owned);
+
+    Entity { id, name }
+}
+
+fn diff_value(path: String, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    if old == new {
+        return;
+    }
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => diff_object(&path, old, new, changes),
+        (Value::Array(old), Value::Array(new)) => diff_array(path, old, new, changes),
+        _ => changes.push(Change::Changed {
+            path,
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+fn diff_object(
+    path: &str,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    changes: &mut Vec<Change>,
+) {
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    for (key, new) in new {
+        match old.get(key) {
+            Some(old) => diff_value(child_path(key), old, new, changes),
+            None => changes.push(Change::Added {
+                path: child_path(key),
+                value: new.clone(),
+            }),
+        }
+    }
+
+    for (key, old) in old {
+        if !new.contains_key(key) {
+            changes.push(Change::Removed {
+                path: child_path(key),
+                value: old.clone(),
+            });
+        }
+    }
+}
+
+fn diff_array(path: String, old: &[Value], new: &[Value], changes: &mut Vec<Change>) {
+    let is_scalar = |v: &Value| !matches!(v, Value::Object(_) | Value::Array(_));
+
+    if old.iter().chain(new).all(is_scalar) {
+        return diff_positions(&path, old, new, changes);
+    }
+
+    let mut remaining: Vec<&Value> = old.iter().collect();
+    let mut added = Vec::new();
+
+    for value in new {
+        match remaining.iter().position(|v| *v == value) {
+            Some(index) => {
+                remaining.remove(index);
+            }
+            None => added.push(value),
+        }
+    }
+
+    // Same elements in a different order.
+    if added.is_empty() && remaining.is_empty() {
+        changes.push(Change::Reordered { path });
+        return;
+    }
+
+    for value in remaining {
+        changes.push(Change::Removed {
+            path: path.clone(),
+            value: value.clone(),
+        });
+    }
+
+    for value in added {
+        changes.push(Change::Added {
+            path: path.clone(),
+            value: value.clone(),
+        });
+    }
+}
+
+/// Compares two arrays of plain values index by index.
+fn diff_positions(path: &str, old: &[Value], new: &[Value], changes: &mut Vec<Change>) {
+    for index in 0..old.len().max(new.len()) {
+        let path = format!("{path}.{index}");
+
+        match (old.get(index), new.get(index)) {
+            (Some(old), Some(new)) if old != new => changes.push(Change::Changed {
+                path,
+                old: old.clone(),
+                new: new.clone(),
+            }),
+            (Some(old), None) => changes.push(Change::Removed {
+                path,
+                value: old.clone(),
+            }),
+            (None, Some(new)) => changes.push(Change::Added {
+                path,
+                value: new.clone(),
+            }),
+            _ => {}
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+
+        for file in &self.files {
+            writeln!(
+                f,
+                "{} ({} added, {} removed, {} changed)",
+                file.file,
+                file.added.len(),
+                file.removed.len(),
+                file.changed.len(),
+            )?;
+
+            for entity in &file.added {
+                writeln!(f, "  + {entity}")?;
+            }
+
+            for entity in &file.removed {
+                writeln!(f, "  - {entity}")?;
+            }
+
+            for diff in &file.changed {
+                writeln!(f, "  ~ {}", diff.entity)?;
+
+                for change in &diff.changes {
+                    writeln!(f, "      {change}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Entity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({name})", self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Changed { path, old, new } => write!(f, "{path}: {old} → {new}"),
+            Self::Added { path, value } => write!(f, "{path}: gained {value}"),
+            Self::Removed { path, value } => write!(f, "{path}: lost {value}"),
+            Self::Reordered { path } => write!(f, "{path}: reordered"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::diff_file;
+    use mhdb_model::Database;
+    use serde_json::{json, Value};
+
+    fn changes(old: Value, new: Value) -> Vec<String> {
+        let old = vec![json!({ "game_id": 1, "value": old })];
+        let new = vec![json!({ "game_id": 1, "value": new })];
+        let diff = diff_file(Database::GREAT_SWORDS, old, new).unwrap();
+
+        diff.changed
+            .iter()
+            .flat_map(|diff| &diff.changes)
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_ids_are_an_error() {
+        let old = vec![json!({ "game_id": 1 })];
+        let new = vec![
+            json!({ "game_id": 1 }),
+            json!({ "game_id": 1, "rarity": 2 }),
+        ];
+
+        let error = diff_file(Database::GREAT_SWORDS, old.clone(), new).unwrap_err();
+        assert!(error.to_string().contains("Duplicate ID 1"), "{error}");
+
+        let duplicated = vec![old[0].clone(), old[0].clone()];
+        assert!(diff_file(Database::GREAT_SWORDS, duplicated, old).is_err());
+    }
+
+    #[test]
+    fn scalar_arrays_are_compared_by_position() {
+        assert_eq!(
+            changes(json!([1, 2, 3]), json!([1, 3, 2])),
+            ["value.1: 2 → 3", "value.2: 3 → 2",]
+        );
+        assert_eq!(
+            changes(json!([1, 1]), json!([1, 1, 1])),
+            ["value.2: gained 1"]
+        );
+        assert_eq!(
+            changes(json!(["a", "b"]), json!(["a"])),
+            ["value.1: lost \"b\""]
+        );
+    }
+
+    #[test]
+    fn object_arrays_are_compared_as_collections() {
+        let a = json!({ "id": "a" });
+        let b = json!({ "id": "b" });
+        let c = json!({ "id": "c" });
+
+        assert_eq!(changes(json!([a, b]), json!([b, a])), ["value: reordered"]);
+        assert_eq!(
+            changes(json!([a, b]), json!([a, c])),
+            ["value: lost {\"id\":\"b\"}", "value: gained {\"id\":\"c\"}",]
+        );
+    }
+}
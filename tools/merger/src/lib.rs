@@ -0,0 +1,29 @@
+use crate::diagnostics::Diagnostics;
+use mhdb_model::Database;
+use rslib::config::Config;
+
+pub mod diagnostics;
+pub mod diff;
+pub mod localize;
+mod placeholders;
+pub mod processor;
+pub mod sqlite;
+pub mod validate;
+
+pub use processor::Processor;
+
+/// Runs the processors allowed by `filters` (or all of them, if `filters` is empty) and returns the
+/// merged data. Nothing is written to disk; use [`Database::write()`] to persist the result.
+///
+/// Problems with individual rows don't stop the merge. They're recorded in `diagnostics` instead,
+/// and the affected objects are skipped or left incomplete.
+pub fn run(
+    config: &Config,
+    filters: &[Processor],
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<Database> {
+    let mut db = Database::default();
+    processor::all(config, filters, &mut db, diagnostics)?;
+
+    Ok(db)
+}
@@ -0,0 +1,274 @@
+use crate::diff::{identify, identity_fields};
+use anyhow::bail;
+use mhdb_model::{Database, Language};
+use rslib::config::{Merger, StringLayout};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The fields that hold a `LanguageMap`, `RichTextMap`, or `VariantMap` in the model types.
+const LOCALIZED_FIELDS: &[&str] = &[
+    "names",
+    "descriptions",
+    "features",
+    "tips",
+    "condition",
+    "rich_descriptions",
+    "rich_features",
+    "rich_tips",
+    "descriptions_variants",
+    "features_variants",
+    "tips_variants",
+];
+
+/// The name of the directory holding per-language string files, relative to the `merged/`
+/// directory.
+pub const STRINGS_DIRECTORY: &str = "strings";
+
+/// The name of the file recording which layout the `merged/` directory was written with. It's
+/// only present for layouts other than [`Localization::Embedded`].
+pub const LAYOUT_FILE: &str = ".layout.json";
+
+/// Controls how localized fields are written to the merged output.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Localization {
+    /// Every localized field holds a map of language codes to strings. This is the only layout
+    /// that [`Database::load()`] can read back.
+    Embedded,
+
+    /// Localized fields are removed from the data files, and their values are written to
+    /// `strings/<lang>.json` instead. Each strings file is keyed by file (e.g. `Item` or
+    /// `weapons/Bow`), then by object ID, then by field path (e.g. `names` or `ranks.0.names`).
+    Split,
+
+    /// Localized fields are replaced by their value in a single language, or `null` if the field
+    /// has no value in that language.
+    Flatten(Language),
+}
+
+impl Localization {
+    /// Picks a layout from the `[merger]` section of the config. `language` overrides the config's
+    /// language, if given.
+    pub fn new(config: &Merger, language: Option<Language>) -> Self {
+        match (language.or(config.language), config.strings) {
+            (Some(language), _) => Self::Flatten(language),
+            (None, StringLayout::Embedded) => Self::Embedded,
+            (None, StringLayout::Split) => Self::Split,
+        }
+    }
+
+    /// Returns the layout a `merged/` directory was last written with.
+    pub fn current<P: AsRef<Path>>(dir: P) -> anyhow::Result<Self> {
+        let path = dir.as_ref().join(LAYOUT_FILE);
+
+        if !path.exists() {
+            return Ok(Self::Embedded);
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+/// Fails with an explanation if the `merged/` directory wasn't written with
+/// [`Localization::Embedded`], since that's the only layout [`Database::load()`] can read back.
+pub fn ensure_embedded<P: AsRef<Path>>(dir: P) -> anyhow::Result<()> {
+    let dir = dir.as_ref();
+    let layout = Localization::current(dir)?;
+
+    if layout != Localization::Embedded {
+        bail!(
+            "{} was written with the {layout:?} string layout, which can't be read back; re-run the \
+            merger with the default (embedded) layout first",
+            dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// language -> file -> object ID -> field path -> value
+type Strings = BTreeMap<String, BTreeMap<String, BTreeMap<String, BTreeMap<String, Value>>>>;
+
+/// Writes `db` to a `merged/` directory using the given layout for localized fields.
+///
+/// If `partial` is set, `db` only holds the output of some processors, and the files it doesn't
+/// cover are left as they are. That's only possible if the directory was last written with the
+/// same layout, since a directory can't mix layouts.
+pub fn write<P: AsRef<Path>>(
+    db: &Database,
+    dir: P,
+    localization: Localization,
+    partial: bool,
+) -> anyhow::Result<()> {
+    let dir = dir.as_ref();
+    let previous = Localization::current(dir)?;
+
+    if partial && previous != localization {
+        bail!(
+            "{} was written with the {previous:?} string layout; run every processor to switch it to \
+            the {localization:?} layout",
+            dir.display()
+        );
+    }
+
+    // Strings from a previous split run only stay relevant if this run is split, too.
+    let strings_dir = dir.join(STRINGS_DIRECTORY);
+
+    if localization != Localization::Split && strings_dir.exists() {
+        fs::remove_dir_all(&strings_dir)?;
+    }
+
+    let layout_path = dir.join(LAYOUT_FILE);
+
+    let language = match localization {
+        Localization::Embedded => {
+            db.write(dir)?;
+
+            if layout_path.exists() {
+                fs::remove_file(&layout_path)?;
+            }
+
+            return Ok(());
+        }
+        Localization::Split => None,
+        Localization::Flatten(language) => Some(serde_json::to_value(language)?),
+    };
+
+    // Start from the strings already on disk, so that a partial run keeps the strings of files it
+    // didn't write.
+    let mut strings = if localization == previous {
+        read_strings(&strings_dir)?
+    } else {
+        Strings::new()
+    };
+
+    for (file, mut section) in db.sections()? {
+        let entity = file.strip_suffix(".json").unwrap_or(file);
+        let fields = identity_fields(file);
+
+        for files in strings.values_mut() {
+            files.remove(entity);
+        }
+
+        for object in section.as_array_mut().into_iter().flatten() {
+            let id = identify(object, fields);
+
+            visit(object, "", &mut |path, values| {
+                let Some(language) = &language else {
+                    for (code, value) in values {
+                        // Skip strings for the "disabled" language, which has an empty code.
+                        if code.is_empty() {
+                            continue;
+                        }
+
+                        strings
+                            .entry(code)
+                            .or_default()
+                            .entry(entity.to_owned())
+                            .or_default()
+                            .entry(id.clone())
+                            .or_default()
+                            .insert(path.clone(), value);
+                    }
+
+                    return None;
+                };
+
+                let code = language.as_str().unwrap_or_default();
+                Some(values.get(code).cloned().unwrap_or(Value::Null))
+            });
+        }
+
+        write_file(&dir.join(file), &section)?;
+    }
+
+    for (code, entities) in strings {
+        write_file(&strings_dir.join(format!("{code}.json")), &entities)?;
+    }
+
+    write_file(&layout_path, &localization)?;
+
+    Ok(())
+}
+
+/// Reads every `<lang>.json` file in `dir`, if it exists.
+fn read_strings(dir: &Path) -> anyhow::Result<Strings> {
+    let mut strings = Strings::new();
+
+    if !dir.exists() {
+        return Ok(strings);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        let Some(code) = path
+            .file_name()
+            .and_then(|v| v.to_str())
+            .and_then(|v| v.strip_suffix(".json"))
+        else {
+            continue;
+        };
+
+        strings.insert(
+            code.to_owned(),
+            serde_json::from_str(&fs::read_to_string(&path)?)?,
+        );
+    }
+
+    Ok(strings)
+}
+
+/// Calls `replace` for every localized field in `value` with the field's path and its map of
+/// translations. The field is replaced by the returned value, or removed if `None` is returned.
+fn visit<F>(value: &mut Value, path: &str, replace: &mut F)
+where
+    F: FnMut(String, Map<String, Value>) -> Option<Value>,
+{
+    let child_path = |key: &str| {
+        if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{path}.{key}")
+        }
+    };
+
+    match value {
+        Value::Object(object) => {
+            for (key, child) in std::mem::take(object) {
+                let path = child_path(&key);
+
+                match child {
+                    Value::Object(values) if LOCALIZED_FIELDS.contains(&key.as_str()) => {
+                        if let Some(replacement) = replace(path, values) {
+                            object.insert(key, replacement);
+                        }
+                    }
+                    mut child => {
+                        visit(&mut child, &path, replace);
+                        object.insert(key, child);
+                    }
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (index, child) in values.iter_mut().enumerate() {
+                visit(child, &child_path(&index.to_string()), replace);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn write_file<T: Serialize + ?Sized>(path: &Path, value: &T) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
@@ -1,11 +1,13 @@
-use crate::cli::Cli;
+use crate::cli::{Cli, Command, DiffFormat};
+use anyhow::bail;
 use clap::Parser;
+use merger::diagnostics::Diagnostics;
+use merger::localize::Localization;
+use mhdb_model::Database;
 use rslib::config::Config;
+use std::path::Path;
 
 mod cli;
-mod placeholders;
-mod processor;
-mod serde;
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
@@ -15,5 +17,99 @@ fn main() -> anyhow::Result<()> {
         std::env::set_current_dir(cwd).expect("--cwd option specified an invalid path");
     }
 
-    processor::all(&config, &cli.filter)
+    match cli.command {
+        Some(Command::Validate { deny_orphans }) => return validate(&config, deny_orphans),
+        Some(Command::Diff { old, new, format }) => return diff(&old, &new, format),
+        Some(Command::ExportSqlite { path }) => return export_sqlite(&config, &path),
+        None => (),
+    }
+
+    let schema_dir = config.io.output.join(Database::SCHEMA_DIRECTORY);
+
+    if cli.check_schema {
+        let stale = Database::check_schemas(&schema_dir)?;
+
+        if stale.is_empty() {
+            println!("All schema files are up to date.");
+            return Ok(());
+        }
+
+        for path in &stale {
+            eprintln!("Schema is missing or out of date: {}", path.display());
+        }
+
+        bail!(
+            "{} schema file(s) differ from the generated schemas; re-run the merger to update them",
+            stale.len()
+        );
+    }
+
+    let mut diagnostics = Diagnostics::new();
+    let db = merger::run(&config, &cli.filter, &mut diagnostics)?;
+
+    let localization = Localization::new(&config.merger, cli.language);
+    merger::localize::write(
+        &db,
+        config.io.output.join(Database::DIRECTORY),
+        localization,
+        !cli.filter.is_empty(),
+    )?;
+    Database::write_schemas(&schema_dir)?;
+
+    diagnostics.print_report();
+
+    if diagnostics.is_failure(cli.strict) {
+        bail!("Merging finished with problems; see the report above");
+    }
+
+    Ok(())
+}
+
+fn validate(config: &Config, deny_orphans: bool) -> anyhow::Result<()> {
+    let dir = config.io.output.join(Database::DIRECTORY);
+    merger::localize::ensure_embedded(&dir)?;
+
+    let db = Database::load(dir)?;
+    let report = merger::validate::validate(&db);
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+
+    eprintln!(
+        "Found {} dangling reference(s), {} duplicate ID(s), and {} orphan(s).",
+        report.dangling.len(),
+        report.duplicates.len(),
+        report.orphans.len(),
+    );
+
+    if report.has_errors() || (deny_orphans && !report.orphans.is_empty()) {
+        bail!("Merged output failed validation");
+    }
+
+    Ok(())
+}
+
+fn diff(old: &Path, new: &Path, format: DiffFormat) -> anyhow::Result<()> {
+    merger::localize::ensure_embedded(old)?;
+    merger::localize::ensure_embedded(new)?;
+
+    let report = merger::diff::diff(old, new)?;
+
+    match format {
+        DiffFormat::Text => print!("{report}"),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+fn export_sqlite(config: &Config, path: &Path) -> anyhow::Result<()> {
+    let dir = config.io.output.join(Database::DIRECTORY);
+    merger::localize::ensure_embedded(&dir)?;
+
+    let db = Database::load(dir)?;
+    merger::sqlite::export(&db, path)?;
+
+    println!("Wrote {}", path.display());
+
+    Ok(())
 }
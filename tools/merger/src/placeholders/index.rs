@@ -0,0 +1,73 @@
+use mhdb_model::Language;
+use rslib::dump::{self, DumpFormat};
+use rslib::formats::msg::Msg;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Finds msg entries by name across every extracted `.msg` file, so that `<REF>` placeholders can
+/// be resolved no matter which file the referenced entry lives in.
+#[derive(Debug, Default)]
+pub struct ReferenceIndex {
+    files: Vec<Msg>,
+    /// Entry name -> (index into `files`, index into that file's entries)
+    names: HashMap<String, (usize, usize)>,
+}
+
+impl ReferenceIndex {
+    /// Reads every msg dump in `dir` and its subdirectories. Files are read in path order, and if
+    /// more than one file has an entry with the same name, the first one read wins.
+    pub fn load<P: AsRef<Path>>(dir: P) -> dump::Result<Self> {
+        let mut paths = BTreeSet::new();
+        find_dumps(dir.as_ref(), &mut paths)?;
+
+        let mut index = Self::default();
+
+        for path in paths {
+            let path = dump::locate(&path).unwrap_or(path);
+            index.add(dump::read(path)?);
+        }
+
+        Ok(index)
+    }
+
+    pub fn add(&mut self, msg: Msg) {
+        let file = self.files.len();
+
+        for (entry, value) in msg.entries.iter().enumerate() {
+            self.names
+                .entry(value.name.to_owned())
+                .or_insert((file, entry));
+        }
+
+        self.files.push(msg);
+    }
+
+    pub fn find(&self, name: &str, language: Language) -> Option<&str> {
+        let (file, entry) = *self.names.get(name)?;
+        let msg = &self.files[file];
+        let index = msg.get_language_index(language.into())?;
+
+        msg.entries[entry].get(index)
+    }
+}
+
+/// Adds the path of every dump under `dir` to `paths`, with its extension replaced by `.json` so
+/// that a file dumped in more than one format is only listed once.
+fn find_dumps(dir: &Path, paths: &mut BTreeSet<PathBuf>) -> dump::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            find_dumps(&path, paths)?;
+        } else if DumpFormat::detect(&path).is_ok() {
+            paths.insert(path.with_extension("json"));
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::placeholders::{ApplyContext, ApplyPlaceholder};
+use crate::placeholders::{ApplyContext, ApplyPlaceholder, Error, Result};
 use unicode_segmentation::UnicodeSegmentation;
 
 pub struct Listener {
@@ -52,12 +52,51 @@ impl Listener {
 }
 
 impl ApplyPlaceholder for Listener {
-    fn apply(&self, value: &str, _context: &ApplyContext<'_>) -> String {
+    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> Result {
         let options = self.options();
-        let replace = options
-            .first()
-            .expect("Listener options should not be empty");
+        let index = context.listener_option();
 
-        value.replace(&self.value, replace)
+        // Listeners with fewer options than the configured index fall back to their first one.
+        let Some(replace) = options.get(index).or(options.first()) else {
+            return Err(Error::EmptyListener(self.value.to_owned()));
+        };
+
+        Ok(value.replace(&self.value, replace))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::placeholders::{ApplyContext, Error, Placeholder};
+    use mhdb_model::{Language, LanguageMap, VariantMap};
+
+    #[test]
+    fn writes_every_form_and_reports_failures_once() {
+        let mut values = LanguageMap::from([(
+            Language::French,
+            String::from("Un <LSNR \"chasseur\" \"chasseuse\"> <NEW 1>"),
+        )]);
+        let mut variants = VariantMap::new();
+
+        let errors = Placeholder::process(&mut values, None, &mut variants, &ApplyContext::empty());
+
+        assert_eq!(values[&Language::French], "Un chasseur <NEW 1>");
+        assert_eq!(
+            variants[&Language::French],
+            ["Un chasseur <NEW 1>", "Un chasseuse <NEW 1>"]
+        );
+        assert!(matches!(errors[..], [Error::Unrecognized { .. }]));
+    }
+
+    #[test]
+    fn reports_empty_listener_once() {
+        let mut values =
+            LanguageMap::from([(Language::French, String::from("<LSNR \"a\" \"b\"> <LSNR>"))]);
+        let mut variants = VariantMap::new();
+
+        let errors = Placeholder::process(&mut values, None, &mut variants, &ApplyContext::empty());
+
+        assert_eq!(variants[&Language::French], ["a <LSNR>", "b <LSNR>"]);
+        assert!(matches!(errors[..], [Error::EmptyListener(_)]));
     }
 }
@@ -0,0 +1,62 @@
+use crate::placeholders::Item;
+use mhdb_model::Span;
+
+/// Splits `value` into spans of text with the same formatting, based on the `<BOLD>` and `<COLOR>`
+/// tags it contains. Any other placeholders are kept as part of the text.
+pub fn to_spans(value: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut bold = 0usize;
+    let mut colors: Vec<String> = Vec::new();
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let tag = rest.find(Item::BOUNDARY_START_CHAR).and_then(|start| {
+            let end = start + rest[start..].find(Item::BOUNDARY_END_CHAR)?;
+            Some((start, end))
+        });
+
+        let Some((start, end)) = tag else {
+            push(&mut spans, rest, bold > 0, colors.last());
+            break;
+        };
+
+        push(&mut spans, &rest[..start], bold > 0, colors.last());
+
+        let item = Item::new(&rest[start..=end]);
+
+        match item.kind() {
+            "BOLD" => bold += 1,
+            "/BOLD" => bold = bold.saturating_sub(1),
+            "COLOR" => colors.push(item.argument().to_owned()),
+            "/COLOR" => {
+                colors.pop();
+            }
+            _ => push(&mut spans, &item.value, bold > 0, colors.last()),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    spans
+}
+
+/// Appends `text` to the last span if it has the same formatting, or starts a new span if not.
+fn push(spans: &mut Vec<Span>, text: &str, bold: bool, color: Option<&String>) {
+    if text.is_empty() {
+        return;
+    }
+
+    if let Some(last) = spans.last_mut()
+        && last.bold == bold
+        && last.color.as_ref() == color
+    {
+        last.text.push_str(text);
+        return;
+    }
+
+    spans.push(Span {
+        text: text.to_owned(),
+        bold,
+        color: color.cloned(),
+    });
+}
@@ -1,60 +1,217 @@
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::listener::Listener;
 use crate::placeholders::reference::Reference;
-use crate::processor::{Language, LanguageMap};
+use crate::placeholders::value::Value;
+use mhdb_model::{Language, LanguageMap, RichTextMap, VariantMap};
 use rslib::formats::msg::Msg;
+use std::collections::HashMap;
 use unicode_segmentation::UnicodeSegmentation;
 
+pub mod index;
 pub mod listener;
+pub mod markup;
 pub mod reference;
+pub mod value;
+
+pub type Result<T = String> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unrecognized placeholder '{tag}' in language {language:?}")]
+    Unrecognized { tag: String, language: Language },
+
+    #[error("Reference does not match the expected pattern: '{0}'")]
+    MalformedReference(String),
+
+    #[error("Could not find reference entry for '{key}' in language {language:?}")]
+    MissingReference { key: String, language: Language },
+
+    #[error("Reference entry '{key}' refers back to itself: {chain}")]
+    CyclicReference { key: String, chain: String },
+
+    #[error("Listener has no options: '{0}'")]
+    EmptyListener(String),
+
+    #[error("No value to fill in numeric insert '{0}'")]
+    MissingValue(String),
+}
+
+/// Builds a placeholder from the full text of a tag, e.g. `<REF ...>`.
+pub type Handler = fn(String) -> Placeholder;
+
+/// The handler for each kind of tag. Tags of any other kind are left in the string as-is, and
+/// reported as [`Error::Unrecognized`] when applied.
+pub const HANDLERS: &[(&str, Handler)] = &[
+    ("REF", |value| Placeholder::Reference(Reference::new(value))),
+    ("LSNR", |value| Placeholder::Listener(Listener::new(value))),
+    ("BOLD", Placeholder::Remove),
+    ("/BOLD", Placeholder::Remove),
+    ("COLOR", Placeholder::Remove),
+    ("/COLOR", Placeholder::Remove),
+];
 
 pub enum Placeholder {
     Reference(Reference),
     Listener(Listener),
+    Value(Value),
     Remove(String),
+    Unrecognized(String),
 }
 
 impl Placeholder {
-    pub fn extract(value: &str) -> Vec<Self> {
+    /// Finds every placeholder in `value`. Numeric inserts such as `{0}` are only treated as
+    /// placeholders if `context` has values to fill them in with, so that strings which happen to
+    /// contain braces aren't reported as missing values.
+    pub fn extract(value: &str, context: &ApplyContext<'_>) -> Vec<Self> {
         let mut placeholders = Vec::new();
 
         for item in Item::extract(value) {
-            let placeholder = match item.kind() {
-                "REF" => Self::Reference(Reference::new(item.value)),
-                "LSNR" => Self::Listener(Listener::new(item.value)),
-                "BOLD" | "/BOLD" | "COLOR" | "/COLOR" => Self::Remove(item.value),
-                v => panic!("Unrecognized placeholder name '{v}'"),
+            let handler = HANDLERS
+                .iter()
+                .find(|(kind, _)| *kind == item.kind())
+                .map(|(_, handler)| handler);
+
+            let placeholder = match handler {
+                Some(handler) => handler(item.value),
+                None => Self::Unrecognized(item.value),
             };
 
             placeholders.push(placeholder);
         }
 
+        if !context.values.is_empty() {
+            placeholders.extend(Value::extract(value).into_iter().map(Self::Value));
+        }
+
         placeholders
     }
 
-    pub fn process(values: &mut LanguageMap, context: &ApplyContext<'_>) {
+    /// Fills in or removes every placeholder in `values`. Placeholders that can't be applied are
+    /// left as-is, and the reason each one failed is returned.
+    ///
+    /// If `rich` is given, each value is also split into spans (see [`markup::to_spans()`]) before
+    /// its formatting tags are removed, and the spans are added to `rich`.
+    ///
+    /// Values containing listeners with more than one option have every form of the value added
+    /// to `variants`, with the first form keeping the first option of each listener, and so on.
+    ///
+    /// Only descriptive fields are processed. Names are copied from the msg files as-is, since they
+    /// don't hold listener tags or references, so there's no `names_variants` field.
+    #[must_use]
+    pub fn process(
+        values: &mut LanguageMap,
+        mut rich: Option<&mut RichTextMap>,
+        variants: &mut VariantMap,
+        context: &ApplyContext<'_>,
+    ) -> Vec<Error> {
+        let mut errors = Vec::new();
+
         for (lang, value) in values {
-            let context = if context.reference_strings.is_empty() {
-                context
-            } else {
-                &context.with_lang(*lang)
-            };
+            // Formatting tags are needed to build the spans, which also remove them.
+            let mut context = context.with_lang(*lang);
+            context.keep_formatting = rich.is_some();
+
+            let context = &context;
+            let placeholders = Self::extract(value, context);
+
+            let variant_count = placeholders
+                .iter()
+                .filter_map(|v| match v {
+                    Self::Listener(listener) => Some(listener.options().len()),
+                    _ => None,
+                })
+                .max()
+                .unwrap_or_default();
+
+            let mut variant_errors = Vec::new();
 
-            let placeholders = Self::extract(value);
+            if variant_count > 1 {
+                let forms = (0..variant_count)
+                    .map(|index| {
+                        let mut context = context.with_listener_option(index);
+                        context.keep_formatting = false;
+
+                        Self::apply_all(value, &placeholders, &context, &mut variant_errors)
+                    })
+                    .collect();
+
+                variants.insert(*lang, forms);
+            }
+
+            let start = errors.len();
 
             for placeholder in placeholders {
-                let new_value = placeholder.apply(value, context);
-                *value = new_value;
+                match placeholder.apply(value, context) {
+                    Ok(new_value) => *value = new_value,
+                    Err(error) => errors.push(error),
+                }
+            }
+
+            // Every form applies the same placeholders as the value itself, so only report the
+            // failures that are specific to a form.
+            for error in variant_errors {
+                let message = error.to_string();
+
+                if !errors[start..].iter().any(|v| v.to_string() == message) {
+                    errors.push(error);
+                }
+            }
+
+            if let Some(rich) = rich.as_deref_mut() {
+                let spans = markup::to_spans(value);
+                *value = spans.iter().map(|v| v.text.as_str()).collect();
+
+                rich.insert(*lang, spans);
             }
         }
+
+        errors
+    }
+
+    /// Applies every placeholder in `value`, failing on the first one that can't be applied. Used
+    /// to fill in the text that a reference is replaced with.
+    pub fn expand(value: &str, context: &ApplyContext<'_>) -> Result {
+        Self::extract(value, context)
+            .iter()
+            .try_fold(value.to_owned(), |value, placeholder| {
+                placeholder.apply(&value, context)
+            })
+    }
+
+    /// Applies every placeholder in `placeholders` to `value`. Any that fail are left as-is, and
+    /// the reason each one failed is added to `errors`.
+    fn apply_all(
+        value: &str,
+        placeholders: &[Self],
+        context: &ApplyContext<'_>,
+        errors: &mut Vec<Error>,
+    ) -> String {
+        placeholders
+            .iter()
+            .fold(value.to_owned(), |value, placeholder| {
+                match placeholder.apply(&value, context) {
+                    Ok(new_value) => new_value,
+                    Err(error) => {
+                        errors.push(error);
+                        value
+                    }
+                }
+            })
     }
 }
 
 impl ApplyPlaceholder for Placeholder {
-    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> String {
+    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> Result {
         match self {
             Self::Listener(v) => v.apply(value, context),
             Self::Reference(v) => v.apply(value, context),
-            Self::Remove(pattern) => value.replace(pattern, ""),
+            Self::Value(v) => v.apply(value, context),
+            Self::Remove(_) if context.keep_formatting => Ok(value.to_owned()),
+            Self::Remove(pattern) => Ok(value.replace(pattern, "")),
+            Self::Unrecognized(pattern) => Err(Error::Unrecognized {
+                tag: pattern.to_owned(),
+                language: context.language,
+            }),
         }
     }
 }
@@ -105,11 +262,38 @@ impl Item {
         let end_index = self.value.find(' ').unwrap_or(self.value.len() - 1);
         &self.value[1..end_index]
     }
+
+    /// Returns everything after the kind, e.g. `FF0000` for `<COLOR FF0000>`.
+    fn argument(&self) -> &str {
+        match self.value.find(' ') {
+            Some(start) => &self.value[start + 1..self.value.len() - 1],
+            None => "",
+        }
+    }
 }
 
+#[derive(Clone)]
 pub struct ApplyContext<'a> {
+    /// Files to search for reference entries before falling back to `references`.
     pub reference_strings: Vec<&'a Msg>,
+    pub references: Option<&'a ReferenceIndex>,
     pub language: Language,
+
+    /// The values used to fill in numeric inserts such as `{0}`.
+    pub values: &'a [i32],
+
+    /// The option to keep from each listener, by language. Languages that aren't listed keep the
+    /// first option.
+    pub listener_options: Option<&'a HashMap<Language, usize>>,
+
+    /// If set, overrides `listener_options` for every language.
+    listener_option: Option<usize>,
+
+    /// If `true`, formatting tags are left in place instead of being removed.
+    keep_formatting: bool,
+
+    /// The names of the references currently being expanded, outermost first.
+    reference_stack: Vec<String>,
 }
 
 impl<'a> ApplyContext<'a> {
@@ -120,17 +304,81 @@ impl<'a> ApplyContext<'a> {
     pub fn new(reference_strings: Vec<&'a Msg>) -> Self {
         Self {
             reference_strings,
+            references: None,
             language: Language::Disabled,
+            values: &[],
+            listener_options: None,
+            listener_option: None,
+            keep_formatting: false,
+            reference_stack: Vec::new(),
         }
     }
 
     pub fn with_lang(&self, language: Language) -> Self {
         Self {
             language,
-            reference_strings: self.reference_strings.clone(),
+            ..self.clone()
         }
     }
 
+    pub fn with_references(self, references: &'a ReferenceIndex) -> Self {
+        Self {
+            references: Some(references),
+            ..self
+        }
+    }
+
+    pub fn with_values(&self, values: &'a [i32]) -> Self {
+        Self {
+            values,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_listener_options(self, listener_options: &'a HashMap<Language, usize>) -> Self {
+        Self {
+            listener_options: Some(listener_options),
+            ..self
+        }
+    }
+
+    /// Returns a copy of the context that keeps option `index` of every listener, regardless of
+    /// language.
+    pub fn with_listener_option(&self, index: usize) -> Self {
+        Self {
+            listener_option: Some(index),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the index of the option to keep from each listener in the context's language.
+    pub fn listener_option(&self) -> usize {
+        self.listener_option
+            .or_else(|| self.listener_options?.get(&self.language).copied())
+            .unwrap_or_default()
+    }
+
+    /// Returns a copy of the context for expanding the reference entry `key`, or an error if `key`
+    /// is already being expanded.
+    pub fn enter_reference(&self, key: &str) -> Result<Self> {
+        let mut reference_stack = self.reference_stack.clone();
+        let is_cycle = reference_stack.iter().any(|v| v == key);
+
+        reference_stack.push(key.to_owned());
+
+        if is_cycle {
+            return Err(Error::CyclicReference {
+                key: key.to_owned(),
+                chain: reference_stack.join(" -> "),
+            });
+        }
+
+        Ok(Self {
+            reference_stack,
+            ..self.clone()
+        })
+    }
+
     pub fn find_reference(&self, name: &str) -> Option<&str> {
         let lang = self.language.into();
 
@@ -140,10 +388,10 @@ impl<'a> ApplyContext<'a> {
             }
         }
 
-        None
+        self.references?.find(name, self.language)
     }
 }
 
 pub trait ApplyPlaceholder {
-    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> String;
+    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> Result;
 }
@@ -1,4 +1,4 @@
-use crate::placeholders::{ApplyContext, ApplyPlaceholder};
+use crate::placeholders::{ApplyContext, ApplyPlaceholder, Error, Placeholder, Result};
 
 #[derive(Debug)]
 pub struct Reference {
@@ -10,28 +10,30 @@ impl Reference {
         Self { value }
     }
 
-    pub fn key(&self) -> &str {
+    pub fn key(&self) -> Result<&str> {
         let Some(start) = self.value.find(' ') else {
-            panic!(
-                "Reference does not match the expected pattern: '{}'",
-                self.value
-            );
+            return Err(Error::MalformedReference(self.value.to_owned()));
         };
 
-        &self.value[start + 1..self.value.len() - 1]
+        Ok(&self.value[start + 1..self.value.len() - 1])
     }
 }
 
 impl ApplyPlaceholder for Reference {
-    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> String {
-        let key = self.key();
+    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> Result {
+        let key = self.key()?;
+        let context = context.enter_reference(key)?;
+
         let Some(replace) = context.find_reference(key) else {
-            panic!(
-                "Could not find reference entry for '{key}' in language {:?}",
-                context.language
-            );
+            return Err(Error::MissingReference {
+                key: key.to_owned(),
+                language: context.language,
+            });
         };
 
-        value.replace(&self.value, replace)
+        // The referenced entry can contain placeholders of its own, including other references.
+        let replace = Placeholder::expand(replace, &context)?;
+
+        Ok(value.replace(&self.value, &replace))
     }
 }
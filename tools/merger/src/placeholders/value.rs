@@ -0,0 +1,108 @@
+use crate::placeholders::{ApplyContext, ApplyPlaceholder, Error, Result};
+
+/// A numeric insert such as `{0}`, which is replaced by the value at that index in
+/// [`ApplyContext::values`].
+pub struct Value {
+    pub value: String,
+    index: usize,
+}
+
+impl Value {
+    /// Finds every numeric insert in `value`.
+    pub fn extract(value: &str) -> Vec<Self> {
+        let mut matches = Vec::new();
+        let mut rest = value;
+
+        while let Some(start) = rest.find('{') {
+            rest = &rest[start..];
+
+            let Some(end) = rest.find('}') else {
+                break;
+            };
+
+            let insert = &rest[..=end];
+            let digits = &insert[1..end];
+
+            match digits.parse() {
+                Ok(index) if digits.bytes().all(|v| v.is_ascii_digit()) => {
+                    matches.push(Self {
+                        value: insert.to_owned(),
+                        index,
+                    });
+
+                    rest = &rest[end + 1..];
+                }
+                _ => rest = &rest[1..],
+            }
+        }
+
+        matches
+    }
+}
+
+impl ApplyPlaceholder for Value {
+    fn apply(&self, value: &str, context: &ApplyContext<'_>) -> Result {
+        let Some(replace) = context.values.get(self.index) else {
+            return Err(Error::MissingValue(self.value.to_owned()));
+        };
+
+        Ok(value.replace(&self.value, &replace.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::placeholders::{ApplyContext, Placeholder};
+    use mhdb_model::{Language, LanguageMap, VariantMap};
+
+    /// `_value` of Resentment's first rank in `SkillData.user.3`.
+    const RESENTMENT_VALUES: [i32; 4] = [5, 0, 0, 0];
+
+    fn process(description: &str, context: &ApplyContext<'_>) -> (String, usize) {
+        let mut values = LanguageMap::from([(Language::English, description.to_owned())]);
+        let errors = Placeholder::process(&mut values, None, &mut VariantMap::new(), context);
+
+        (values.remove(&Language::English).unwrap(), errors.len())
+    }
+
+    #[test]
+    fn fills_in_rank_values() {
+        let context = ApplyContext::empty();
+        let context = context.with_values(&RESENTMENT_VALUES);
+
+        assert_eq!(
+            process("Attack +{0} while active.", &context),
+            (String::from("Attack +5 while active."), 0)
+        );
+    }
+
+    #[test]
+    fn leaves_real_rank_description_alone() {
+        let context = ApplyContext::empty();
+        let context = context.with_values(&RESENTMENT_VALUES);
+
+        assert_eq!(
+            process("Attack +5 while active.", &context),
+            (String::from("Attack +5 while active."), 0)
+        );
+    }
+
+    #[test]
+    fn reports_inserts_past_the_end_of_the_values() {
+        let context = ApplyContext::empty();
+        let context = context.with_values(&RESENTMENT_VALUES);
+
+        assert_eq!(
+            process("Attack +{4}", &context),
+            (String::from("Attack +{4}"), 1)
+        );
+    }
+
+    #[test]
+    fn ignores_inserts_without_values() {
+        assert_eq!(
+            process("Attack +{0} while active.", &ApplyContext::empty()),
+            (String::from("Attack +{0} while active."), 0)
+        );
+    }
+}
@@ -1,26 +1,35 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
 use crate::processor::{
-    to_ingame_rarity, IconColor, IdMap, LanguageMap, PopulateStrings, Processor, ReadFile, Result,
-    WriteFile,
+    to_ingame_rarity, IconColorCode, IdMap, LanguageMap, PopulateStrings, Processor, ReadFile,
+    Result,
 };
-use crate::serde::ordered_map;
 use crate::should_run;
 use indicatif::ProgressBar;
+use mhdb_model::accessories::{Accessory, AllowedOn};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::accessories::{DATA, STRINGS};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
-const DATA: &str = "user/AccessoryData.json";
-const STRINGS: &str = "msg/Accessory.json";
-
-const OUTPUT: &str = "merged/Accessory.json";
-
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
+pub fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> Result {
     should_run!(filters, Processor::Accessories);
 
     let data: Vec<AccessoryData> = Vec::read_file(config.io.output.join(DATA))?;
     let strings = Msg::read_file(config.io.output.join(STRINGS))?;
+    let rich_text = config.merger.rich_text;
+    let placeholder_context = ApplyContext::empty()
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
 
     let progress = ProgressBar::new(data.len() as u64);
 
@@ -34,7 +43,13 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         strings.populate(&data.name_guid, &mut accessory.names);
 
         strings.populate(&data.description_guid, &mut accessory.descriptions);
-        Placeholder::process(&mut accessory.descriptions, &ApplyContext::empty());
+        let errors = Placeholder::process(
+            &mut accessory.descriptions,
+            rich_text.then_some(&mut accessory.rich_descriptions),
+            &mut accessory.descriptions_variants,
+            &placeholder_context,
+        );
+        diagnostics.warn_placeholders(STRINGS, accessory.game_id, errors);
 
         for (id, level) in data.skill_ids.iter().zip(data.skill_levels) {
             if *id != 0 {
@@ -48,24 +63,9 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     progress.finish_and_clear();
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(OUTPUT))
-}
+    db.accessories = merged;
 
-#[derive(Debug, Serialize)]
-struct Accessory {
-    game_id: isize,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    rarity: u8,
-    price: u16,
-    level: u8,
-    #[serde(serialize_with = "ordered_map")]
-    skills: IdMap,
-    allowed_on: AllowedOn,
-    icon_color: IconColor,
-    icon_color_id: u8,
+    Ok(())
 }
 
 impl From<&AccessoryData> for Accessory {
@@ -77,9 +77,11 @@ impl From<&AccessoryData> for Accessory {
             level: value.level,
             names: LanguageMap::new(),
             descriptions: LanguageMap::new(),
+            rich_descriptions: RichTextMap::new(),
+            descriptions_variants: VariantMap::new(),
             skills: IdMap::new(),
             allowed_on: value.allowed_on.into(),
-            icon_color: value.icon_color,
+            icon_color: value.icon_color.into(),
             icon_color_id: value.icon_color as u8,
         }
     }
@@ -106,7 +108,7 @@ struct AccessoryData {
     #[serde(rename = "_AccessoryType")]
     allowed_on: AllowedOnCode,
     #[serde(rename = "_IconColor")]
-    icon_color: IconColor,
+    icon_color: IconColorCode,
 }
 
 #[derive(Debug, Deserialize_repr, Copy, Clone)]
@@ -116,13 +118,6 @@ enum AllowedOnCode {
     Weapon = -1638455296,
 }
 
-#[derive(Debug, Serialize, Copy, Clone)]
-#[serde(rename_all = "kebab-case")]
-enum AllowedOn {
-    Armor,
-    Weapon,
-}
-
 impl From<AllowedOnCode> for AllowedOn {
     fn from(value: AllowedOnCode) -> Self {
         match value {
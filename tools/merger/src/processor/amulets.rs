@@ -1,26 +1,34 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
 use crate::processor::{
-    to_ingame_rarity, IdMap, LanguageMap, PopulateStrings, Processor, ReadFile, Result, WriteFile,
+    to_ingame_rarity, IdMap, LanguageMap, PopulateStrings, Processor, ReadFile, Result,
 };
-use crate::serde::ordered_map;
 use crate::should_run;
 use indicatif::ProgressBar;
+use mhdb_model::amulets::{Amulet, Rank, Recipe};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::amulets::{DATA, RECIPES, STRINGS};
+use serde::Deserialize;
 use std::collections::HashMap;
 
-const DATA: &str = "user/AmuletData.json";
-const STRINGS: &str = "msg/Amulet.json";
-const RECIPES: &str = "user/AmuletRecipeData.json";
-
-const OUTPUT: &str = "merged/Amulet.json";
-
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
+pub fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> Result {
     should_run!(filters, Processor::Amulets);
 
     let data: Vec<AmuletData> = Vec::read_file(config.io.output.join(DATA))?;
     let strings = Msg::read_file(config.io.output.join(STRINGS))?;
+    let rich_text = config.merger.rich_text;
+    let placeholder_context = ApplyContext::empty()
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
 
     let mut merged: Vec<Amulet> = Vec::with_capacity(data.len());
     let mut lookup: HashMap<isize, usize> = HashMap::new();
@@ -52,7 +60,13 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         strings.populate(&data.name_guid, &mut rank.names);
 
         strings.populate(&data.description_guid, &mut rank.descriptions);
-        Placeholder::process(&mut rank.descriptions, &ApplyContext::empty());
+        let errors = Placeholder::process(
+            &mut rank.descriptions,
+            rich_text.then_some(&mut rank.rich_descriptions),
+            &mut rank.descriptions_variants,
+            &placeholder_context,
+        );
+        diagnostics.warn_placeholders(STRINGS, data.group_id, errors);
 
         for (id, level) in data.skill_ids.into_iter().zip(data.skill_levels) {
             if id != 0 {
@@ -101,28 +115,9 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     }
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(OUTPUT))
-}
-
-#[derive(Debug, Serialize)]
-struct Amulet {
-    game_id: isize,
-    is_random: bool,
-    ranks: Vec<Rank>,
-}
+    db.amulets = merged;
 
-#[derive(Debug, Serialize)]
-struct Rank {
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    rarity: u8,
-    level: u8,
-    price: usize,
-    #[serde(serialize_with = "ordered_map")]
-    skills: IdMap,
-    recipe: Recipe,
+    Ok(())
 }
 
 impl From<&AmuletData> for Rank {
@@ -133,17 +128,14 @@ impl From<&AmuletData> for Rank {
             price: value.price,
             names: LanguageMap::new(),
             descriptions: LanguageMap::new(),
+            rich_descriptions: RichTextMap::new(),
+            descriptions_variants: VariantMap::new(),
             skills: IdMap::new(),
             recipe: Recipe::default(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Default)]
-struct Recipe {
-    #[serde(serialize_with = "ordered_map")]
-    inputs: IdMap,
-}
 // 353607840 -1608406656 837371776 -1382563840
 #[derive(Debug, Deserialize)]
 struct AmuletData {
@@ -1,30 +1,33 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
 use crate::processor::{
-    to_ingame_rarity, IdMap, LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile, Result,
-    WriteFile,
+    to_ingame_rarity, IdMap, LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile,
+    Result,
 };
-use crate::serde::ordered_map;
 use crate::should_run;
 use indicatif::ProgressBar;
+use mhdb_model::armor::{
+    Armor, Crafting, Defense, PartKind, Resistances, Set, Upgrade, UpgradeStep,
+};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::armor::{
+    ARMOR_DATA, ARMOR_STRINGS, RECIPE_DATA, SERIES_DATA, SERIES_STRINGS, UPGRADE_DATA,
+};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 use std::collections::HashMap;
 
-const SERIES_DATA: &str = "user/ArmorSeriesData.json";
-const ARMOR_DATA: &str = "user/ArmorData.json";
-const RECIPE_DATA: &str = "user/ArmorRecipeData.json";
-const UPGRADE_DATA: &str = "user/ArmorUpgradeData.json";
-
-const SERIES_STRINGS: &str = "msg/ArmorSeries.json";
-const ARMOR_STRINGS: &str = "msg/Armor.json";
-
-pub const OUTPUT: &str = "merged/Armor.json";
-pub const UPGRADE_OUTPUT: &str = "merged/ArmorUpgrade.json";
-
 /// Armor set and group bonuses are added by the [skills::process()] function.
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
+pub fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> Result {
     should_run!(filters, Processor::Armor);
 
     let data: Vec<SeriesData> = Vec::read_file(config.io.output.join(SERIES_DATA))?;
@@ -33,6 +36,9 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     let mut merged: Vec<Set> = Vec::with_capacity(data.len());
     let mut set_lookup = LookupMap::with_capacity(data.len());
 
+    // Set prices are applied to each piece's crafting info, but aren't part of the set output.
+    let mut set_prices: HashMap<isize, usize> = HashMap::with_capacity(data.len());
+
     let progress = ProgressBar::new(data.len() as u64);
 
     for data in data {
@@ -47,6 +53,7 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         strings.populate(&data.name_guid, &mut set.names);
 
         set_lookup.insert(data.id, merged.len());
+        set_prices.insert(data.id, data.price);
         merged.push(set);
     }
 
@@ -81,6 +88,10 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
 
     let data: Vec<ArmorData> = Vec::read_file(config.io.output.join(ARMOR_DATA))?;
     let strings = Msg::read_file(config.io.output.join(ARMOR_STRINGS))?;
+    let rich_text = config.merger.rich_text;
+    let placeholder_context = ApplyContext::empty()
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
 
     let progress = ProgressBar::new(data.len() as u64);
 
@@ -97,7 +108,13 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         strings.populate(&data.name_guid, &mut armor.names);
 
         strings.populate(&data.description_guid, &mut armor.descriptions);
-        Placeholder::process(&mut armor.descriptions, &ApplyContext::empty());
+        let errors = Placeholder::process(
+            &mut armor.descriptions,
+            rich_text.then_some(&mut armor.rich_descriptions),
+            &mut armor.descriptions_variants,
+            &placeholder_context,
+        );
+        diagnostics.warn_placeholders(ARMOR_STRINGS, piece_id(&data), errors);
 
         for (id, level) in data.skill_ids.into_iter().zip(data.skill_levels) {
             if id != 0 {
@@ -105,17 +122,34 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
             }
         }
 
-        let set = set_lookup
-            .find_in_mut(data.series_id, &mut merged)
-            .unwrap_or_else(|| panic!("Could not find set by ID: {}", data.series_id));
+        let Some(set) = set_lookup.find_in_mut(data.series_id, &mut merged) else {
+            diagnostics
+                .error(
+                    ARMOR_DATA,
+                    format!("Could not find set by ID {}", data.series_id),
+                )
+                .entity(piece_id(&data));
 
-        armor.crafting.price = set.price;
+            continue;
+        };
 
-        let upgrade = upgrades
-            .get(&set.rarity)
-            .unwrap_or_else(|| panic!("Could not find upgrade data for rarity {}", set.rarity));
+        armor.crafting.price = set_prices[&data.series_id];
 
-        armor.defense.max = armor.defense.base + upgrade.get_total_defense_bonus();
+        match upgrades.get(&set.rarity) {
+            Some(upgrade) => {
+                armor.defense.max = armor.defense.base + upgrade.get_total_defense_bonus();
+            }
+            None => {
+                armor.defense.max = armor.defense.base;
+
+                diagnostics
+                    .warning(
+                        UPGRADE_DATA,
+                        format!("Could not find upgrade data for rarity {}", set.rarity),
+                    )
+                    .entity(piece_id(&data));
+            }
+        }
 
         set.pieces.push(armor);
     }
@@ -128,20 +162,26 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     for data in data {
         progress.inc(1);
 
-        let set = set_lookup
-            .find_in_mut(data.series_id, &mut merged)
-            .unwrap_or_else(|| panic!("Could not find set by ID: {}", data.series_id));
+        let Some(set) =
+            set_lookup.find_or_report_mut(data.series_id, &mut merged, diagnostics, RECIPE_DATA)
+        else {
+            continue;
+        };
 
-        let piece = set
+        let Some(piece) = set
             .pieces
             .iter_mut()
-            .find(|v| v.kind == data.part_kind)
-            .unwrap_or_else(|| {
-                panic!(
-                    "Could not find {:?} in armor set {}",
-                    data.part_kind, set.game_id
+            .find(|v| v.kind == PartKind::from(data.part_kind))
+        else {
+            diagnostics
+                .error(
+                    RECIPE_DATA,
+                    format!("Could not find {:?} in armor set", data.part_kind),
                 )
-            });
+                .entity(set.game_id);
+
+            continue;
+        };
 
         for (id, amount) in data.input_ids.into_iter().zip(data.input_amounts) {
             if id != 0 {
@@ -152,43 +192,23 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
 
     progress.finish_and_clear();
 
-    let mut upgrades: Vec<_> = upgrades.values().collect();
+    let mut upgrades: Vec<_> = upgrades.into_values().collect();
     upgrades.sort_by_key(|v| v.rarity);
 
-    upgrades.write_file(config.io.output.join(UPGRADE_OUTPUT))?;
+    db.armor_upgrades = upgrades;
 
     for set in merged.iter_mut() {
         set.pieces.sort_by_key(|v| v.kind);
     }
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(OUTPUT))
-}
+    db.armor = merged;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Set {
-    game_id: isize,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    rarity: u8,
-    #[deprecated(
-        since = "1.41.2-wilds",
-        note = "Will be replaced by `set_bonus_id` on or after 2026-05-01"
-    )]
-    pub set_bonus: Option<Bonus>,
-    pub set_bonus_id: Option<isize>,
-    #[deprecated(
-        since = "1.41.2-wilds",
-        note = "Will be replaced by `group_bonus_id` on or after 2026-05-01"
-    )]
-    pub group_bonus: Option<Bonus>,
-    pub group_bonus_id: Option<isize>,
-    pub pieces: Vec<Armor>,
-    #[serde(skip)]
-    price: usize,
-    model_id: usize,
+    Ok(())
 }
 
+// The deprecated bonus fields still need to be initialized until they're removed.
+#[allow(deprecated)]
 impl From<&SeriesData> for Set {
     fn from(value: &SeriesData) -> Self {
         Self {
@@ -200,37 +220,14 @@ impl From<&SeriesData> for Set {
             group_bonus_id: None,
             names: LanguageMap::new(),
             pieces: Vec::new(),
-            price: value.price,
             model_id: value.model_id,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Bonus {
-    pub skill_id: isize,
-    pub ranks: Vec<BonusRank>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct BonusRank {
-    pub pieces: u8,
-    pub skill_level: u8,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Armor {
-    kind: PartKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    defense: Defense,
-    resistances: Resistances,
-    slots: Vec<u8>,
-    #[serde(serialize_with = "ordered_map")]
-    pub skills: IdMap,
-    crafting: Crafting,
+/// Armor pieces don't have their own ID, so they're identified by their set ID and part kind.
+fn piece_id(data: &ArmorData) -> String {
+    format!("{} {:?}", data.series_id, PartKind::from(data.kind))
 }
 
 impl From<&ArmorData> for Armor {
@@ -239,6 +236,8 @@ impl From<&ArmorData> for Armor {
             kind: value.kind.into(),
             names: LanguageMap::new(),
             descriptions: LanguageMap::new(),
+            rich_descriptions: RichTextMap::new(),
+            descriptions_variants: VariantMap::new(),
             defense: value.into(),
             resistances: (&value.resistances).into(),
             slots: value.slots.into_iter().filter(|v| *v != 0).collect(),
@@ -248,12 +247,6 @@ impl From<&ArmorData> for Armor {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Defense {
-    base: u16,
-    max: u16,
-}
-
 impl From<&ArmorData> for Defense {
     fn from(value: &ArmorData) -> Self {
         Self {
@@ -263,15 +256,6 @@ impl From<&ArmorData> for Defense {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Resistances {
-    fire: i8,
-    water: i8,
-    thunder: i8,
-    ice: i8,
-    dragon: i8,
-}
-
 impl From<&ResistanceData> for Resistances {
     fn from(value: &ResistanceData) -> Self {
         Self {
@@ -284,13 +268,6 @@ impl From<&ResistanceData> for Resistances {
     }
 }
 
-#[derive(Debug, Serialize, Default, Deserialize)]
-pub struct Crafting {
-    price: usize,
-    #[serde(serialize_with = "ordered_map")]
-    inputs: IdMap,
-}
-
 #[derive(Debug, Deserialize)]
 struct SeriesData {
     #[serde(rename = "_Series")]
@@ -379,17 +356,6 @@ enum PartKindCode {
     Legs,
 }
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
-#[serde(rename_all = "kebab-case")]
-#[repr(u8)]
-enum PartKind {
-    Head,
-    Chest,
-    Arms,
-    Waist,
-    Legs,
-}
-
 impl From<&PartKindCode> for PartKind {
     fn from(value: &PartKindCode) -> Self {
         Self::from(*value)
@@ -408,12 +374,6 @@ impl From<PartKindCode> for PartKind {
     }
 }
 
-impl PartialEq<PartKindCode> for PartKind {
-    fn eq(&self, other: &PartKindCode) -> bool {
-        (*self as u8) == (*other as u8)
-    }
-}
-
 #[derive(Debug, Deserialize)]
 struct UpgradeData {
     #[serde(rename = "_Rare")]
@@ -427,23 +387,3 @@ struct UpgradeData {
     #[serde(rename = "_Price")]
     zenny_cost: usize,
 }
-
-#[derive(Debug, Serialize)]
-struct Upgrade {
-    rarity: u8,
-    steps: Vec<UpgradeStep>,
-}
-
-impl Upgrade {
-    fn get_total_defense_bonus(&self) -> u16 {
-        self.steps.iter().fold(0, |sum, v| sum + v.extra_defense)
-    }
-}
-
-#[derive(Debug, Serialize)]
-struct UpgradeStep {
-    level: u8,
-    extra_defense: u16,
-    point_cost: usize,
-    zenny_cost: usize,
-}
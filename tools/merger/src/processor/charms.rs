@@ -1,17 +1,14 @@
-use crate::processor::{LanguageMap, PopulateStrings, Processor, ReadFile, Result, WriteFile};
-use crate::serde::ordered_map;
+use crate::processor::{LanguageMap, PopulateStrings, Processor, ReadFile, Result};
 use crate::should_run;
 use indicatif::ProgressBar;
+use mhdb_model::charms::Charm;
+use mhdb_model::Database;
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::charms::{DATA, STRINGS};
+use serde::Deserialize;
 
-const DATA: &str = "user/Charm.json";
-const STRINGS: &str = "msg/Charm.json";
-
-const OUTPUT: &str = "merged/Charm.json";
-
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
+pub fn process(config: &Config, filters: &[Processor], db: &mut Database) -> Result {
     should_run!(filters, Processor::Charms);
 
     let data: Vec<CharmData> = Vec::read_file(config.io.output.join(DATA))?;
@@ -32,14 +29,9 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     progress.finish_and_clear();
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(OUTPUT))
-}
+    db.charms = merged;
 
-#[derive(Debug, Serialize)]
-struct Charm {
-    game_id: isize,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
+    Ok(())
 }
 
 impl From<&CharmData> for Charm {
@@ -1,40 +1,46 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
 use crate::processor::{
-    to_ingame_rarity, IconColor, LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile,
-    Result, WriteFile,
+    to_ingame_rarity, IconColorCode, LanguageMap, Lookup, LookupMap, PopulateStrings, Processor,
+    ReadFile, Result,
 };
-use crate::serde::ordered_map;
 use crate::should_run;
 use indicatif::ProgressBar;
+use mhdb_model::items::{
+    Foundry, FoundryMaterial, FoundryOutput, IconKind, Item, ItemId, ItemKind, Recipe,
+};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::items::{
+    DATA, FOUNDRY_MATERIAL_DATA, FOUNDRY_ORE_DATA, FOUNDRY_SPHERE_DATA, RECIPES, STRINGS,
+};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
-pub type ItemId = isize;
-
-const DATA: &str = "user/itemData.json";
-
-const FOUNDRY_MATERIAL_DATA: &str = "user/facilities/foundry/SmallWorkshopItemData.json";
-const FOUNDRY_SPHERE_DATA: &str = "user/facilities/foundry/SmallWorkshopDrillData.json";
-const FOUNDRY_ORE_DATA: &str = "user/facilities/foundry/SmallWorkshopRefineData.json";
 const FOUNDRY_OUTPUTS_DATA: &[&str] = &[FOUNDRY_SPHERE_DATA, FOUNDRY_ORE_DATA];
 
-const RECIPES: &str = "user/ItemRecipe.json";
-const STRINGS: &str = "msg/Item.json";
-
-const OUTPUT: &str = "merged/Item.json";
-
 // IDs for items that show up in the item data files, but are definitely not real items, such as
 // "Equipped Mantles" (which seems to be some kind of placeholder for whatever mantles the player
 // currently has equipped) and a duplicate "Screamer Pod" entry.
 const IGNORED_IDS: &[ItemId] = &[1, 278, 409];
 
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
+pub fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> Result {
     should_run!(filters, Processor::Items);
 
     let data: Vec<ItemData> = Vec::read_file(config.io.output.join(DATA))?;
     let strings = Msg::read_file(config.io.output.join(STRINGS))?;
+    let rich_text = config.merger.rich_text;
+    let placeholder_context = ApplyContext::empty()
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
     let progress = ProgressBar::new(data.len() as u64);
 
     let mut merged: Vec<Item> = Vec::with_capacity(data.len());
@@ -55,7 +61,13 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         }
 
         strings.populate(&data.description_guid, &mut item.descriptions);
-        Placeholder::process(&mut item.descriptions, &ApplyContext::empty());
+        let errors = Placeholder::process(
+            &mut item.descriptions,
+            rich_text.then_some(&mut item.rich_descriptions),
+            &mut item.descriptions_variants,
+            &placeholder_context,
+        );
+        diagnostics.warn_placeholders(STRINGS, item.game_id, errors);
 
         lookup.insert(item.game_id, merged.len());
         merged.push(item);
@@ -69,7 +81,11 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     for recipe in recipes {
         progress.inc(1);
 
-        let item = lookup.find_or_panic_mut(recipe.output_id, &mut merged);
+        let Some(item) =
+            lookup.find_or_report_mut(recipe.output_id, &mut merged, diagnostics, RECIPES)
+        else {
+            continue;
+        };
 
         item.recipes.push(recipe.into());
         item.recipes
@@ -78,85 +94,76 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
 
     progress.finish_and_clear();
 
-    let data: Vec<FoundryMaterial> = Vec::read_file(config.io.output.join(FOUNDRY_MATERIAL_DATA))?;
+    let data: Vec<FoundryMaterialData> =
+        Vec::read_file(config.io.output.join(FOUNDRY_MATERIAL_DATA))?;
     let progress = ProgressBar::new(data.len() as u64);
 
     for data in data {
         progress.inc(1);
 
-        let item = lookup.find_or_panic_mut(data.item_id, &mut merged);
-        item.foundry = Some(Foundry::Material(data));
+        let Some(item) = lookup.find_or_report_mut(
+            data.item_id,
+            &mut merged,
+            diagnostics,
+            FOUNDRY_MATERIAL_DATA,
+        ) else {
+            continue;
+        };
+
+        item.foundry = Some(Foundry::Material(data.into()));
     }
 
     progress.finish_and_clear();
 
     for data_path in FOUNDRY_OUTPUTS_DATA {
-        let data: Vec<FoundryOutput> = Vec::read_file(config.io.output.join(data_path))?;
+        let data: Vec<FoundryOutputData> = Vec::read_file(config.io.output.join(data_path))?;
         let progress = ProgressBar::new(data.len() as u64);
 
         for data in data {
             progress.inc(1);
 
-            let item = lookup.find_or_panic_mut(data.item_id, &mut merged);
-            item.foundry = Some(Foundry::Output(data));
+            let Some(item) =
+                lookup.find_or_report_mut(data.item_id, &mut merged, diagnostics, data_path)
+            else {
+                continue;
+            };
+
+            item.foundry = Some(Foundry::Output(data.into()));
         }
 
         progress.finish_and_clear();
     }
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(OUTPUT))
-}
+    db.items = merged;
 
-#[derive(Debug, Serialize)]
-struct Item {
-    game_id: ItemId,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    kind: ItemKind,
-    rarity: u8,
-    max_count: u8,
-    sell_price: usize,
-    buy_price: usize,
-    recipes: Vec<Recipe>,
-    out_box: bool,
-    icon: IconKind,
-    icon_id: u8,
-    icon_color: IconColor,
-    icon_color_id: u8,
-    foundry: Option<Foundry>,
+    Ok(())
 }
 
 impl From<&ItemData> for Item {
     fn from(value: &ItemData) -> Self {
         Self {
             game_id: value.id,
-            kind: value.kind,
+            kind: value.kind.into(),
             rarity: to_ingame_rarity(value.rarity),
             max_count: value.max_count,
             sell_price: value.sell_price,
             buy_price: value.buy_price,
             names: LanguageMap::new(),
             descriptions: LanguageMap::new(),
+            rich_descriptions: RichTextMap::new(),
+            descriptions_variants: VariantMap::new(),
             recipes: Vec::new(),
             out_box: value.out_box,
-            icon: value.icon,
+            icon: value.icon.into(),
             icon_id: value.icon as u8,
-            icon_color: value.icon_color,
+            icon_color: value.icon_color.into(),
             icon_color_id: value.icon_color as u8,
             foundry: None,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Recipe {
-    amount: u8,
-    inputs: Vec<isize>,
-}
-
 impl From<RecipeData> for Recipe {
     fn from(value: RecipeData) -> Self {
         let mut inputs: Vec<_> = value
@@ -183,7 +190,7 @@ struct ItemData {
     #[serde(rename = "_RawExplain")]
     description_guid: String,
     #[serde(rename = "_Type")]
-    kind: ItemKind,
+    kind: ItemKindCode,
     #[serde(rename = "_Rare")]
     rarity: u8,
     #[serde(rename = "_MaxCount")]
@@ -195,9 +202,9 @@ struct ItemData {
     #[serde(rename = "_OutBox")]
     out_box: bool,
     #[serde(rename = "_IconType")]
-    icon: IconKind,
+    icon: IconKindCode,
     #[serde(rename = "_IconColor")]
-    icon_color: IconColor,
+    icon_color: IconColorCode,
 }
 
 #[derive(Debug, Deserialize)]
@@ -210,10 +217,9 @@ struct RecipeData {
     input_ids: Vec<ItemId>,
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-enum ItemKind {
+enum ItemKindCode {
     Consumable = 0,
     Tool,
     Material,
@@ -223,10 +229,23 @@ enum ItemKind {
     Mystery,
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all = "kebab-case")]
+impl From<ItemKindCode> for ItemKind {
+    fn from(value: ItemKindCode) -> Self {
+        match value {
+            ItemKindCode::Consumable => Self::Consumable,
+            ItemKindCode::Tool => Self::Tool,
+            ItemKindCode::Material => Self::Material,
+            ItemKindCode::BowgunAmmo => Self::BowgunAmmo,
+            ItemKindCode::BowCoating => Self::BowCoating,
+            ItemKindCode::Point => Self::Point,
+            ItemKindCode::Mystery => Self::Mystery,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-enum IconKind {
+enum IconKindCode {
     MysteryArtian = 0,
     MysteryMaterial = 1,
     Question = 2,
@@ -295,16 +314,81 @@ enum IconKind {
     Unknown = u8::MAX,
 }
 
-#[derive(Debug, Serialize)]
-#[serde(tag = "kind", rename_all = "kebab-case")]
-enum Foundry {
-    Material(FoundryMaterial),
-    Output(FoundryOutput),
+impl From<IconKindCode> for IconKind {
+    fn from(value: IconKindCode) -> Self {
+        match value {
+            IconKindCode::MysteryArtian => Self::MysteryArtian,
+            IconKindCode::MysteryMaterial => Self::MysteryMaterial,
+            IconKindCode::Question => Self::Question,
+            IconKindCode::Mushroom => Self::Mushroom,
+            IconKindCode::Egg => Self::Egg,
+            IconKindCode::Honey => Self::Honey,
+            IconKindCode::Plant => Self::Plant,
+            IconKindCode::Potion => Self::Potion,
+            IconKindCode::Powder => Self::Powder,
+            IconKindCode::Whetstone => Self::Whetstone,
+            IconKindCode::Pill => Self::Pill,
+            IconKindCode::Fish => Self::Fish,
+            IconKindCode::Meat => Self::Meat,
+            IconKindCode::Barrel => Self::Barrel,
+            IconKindCode::Bomb => Self::Bomb,
+            IconKindCode::TrapTool => Self::TrapTool,
+            IconKindCode::Trap => Self::Trap,
+            IconKindCode::Gem => Self::Gem,
+            IconKindCode::Smoke => Self::Smoke,
+            IconKindCode::FishingRod => Self::FishingRod,
+            IconKindCode::Binoculars => Self::Binoculars,
+            IconKindCode::Knife => Self::Knife,
+            IconKindCode::Grill => Self::Grill,
+            IconKindCode::Voucher => Self::Voucher,
+            IconKindCode::Certificate => Self::Certificate,
+            IconKindCode::Coin => Self::Coin,
+            IconKindCode::Nut => Self::Nut,
+            IconKindCode::AmmoBasic => Self::AmmoBasic,
+            IconKindCode::Phial => Self::Phial,
+            IconKindCode::Web => Self::Web,
+            IconKindCode::Seed => Self::Seed,
+            IconKindCode::Ore => Self::Ore,
+            IconKindCode::Bug => Self::Bug,
+            IconKindCode::Poop => Self::Poop,
+            IconKindCode::Medulla => Self::Medulla,
+            IconKindCode::Bone => Self::Bone,
+            IconKindCode::Scale => Self::Scale,
+            IconKindCode::Hide => Self::Hide,
+            IconKindCode::Claw => Self::Claw,
+            IconKindCode::Shell => Self::Shell,
+            IconKindCode::Tail => Self::Tail,
+            IconKindCode::Wing => Self::Wing,
+            IconKindCode::Skull => Self::Skull,
+            IconKindCode::Plate => Self::Plate,
+            IconKindCode::Crystal => Self::Crystal,
+            IconKindCode::ArmorSphere => Self::ArmorSphere,
+            IconKindCode::MysteryDecoration => Self::MysteryDecoration,
+            IconKindCode::CampingKit => Self::CampingKit,
+            IconKindCode::SlingerAmmo => Self::SlingerAmmo,
+            IconKindCode::CaptureNet => Self::CaptureNet,
+            IconKindCode::AmmoSlug => Self::AmmoSlug,
+            IconKindCode::AmmoSpecial => Self::AmmoSpecial,
+            IconKindCode::AmmoUtility => Self::AmmoUtility,
+            IconKindCode::AmmoHeavy => Self::AmmoHeavy,
+            IconKindCode::Curative => Self::Curative,
+            IconKindCode::Drug => Self::Drug,
+            IconKindCode::Extract => Self::Extract,
+            IconKindCode::Mantle => Self::Mantle,
+            IconKindCode::CookingCheese => Self::CookingCheese,
+            IconKindCode::CookingMushroom => Self::CookingMushroom,
+            IconKindCode::CookingShellfish => Self::CookingShellfish,
+            IconKindCode::CookingGarlic => Self::CookingGarlic,
+            IconKindCode::CookingEgg => Self::CookingEgg,
+            IconKindCode::Sprout => Self::Sprout,
+            IconKindCode::Unknown => Self::Unknown,
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct FoundryMaterial {
-    #[serde(rename(deserialize = "_ItemId"), skip_serializing)]
+#[derive(Debug, Deserialize)]
+struct FoundryMaterialData {
+    #[serde(rename = "_ItemId")]
     item_id: isize,
 
     #[serde(alias = "_RefinePoint")]
@@ -314,11 +398,26 @@ struct FoundryMaterial {
     ore_value: u16,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct FoundryOutput {
-    #[serde(rename(deserialize = "_ItemId"), skip_serializing)]
+impl From<FoundryMaterialData> for FoundryMaterial {
+    fn from(value: FoundryMaterialData) -> Self {
+        Self {
+            armor_sphere_value: value.armor_sphere_value,
+            ore_value: value.ore_value,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FoundryOutputData {
+    #[serde(rename = "_ItemId")]
     item_id: isize,
 
     #[serde(alias = "_RefinePoint", alias = "_DrillPoint")]
     cost: u16,
 }
+
+impl From<FoundryOutputData> for FoundryOutput {
+    fn from(value: FoundryOutputData) -> Self {
+        Self { cost: value.cost }
+    }
+}
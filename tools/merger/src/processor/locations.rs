@@ -1,32 +1,25 @@
-use crate::processor::{
-    LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile, WriteFile,
-};
-use crate::serde::ordered_map;
+use crate::diagnostics::Diagnostics;
+use crate::processor::{LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile};
 use crate::should_run;
+use mhdb_model::locations::{Camp, GimmickId, Position, Risk, Stage, StageId};
+use mhdb_model::Database;
 use rslib::config::Config;
 use rslib::formats::msg::{LanguageCode, Msg};
-use serde::{Deserialize, Deserializer, Serialize};
+use rslib::processors::inputs::locations::{
+    CAMP_PATH_PREFIX, DARK_AREA_DATA, GIMMICK_DATA, GIMMICK_ID_DATA, GIMMICK_STRINGS,
+    GIMMICK_TEXT_DATA, STAGE_ID_DATA, STAGE_STRINGS,
+};
+use serde::{Deserialize, Deserializer};
 use serde_repr::Deserialize_repr;
 use std::collections::HashMap;
-
-pub type StageId = isize;
-pub type GimmickId = isize;
-
-const STAGE_ID_DATA: &str = "user/locations/Stage.json";
-const GIMMICK_ID_DATA: &str = "user/GmID.json";
-
-const DARK_AREA_DATA: &str = "user/locations/DarkAreaSetting.json";
-const GIMMICK_DATA: &str = "user/GimmickBasicData.json";
-const GIMMICK_TEXT_DATA: &str = "user/GimmickTextData.json";
-
-const CAMP_PATH_PREFIX: &str = "user/locations/camps";
-
-const STAGE_STRINGS: &str = "msg/RefEnvironment.json";
-const GIMMICK_STRINGS: &str = "msg/Gimmick.json";
-
-pub const OUTPUT: &str = "merged/Stage.json";
-
-pub(super) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<()> {
+use std::path::Path;
+
+pub(super) fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     should_run!(filters, Processor::Locations);
 
     let data: Vec<StageIdData> = Vec::read_file(config.io.output.join(STAGE_ID_DATA))?;
@@ -43,7 +36,21 @@ pub(super) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<
         }
 
         let mut stage = Stage::from(&data);
-        strings.populate(data.get_name_guid(), &mut stage.names);
+
+        match data.get_name_guid() {
+            Some(guid) => strings.populate(guid, &mut stage.names),
+            None => {
+                diagnostics
+                    .error(
+                        STAGE_ID_DATA,
+                        format!(
+                            "Unrecognized stage name {}; you probably forgot to add a mapping :(",
+                            data.name
+                        ),
+                    )
+                    .entity(stage.game_id);
+            }
+        }
 
         lookup.insert(stage.game_id, stages.len());
         stages.push(stage);
@@ -74,7 +81,15 @@ pub(super) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<
             })
             .max();
 
-        stage.areas = area_count.expect("A field stage shouldn't have zero zones??");
+        let Some(area_count) = area_count else {
+            diagnostics
+                .error(DARK_AREA_DATA, "A field stage shouldn't have zero zones??")
+                .entity(stage.game_id);
+
+            continue;
+        };
+
+        stage.areas = area_count;
     }
 
     let data: Vec<GimmickIdData> = Vec::read_file(config.io.output.join(GIMMICK_ID_DATA))?;
@@ -91,23 +106,34 @@ pub(super) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<
             continue;
         }
 
-        let name = gimmick_ids
-            .get(&data.id)
-            .unwrap_or_else(|| panic!("Could not find gimmick with ID {}", data.id));
+        let Some(name) = gimmick_ids.get(&data.id) else {
+            diagnostics
+                .error(GIMMICK_ID_DATA, "Could not find gimmick name")
+                .entity(data.id);
+
+            continue;
+        };
+
+        let Some(text) = gimmick_text.get(&data.id) else {
+            diagnostics
+                .error(GIMMICK_TEXT_DATA, "Could not find gimmick text")
+                .entity(data.id);
+
+            continue;
+        };
 
-        let name = name.to_owned() + "_AaaUniqueParam.json";
-        let path = config.io.output.join(CAMP_PATH_PREFIX).join(name);
-        let camp_data = CampData::read_file(path)?;
+        let path = Path::new(CAMP_PATH_PREFIX).join(name.to_owned() + "_AaaUniqueParam.json");
+        let camp_data = CampData::read_file(config.io.output.join(&path))?;
 
-        let stage = lookup.find_or_panic_mut(camp_data.stage_id, &mut stages);
+        let Some(stage) =
+            lookup.find_or_report_mut(camp_data.stage_id, &mut stages, diagnostics, &path)
+        else {
+            continue;
+        };
 
         let mut camp = Camp::from(camp_data);
         camp.game_id = data.id;
 
-        let text = gimmick_text
-            .get(&data.id)
-            .unwrap_or_else(|| panic!("Could not find gimmick with ID {}", data.id));
-
         strings.populate(&text.name_guid, &mut camp.names);
 
         // For some bizarre reason, some camps seem to have an area number that's outside the range
@@ -123,20 +149,19 @@ pub(super) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<
         // determine the real area number, all we need to do is parse `<num>` out of the name. It's
         // a bit hacky, but it's the best I think I can do right now.
         if camp.area > stage.areas {
-            let en_name = strings
-                .get_lang(&text.name_guid, LanguageCode::English)
-                .unwrap_or_else(|| {
-                    panic!(
-                        "No english name found for fallback for gimmick ID {}",
-                        data.id
-                    )
-                });
-
-            let start = en_name.find(' ').unwrap_or_default() + 1;
-            let end = en_name.find(':').unwrap_or(en_name.len());
-            let fragment = &en_name[start..end];
-
-            camp.area = fragment.parse()?;
+            let en_name = strings.get_lang(&text.name_guid, LanguageCode::English);
+
+            match en_name.and_then(area_from_name) {
+                Some(area) => camp.area = area,
+                None => {
+                    diagnostics
+                        .warning(
+                            GIMMICK_STRINGS,
+                            "Could not find the area number in the camp's English name",
+                        )
+                        .entity(data.id);
+                }
+            }
         }
 
         stage.camps.push(camp);
@@ -147,19 +172,17 @@ pub(super) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<
     }
 
     stages.sort_by_key(|v| v.game_id);
-    stages.write_file(config.io.output.join(OUTPUT))?;
+    db.stages = stages;
 
     Ok(())
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Stage {
-    pub game_id: StageId,
-    #[serde(serialize_with = "ordered_map")]
-    pub names: LanguageMap,
-    pub areas: u16,
-    pub camps: Vec<Camp>,
-    pub bitmask_value: u32,
+/// Parses `<num>` out of a camp name following the pattern "Area <num>: <name>".
+fn area_from_name(name: &str) -> Option<u16> {
+    let start = name.find(' ').unwrap_or_default() + 1;
+    let end = name.find(':').unwrap_or(name.len());
+
+    name.get(start..end)?.parse().ok()
 }
 
 impl From<&StageIdData> for Stage {
@@ -189,28 +212,19 @@ impl StageIdData {
         1 << (self.value + 1)
     }
 
-    fn get_name_guid(&self) -> &str {
+    fn get_name_guid(&self) -> Option<&str> {
         // Mappings current as of 2025-03-31
-        match self.name.as_ref() {
+        let guid = match self.name.as_ref() {
             "ST101" => "53c75773-e1c1-4842-b853-594c064c9dcf",
             "ST102" => "b05b96d2-3151-447c-911c-9e3d3b9e781c",
             "ST103" => "53dbc540-c48a-4c3d-bf1a-e7a715db927c",
             "ST104" => "c19b98a4-c220-4891-ac0e-15e21edf67bc",
             "ST105" => "2d17ecc9-6c48-4544-91ed-a078e05a4075",
-            v => panic!("Unrecognized stage name {v}; you probably forgot to add a mapping :("),
-        }
-    }
-}
+            _ => return None,
+        };
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Camp {
-    game_id: GimmickId,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    area: u16,
-    floor: u16,
-    risk: Risk,
-    position: Position,
+        Some(guid)
+    }
 }
 
 impl From<CampData> for Camp {
@@ -254,14 +268,6 @@ enum RiskData {
     Safe,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
-pub enum Risk {
-    Dangerous,
-    Insecure,
-    Safe,
-}
-
 impl From<RiskData> for Risk {
     fn from(value: RiskData) -> Self {
         match value {
@@ -272,13 +278,6 @@ impl From<RiskData> for Risk {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Position {
-    x: f32,
-    y: f32,
-    z: f32,
-}
-
 #[derive(Debug, Deserialize)]
 struct DarkAreaData {
     #[serde(rename = "_Stage")]
@@ -1,14 +1,16 @@
-use clap::ValueEnum;
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use console::Style;
+use mhdb_model::{Database, IconColor, IdMap, LanguageMap};
 use rslib::config::Config;
-use rslib::formats::msg::{LanguageCode, Msg};
+use rslib::dump;
+use rslib::formats::msg::Msg;
+use rslib::processors::MSG_DIRECTORY;
 use serde::de::DeserializeOwned;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::fs;
-use std::fs::File;
 use std::hash::Hash;
 use std::path::Path;
 
@@ -22,58 +24,7 @@ mod monsters;
 mod skills;
 mod weapons;
 
-#[derive(Debug, Deserialize, ValueEnum, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
-#[serde(rename_all = "lowercase")]
-pub enum Processor {
-    Accessories,
-    Items,
-    Charms,
-    Amulets,
-    Armor,
-    Skill,
-    Weapons,
-    Bow,
-    ChargeBlade,
-    Gunlance,
-    Hammer,
-    HeavyBowgun,
-    Lance,
-    LightBowgun,
-    GreatSword,
-    InsectGlaive,
-    SwordShield,
-    SwitchAxe,
-    LongSword,
-    DualBlades,
-    HuntingHorn,
-    Monsters,
-    Locations,
-    WeaponSeries,
-}
-
-impl Processor {
-    fn is_weapon(&self) -> bool {
-        use Processor::*;
-
-        matches!(
-            self,
-            Bow | ChargeBlade
-                | Gunlance
-                | Hammer
-                | HeavyBowgun
-                | Lance
-                | LightBowgun
-                | GreatSword
-                | InsectGlaive
-                | SwordShield
-                | SwitchAxe
-                | LongSword
-                | DualBlades
-                | HuntingHorn
-                | WeaponSeries
-        )
-    }
-}
+pub use rslib::processors::Processor;
 
 trait ShouldRun {
     fn should_run(&self, subject: Processor) -> bool;
@@ -84,6 +35,7 @@ impl ShouldRun for &[Processor] {
         self.is_empty()
             || self.contains(&subject)
             || (subject.is_weapon() && self.contains(&Processor::Weapons))
+            || self.iter().any(|v| v.dependencies().contains(&subject))
     }
 }
 
@@ -96,14 +48,6 @@ macro_rules! should_run {
     };
 }
 
-/// A map of RFC 639 language codes to a string value. Used to hold translations for an object
-/// field.
-pub type LanguageMap = HashMap<Language, String>;
-
-/// A map of object IDs to a level or quantity indicator. Used for things like skill ranks granted
-/// by decorations, or inputs in recipes.
-type IdMap = HashMap<isize, u8>;
-
 /// A map of game IDs to an index. Used for cases where a child object needs to find its parent
 /// during processing.
 type LookupMap<K = isize> = HashMap<K, usize>;
@@ -138,17 +82,33 @@ macro_rules! sections {
     };
 }
 
-pub fn all(config: &Config, filters: &[Processor]) -> anyhow::Result<()> {
+/// Runs every processor allowed by `filters`, storing the merged output in `db`.
+///
+/// Locations are merged before monsters, since monster locations are resolved against the merged
+/// stage list. Every msg file is indexed up front, so that reference placeholders can be resolved
+/// no matter which file the referenced entry lives in.
+///
+/// Problems with individual rows are recorded in `diagnostics` instead of aborting the merge. An
+/// error is only returned if a file can't be read at all.
+pub fn all(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     sections! {
-        "Merging accessory files..." => accessories::process(config, filters)?,
-        "Merging item files..." => items::process(config, filters)?,
-        "Merging charm files..." => charms::process(config, filters)?,
-        "Merging amulet files..." => amulets::process(config, filters)?,
-        "Merging armor files..." => armor::process(config, filters)?,
-        "Merging skill files..." => skills::process(config, filters)?,
-        "Merging weapon files..." => weapons::process(config, filters)?,
-        "Merging monster files..." => monsters::process(config, filters)?,
-        "Merging location files..." => locations::process(config, filters)?,
+        "Indexing msg files..." =>
+            let refs = ReferenceIndex::load(config.io.output.join(MSG_DIRECTORY))?,
+        "Merging accessory files..." =>
+            accessories::process(config, filters, db, &refs, diagnostics)?,
+        "Merging item files..." => items::process(config, filters, db, &refs, diagnostics)?,
+        "Merging charm files..." => charms::process(config, filters, db)?,
+        "Merging amulet files..." => amulets::process(config, filters, db, &refs, diagnostics)?,
+        "Merging armor files..." => armor::process(config, filters, db, &refs, diagnostics)?,
+        "Merging skill files..." => skills::process(config, filters, db, &refs, diagnostics)?,
+        "Merging weapon files..." => weapons::process(config, filters, db, &refs, diagnostics)?,
+        "Merging location files..." => locations::process(config, filters, db, diagnostics)?,
+        "Merging monster files..." => monsters::process(config, filters, db, &refs, diagnostics)?,
     }
 
     Ok(())
@@ -163,173 +123,9 @@ pub enum Error {
 
     #[error("parse: {0}")]
     Parse(#[from] serde_json::Error),
-}
 
-/// Language list from https://github.com/dtlnor/RE_MSG/blob/main/LanguagesEnum.md
-#[derive(Debug, PartialEq, Eq, Deserialize, Copy, Clone, Serialize, Hash, Ord, PartialOrd)]
-pub enum Language {
-    #[serde(rename = "")]
-    Disabled,
-    #[serde(rename = "ja")]
-    Japanese,
-    #[serde(rename = "en")]
-    English,
-    #[serde(rename = "fr")]
-    French,
-    #[serde(rename = "it")]
-    Italian,
-    #[serde(rename = "de")]
-    German,
-    #[serde(rename = "es")]
-    Spanish,
-    #[serde(rename = "ru")]
-    Russian,
-    #[serde(rename = "pl")]
-    Polish,
-    #[serde(rename = "nl")]
-    Dutch,
-    #[serde(rename = "pt")]
-    Portuguese,
-    #[serde(rename = "pt-BR")]
-    BrazilianPortuguese,
-    #[serde(rename = "ko")]
-    Korean,
-    #[serde(rename = "zh-Hant")]
-    TraditionalChinese,
-    #[serde(rename = "zh-Hans")]
-    SimplifiedChinese,
-    #[serde(rename = "fi")]
-    Finnish,
-    #[serde(rename = "sv")]
-    Swedish,
-    #[serde(rename = "da")]
-    Danish,
-    #[serde(rename = "no")]
-    Norwegian,
-    #[serde(rename = "cs")]
-    Czech,
-    #[serde(rename = "hu")]
-    Hungarian,
-    #[serde(rename = "sk")]
-    Slovak,
-    #[serde(rename = "ar")]
-    Arabic,
-    #[serde(rename = "tr")]
-    Turkish,
-    #[serde(rename = "bg")]
-    Bulgarian,
-    #[serde(rename = "el")]
-    Greek,
-    #[serde(rename = "ro")]
-    Romanian,
-    #[serde(rename = "th")]
-    Thai,
-    #[serde(rename = "uk")]
-    Ukrainian,
-    #[serde(rename = "vi")]
-    Vietnamese,
-    #[serde(rename = "id")]
-    Indonesian,
-    #[serde(skip_deserializing, rename = "")]
-    Fiction,
-    #[serde(rename = "hi")]
-    Hindi,
-    #[serde(rename = "es-419")]
-    LatinAmericanSpanish,
-}
-
-impl From<&LanguageCode> for Language {
-    fn from(value: &LanguageCode) -> Self {
-        Self::from(*value)
-    }
-}
-
-impl From<LanguageCode> for Language {
-    fn from(value: LanguageCode) -> Self {
-        match value {
-            LanguageCode::Disabled => Self::Disabled,
-            LanguageCode::Japanese => Self::Japanese,
-            LanguageCode::English => Self::English,
-            LanguageCode::French => Self::French,
-            LanguageCode::Italian => Self::Italian,
-            LanguageCode::German => Self::German,
-            LanguageCode::Spanish => Self::Spanish,
-            LanguageCode::Russian => Self::Russian,
-            LanguageCode::Polish => Self::Polish,
-            LanguageCode::Dutch => Self::Dutch,
-            LanguageCode::Portuguese => Self::Portuguese,
-            LanguageCode::BrazilianPortuguese => Self::BrazilianPortuguese,
-            LanguageCode::Korean => Self::Korean,
-            LanguageCode::TraditionalChinese => Self::TraditionalChinese,
-            LanguageCode::SimplifiedChinese => Self::SimplifiedChinese,
-            LanguageCode::Finnish => Self::Finnish,
-            LanguageCode::Swedish => Self::Swedish,
-            LanguageCode::Danish => Self::Danish,
-            LanguageCode::Norwegian => Self::Norwegian,
-            LanguageCode::Czech => Self::Czech,
-            LanguageCode::Hungarian => Self::Hungarian,
-            LanguageCode::Slovak => Self::Slovak,
-            LanguageCode::Arabic => Self::Arabic,
-            LanguageCode::Turkish => Self::Turkish,
-            LanguageCode::Bulgarian => Self::Bulgarian,
-            LanguageCode::Greek => Self::Greek,
-            LanguageCode::Romanian => Self::Romanian,
-            LanguageCode::Thai => Self::Thai,
-            LanguageCode::Ukrainian => Self::Ukrainian,
-            LanguageCode::Vietnamese => Self::Vietnamese,
-            LanguageCode::Indonesian => Self::Indonesian,
-            LanguageCode::Fiction => Self::Fiction,
-            LanguageCode::Hindi => Self::Hindi,
-            LanguageCode::LatinAmericanSpanish => Self::LatinAmericanSpanish,
-        }
-    }
-}
-
-impl From<Language> for LanguageCode {
-    fn from(value: Language) -> Self {
-        match value {
-            Language::Disabled => LanguageCode::Disabled,
-            Language::Japanese => LanguageCode::Japanese,
-            Language::English => LanguageCode::English,
-            Language::French => LanguageCode::French,
-            Language::Italian => LanguageCode::Italian,
-            Language::German => LanguageCode::German,
-            Language::Spanish => LanguageCode::Spanish,
-            Language::Russian => LanguageCode::Russian,
-            Language::Polish => LanguageCode::Polish,
-            Language::Dutch => LanguageCode::Dutch,
-            Language::Portuguese => LanguageCode::Portuguese,
-            Language::BrazilianPortuguese => LanguageCode::BrazilianPortuguese,
-            Language::Korean => LanguageCode::Korean,
-            Language::TraditionalChinese => LanguageCode::TraditionalChinese,
-            Language::SimplifiedChinese => LanguageCode::SimplifiedChinese,
-            Language::Finnish => LanguageCode::Finnish,
-            Language::Swedish => LanguageCode::Swedish,
-            Language::Danish => LanguageCode::Danish,
-            Language::Norwegian => LanguageCode::Norwegian,
-            Language::Czech => LanguageCode::Czech,
-            Language::Hungarian => LanguageCode::Hungarian,
-            Language::Slovak => LanguageCode::Slovak,
-            Language::Arabic => LanguageCode::Arabic,
-            Language::Turkish => LanguageCode::Turkish,
-            Language::Bulgarian => LanguageCode::Bulgarian,
-            Language::Greek => LanguageCode::Greek,
-            Language::Romanian => LanguageCode::Romanian,
-            Language::Thai => LanguageCode::Thai,
-            Language::Ukrainian => LanguageCode::Ukrainian,
-            Language::Vietnamese => LanguageCode::Vietnamese,
-            Language::Indonesian => LanguageCode::Indonesian,
-            Language::Fiction => LanguageCode::Fiction,
-            Language::Hindi => LanguageCode::Hindi,
-            Language::LatinAmericanSpanish => LanguageCode::LatinAmericanSpanish,
-        }
-    }
-}
-
-impl From<&Language> for LanguageCode {
-    fn from(value: &Language) -> Self {
-        (*value).into()
-    }
+    #[error("{0}")]
+    Dump(#[from] dump::Error),
 }
 
 trait PopulateStrings {
@@ -365,31 +161,13 @@ impl<T> ReadFile for T
 where
     T: Sized + DeserializeOwned,
 {
+    /// Reads the dump at `path`. Paths are written with a `.json` extension, but the dump can be in
+    /// any format the extractor writes.
     fn read_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let file = File::open(path)?;
-        Ok(serde_json::from_reader(file)?)
-    }
-}
-
-trait WriteFile {
-    fn write_file<P: AsRef<Path>>(&self, path: P) -> Result;
-}
-
-impl<T> WriteFile for T
-where
-    T: Serialize,
-{
-    fn write_file<P: AsRef<Path>>(&self, path: P) -> Result {
-        let parent = path.as_ref().parent();
-
-        if let Some(parent) = parent {
-            if !parent.exists() {
-                fs::create_dir_all(parent)?;
-            }
-        }
+        let path = path.as_ref();
+        let path = dump::locate(path).unwrap_or_else(|| path.to_owned());
 
-        fs::write(path, serde_json::to_string_pretty(self)?)?;
-        Ok(())
+        Ok(dump::read(path)?)
     }
 }
 
@@ -411,7 +189,16 @@ trait Lookup {
 
     fn find_in<'a, T>(&self, id: Self::Key, container: &'a [T]) -> Option<&'a T>;
     fn find_in_mut<'a, T>(&self, id: Self::Key, container: &'a mut [T]) -> Option<&'a mut T>;
-    fn find_or_panic_mut<'a, T>(&self, id: Self::Key, container: &'a mut [T]) -> &'a mut T;
+
+    /// Like [`Lookup::find_in_mut()`], but records an error against `source` if no object has the
+    /// given ID.
+    fn find_or_report_mut<'a, T, S: AsRef<Path>>(
+        &self,
+        id: Self::Key,
+        container: &'a mut [T],
+        diagnostics: &mut Diagnostics,
+        source: S,
+    ) -> Option<&'a mut T>;
 }
 
 impl<K> Lookup for LookupMap<K>
@@ -436,9 +223,22 @@ where
         }
     }
 
-    fn find_or_panic_mut<'a, T>(&self, id: Self::Key, container: &'a mut [T]) -> &'a mut T {
-        self.find_in_mut(id, container)
-            .unwrap_or_else(|| panic!("Could not find object by ID {id}"))
+    fn find_or_report_mut<'a, T, S: AsRef<Path>>(
+        &self,
+        id: Self::Key,
+        container: &'a mut [T],
+        diagnostics: &mut Diagnostics,
+        source: S,
+    ) -> Option<&'a mut T> {
+        let value = self.find_in_mut(id, container);
+
+        if value.is_none() {
+            diagnostics
+                .error(source, format!("Could not find object by ID {id}"))
+                .entity(id);
+        }
+
+        value
     }
 }
 
@@ -484,17 +284,9 @@ impl Guid {
     }
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "kebab-case")]
-pub enum HunterRank {
-    Low,
-    High,
-}
-
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-enum IconColor {
+enum IconColorCode {
     None = 0,
     White = 1,
     Gray = 2,
@@ -518,3 +310,32 @@ enum IconColor {
     Purple = 20,
     DarkPurple = 21,
 }
+
+impl From<IconColorCode> for IconColor {
+    fn from(value: IconColorCode) -> Self {
+        match value {
+            IconColorCode::None => Self::None,
+            IconColorCode::White => Self::White,
+            IconColorCode::Gray => Self::Gray,
+            IconColorCode::Rose => Self::Rose,
+            IconColorCode::Pink => Self::Pink,
+            IconColorCode::Red => Self::Red,
+            IconColorCode::Vermilion => Self::Vermilion,
+            IconColorCode::Orange => Self::Orange,
+            IconColorCode::Brown => Self::Brown,
+            IconColorCode::Ivory => Self::Ivory,
+            IconColorCode::Yellow => Self::Yellow,
+            IconColorCode::Lemon => Self::Lemon,
+            IconColorCode::SageGreen => Self::SageGreen,
+            IconColorCode::MossGreen => Self::MossGreen,
+            IconColorCode::Green => Self::Green,
+            IconColorCode::Emerald => Self::Emerald,
+            IconColorCode::Sky => Self::Sky,
+            IconColorCode::Blue => Self::Blue,
+            IconColorCode::Ultramarine => Self::Ultramarine,
+            IconColorCode::BluePurple => Self::BluePurple,
+            IconColorCode::Purple => Self::Purple,
+            IconColorCode::DarkPurple => Self::DarkPurple,
+        }
+    }
+}
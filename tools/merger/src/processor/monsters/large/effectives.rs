@@ -1,26 +1,28 @@
 use crate::add_condition;
-use crate::processor::monsters::large::parts::Multipliers;
+use crate::diagnostics::Diagnostics;
 use crate::processor::monsters::large::RunContext;
-use crate::processor::monsters::MonsterId;
-use crate::processor::weapons::{Element, Status};
 use crate::processor::{Guid, LanguageMap, PopulateStrings, ReadFile};
-use crate::serde::optional_ordered_map;
+use mhdb_model::monsters::large::effectives::{Effect, Resistance, SpecialKind, Weakness};
+use mhdb_model::monsters::large::parts::Multipliers;
+use mhdb_model::monsters::MonsterId;
+use mhdb_model::weapons::{Element, Status};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Deserializer, Serialize};
+use rslib::processors::inputs::monsters::effectives::{
+    CONDITIONS_DATA, CONDITION_PRESET_DATA, ELEMENT_DATA, WEAK_CONDITION_DATA,
+    WEAK_CONDITION_STRINGS,
+};
+use serde::{Deserialize, Deserializer};
 use serde_repr::Deserialize_repr;
 use serde_with::serde_as;
 use std::cell::OnceCell;
 use std::collections::HashMap;
 
-const ELEMENT_DATA: &str = "user/monsters/EnemyWeakAttrData.json";
-const CONDITION_PRESET_DATA: &str = "user/monsters/EmParamBadConditionPreset.json";
-const CONDITIONS_DATA: &str = "user/monsters/EmParamBadCondition2.json";
-const WEAK_CONDITION_DATA: &str = "user/monsters/EnemyReportMeasureFreeInfoData.json";
-
-const WEAK_CONDITION_STRINGS: &str = "msg/EnemyReportMeasureFreeInfoText.json";
-
-pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Result<()> {
+pub(super) fn process(
+    config: &Config,
+    context: &mut RunContext,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     let data: Vec<WeakElementData> = Vec::read_file(config.io.output.join(ELEMENT_DATA))?;
 
     for data in data {
@@ -59,14 +61,14 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
             continue;
         };
 
-        add_condition!(presets.paralyze, data.paralyze => monster, Status::Paralysis);
-        add_condition!(presets.poison, data.poison => monster, Status::Poison);
-        add_condition!(presets.sleep, data.sleep => monster, Status::Sleep);
-        add_condition!(presets.stun, data.stun => monster, Effect::Stun);
-        add_condition!(presets.flash, data.flash => monster, Effect::Flash);
-        add_condition!(presets.noise, data.noise => monster, Effect::Noise);
-        add_condition!(presets.blast, data.blast => monster, Status::Blastblight);
-        add_condition!(presets.exhaust, data.exhaust => monster, Effect::Exhaust);
+        add_condition!(diagnostics; presets.paralyze, data.paralyze => monster, Status::Paralysis);
+        add_condition!(diagnostics; presets.poison, data.poison => monster, Status::Poison);
+        add_condition!(diagnostics; presets.sleep, data.sleep => monster, Status::Sleep);
+        add_condition!(diagnostics; presets.stun, data.stun => monster, Effect::Stun);
+        add_condition!(diagnostics; presets.flash, data.flash => monster, Effect::Flash);
+        add_condition!(diagnostics; presets.noise, data.noise => monster, Effect::Noise);
+        add_condition!(diagnostics; presets.blast, data.blast => monster, Status::Blastblight);
+        add_condition!(diagnostics; presets.exhaust, data.exhaust => monster, Effect::Exhaust);
     }
 
     let data: Vec<ConditionText> = Vec::read_file(config.io.output.join(WEAK_CONDITION_DATA))?;
@@ -78,7 +80,14 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
         };
 
         let Some(weakness) = monster.find_weakness_mut(data.kind.as_special_kind()) else {
-            panic!("Could not find mapped weakness for {:?}", data.kind);
+            diagnostics
+                .error(
+                    WEAK_CONDITION_DATA,
+                    format!("Could not find mapped weakness for {:?}", data.kind),
+                )
+                .entity(monster.game_id);
+
+            continue;
         };
 
         let mut values = LanguageMap::new();
@@ -105,67 +114,6 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
     Ok(())
 }
 
-#[derive(Debug, Serialize)]
-pub struct Weakness {
-    #[serde(flatten)]
-    pub kind: SpecialKind,
-    level: u8,
-    #[serde(serialize_with = "optional_ordered_map")]
-    condition: Option<LanguageMap>,
-}
-
-impl Weakness {
-    fn element(element: Element) -> Self {
-        Self {
-            kind: SpecialKind::Element(element),
-            level: 1,
-            condition: None,
-        }
-    }
-
-    fn status(status: Status, level: u8) -> Self {
-        Self {
-            level,
-            kind: SpecialKind::Status(status),
-            condition: None,
-        }
-    }
-
-    fn effect(effect: Effect) -> Self {
-        Self {
-            kind: SpecialKind::Effect(effect),
-            level: 1,
-            condition: None,
-        }
-    }
-}
-
-#[derive(Debug, Serialize)]
-pub struct Resistance {
-    #[serde(flatten)]
-    kind: SpecialKind,
-}
-
-impl Resistance {
-    fn status(status: Status) -> Self {
-        Self {
-            kind: SpecialKind::Status(status),
-        }
-    }
-
-    fn effect(effect: Effect) -> Self {
-        Self {
-            kind: SpecialKind::Effect(effect),
-        }
-    }
-
-    fn element(element: Element) -> Self {
-        Self {
-            kind: SpecialKind::Element(element),
-        }
-    }
-}
-
 #[derive(Debug, Deserialize)]
 struct WeakElementData {
     #[serde(rename = "_EnumValue")]
@@ -352,23 +300,6 @@ impl WeaknessConditionKind {
     }
 }
 
-#[derive(Debug, Serialize, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
-#[serde(tag = "kind", rename_all = "lowercase")]
-pub enum SpecialKind {
-    Element(Element),
-    Status(Status),
-    Effect(Effect),
-}
-
-#[derive(Debug, Serialize, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
-#[serde(tag = "effect", rename_all = "lowercase")]
-pub enum Effect {
-    Noise,
-    Flash,
-    Stun,
-    Exhaust,
-}
-
 #[derive(Debug)]
 struct Immunities {
     fire: bool,
@@ -429,16 +360,27 @@ impl Immunities {
 
 #[macro_export]
 macro_rules! add_condition {
-    ($presets:expr , $guid:expr => $monster:ident , $enum:ident :: $variant:ident) => {
+    (
+        $diagnostics:ident ;
+        $presets:expr , $guid:expr => $monster:ident , $enum:ident :: $variant:ident
+    ) => {
         if $guid.is_empty() {
             add_condition!(@ $monster resist $enum::$variant);
         } else {
-            #[allow(unused)]
-            let Some(preset) = $presets.get(&$guid) else {
-                panic!("Could not find entry for monster {}", $monster.game_id);
-            };
-
-            add_condition!(@ preset => $monster weak $enum::$variant);
+            match $presets.get(&$guid) {
+                #[allow(unused)]
+                Some(preset) => {
+                    add_condition!(@ preset => $monster weak $enum::$variant);
+                }
+                None => {
+                    $diagnostics
+                        .error(
+                            CONDITIONS_DATA,
+                            format!("Could not find condition preset {}", $guid.as_str()),
+                        )
+                        .entity($monster.game_id);
+                }
+            }
         }
     };
 
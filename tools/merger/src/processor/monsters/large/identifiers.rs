@@ -1,7 +1,10 @@
+use crate::diagnostics::Diagnostics;
 use crate::processor::monsters::MonsterId;
 use crate::processor::ReadFile;
 use anyhow::Context;
 use rslib::config::Config;
+use rslib::dump;
+use rslib::processors::inputs::monsters::identifiers::DATA;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -9,18 +12,30 @@ use std::path::{Path, PathBuf};
 
 pub(super) type IdentifierMap = HashMap<MonsterId, Identifier>;
 
-const DATA: &str = "user/monsters/EmID.json";
-
-pub(super) fn create_identifier_map(config: &Config) -> anyhow::Result<IdentifierMap> {
+pub(super) fn create_identifier_map(
+    config: &Config,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<IdentifierMap> {
     let data: Vec<IdentifierData> = Vec::read_file(config.io.output.join(DATA))?;
 
     Ok(data
         .into_iter()
         .filter_map(|v| {
             if v.name == "INVALID" || v.name == "MAX" {
-                None
-            } else {
-                Some((v.id, Identifier::from(v)))
+                return None;
+            }
+
+            let id = v.id;
+
+            match Identifier::try_from(v) {
+                Ok(identifier) => Some((id, identifier)),
+                Err(error) => {
+                    diagnostics
+                        .error(DATA, format!("Could not parse identifier name: {error}"))
+                        .entity(id);
+
+                    None
+                }
             }
         })
         .collect())
@@ -44,36 +59,22 @@ impl Identifiers {
         Self { identifiers }
     }
 
-    pub fn get(&self, game_id: MonsterId) -> &Identifier {
-        let Some(id) = self.identifiers.get(&game_id) else {
-            panic!("Could not find identifier for monster {game_id}");
-        };
-
-        id
-    }
-
+    /// Returns the path to the monster's copy of a file (see [`IdentifierName::get_path_to()`]), or
+    /// `None` if the monster has no identifier or the file doesn't exist.
     pub fn get_path_to<P, F>(
         &self,
         game_id: MonsterId,
         prefix: P,
         file_suffix: F,
-    ) -> anyhow::Result<PathBuf>
+    ) -> Option<PathBuf>
     where
         P: AsRef<Path>,
         F: AsRef<str> + Display,
     {
-        let ident = self
-            .identifiers
-            .get(&game_id)
-            .context("Could not find identifier by game ID")?;
-
+        let ident = self.identifiers.get(&game_id)?;
         let path = ident.name.get_path_to(prefix, file_suffix);
 
-        if !path.exists() {
-            panic!("File at constructed path {path:?} does not exist");
-        }
-
-        Ok(path)
+        dump::locate(&path).is_some().then_some(path)
     }
 }
 
@@ -82,14 +83,13 @@ pub(super) struct Identifier {
     pub name: IdentifierName,
 }
 
-impl From<IdentifierData> for Identifier {
-    fn from(value: IdentifierData) -> Self {
-        Self {
-            name: value
-                .name
-                .try_into()
-                .expect("Could not parse identifier name"),
-        }
+impl TryFrom<IdentifierData> for Identifier {
+    type Error = anyhow::Error;
+
+    fn try_from(value: IdentifierData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: value.name.try_into()?,
+        })
     }
 }
 
@@ -121,7 +121,7 @@ impl IdentifierName {
 
         let path = prefix.join(format!("{}{file_suffix}", self.get_path_name()));
 
-        if path.exists() {
+        if dump::locate(&path).is_some() {
             path
         } else {
             prefix.join(format!("{}{file_suffix}", self.get_fallback_path_name()))
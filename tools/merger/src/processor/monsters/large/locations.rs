@@ -1,14 +1,19 @@
-use crate::processor::locations::{Stage, OUTPUT};
 use crate::processor::monsters::large::RunContext;
-use crate::processor::monsters::MonsterId;
 use crate::processor::ReadFile;
+use mhdb_model::monsters::MonsterId;
+use mhdb_model::Database;
 use rslib::config::Config;
+use rslib::processors::inputs::monsters::locations::DATA;
 use serde::Deserialize;
 
-const DATA: &str = "user/monsters/EnemyReportBossData.json";
-
-pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Result<()> {
-    let stages: Vec<Stage> = Vec::read_file(config.io.output.join(OUTPUT))?;
+pub(super) fn process(
+    config: &Config,
+    context: &mut RunContext,
+    db: &Database,
+) -> anyhow::Result<()> {
+    // Locations always run along with monsters (see `Processor::dependencies()`), so the stage
+    // list merged in this run is complete.
+    let stages = &db.stages;
     let data: Vec<ReportBossData> = Vec::read_file(config.io.output.join(DATA))?;
 
     for data in data {
@@ -16,7 +21,7 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
             continue;
         };
 
-        for stage in &stages {
+        for stage in stages {
             if data.stage.bits() & stage.bitmask_value > 0 {
                 monster.locations.push(stage.game_id);
             }
@@ -1,22 +1,18 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
-use crate::processor::locations::StageId;
-use crate::processor::monsters::large::effectives::{Resistance, SpecialKind, Weakness};
 use crate::processor::monsters::large::identifiers::{IdentifierMap, Identifiers};
-use crate::processor::monsters::large::parts::Part;
-use crate::processor::monsters::large::rewards::Reward;
-use crate::processor::monsters::large::size::Size;
-use crate::processor::monsters::{
-    CommonData, MonsterId, SpeciesKind, MONSTER_DATA, MONSTER_STRINGS, REFS_FIELD,
-};
-use crate::processor::{
-    LanguageMap, Lookup, LookupMap, PopulateStrings, ReadFile, WriteFile,
-};
-use crate::serde::ordered_map;
-use anyhow::Context;
+use crate::processor::monsters::{CommonData, MONSTER_DATA, MONSTER_STRINGS, REFS_FIELD};
+use crate::processor::{LanguageMap, Lookup, LookupMap, PopulateStrings, ReadFile};
+use mhdb_model::monsters::MonsterId;
+use mhdb_model::monsters::large::parts::PartKind;
+use mhdb_model::monsters::large::size::Size;
+use mhdb_model::monsters::large::{LargeMonster, Variant, VariantKind};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::Serialize;
-use strum::{EnumIter, IntoEnumIterator};
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
 
 mod effectives;
 mod identifiers;
@@ -25,8 +21,6 @@ mod parts;
 mod rewards;
 mod size;
 
-const OUTPUT: &str = "merged/LargeMonsters.json";
-
 const IGNORED_IDS: &[isize] = &[
     // This is the "High Purrformance Barrel Puncher" and it's stupid and breaks my parser and it
     // isn't even a real monster anyway, no matter what its mother told it growing up.
@@ -38,6 +32,9 @@ struct RunContext {
     pub monsters: Vec<LargeMonster>,
     pub lookup: LookupMap,
     pub identifiers: Identifiers,
+
+    /// Maps each monster's break reward table indexes to the part that awards them.
+    pub break_rewards: HashMap<MonsterId, HashMap<i8, PartKind>>,
 }
 
 impl RunContext {
@@ -53,21 +50,29 @@ impl RunContext {
     }
 }
 
-pub(super) fn process(config: &Config) -> anyhow::Result<()> {
+pub(super) fn process(
+    config: &Config,
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     let field_refs = Msg::read_file(config.io.output.join(REFS_FIELD))?;
-    let placeholders = ApplyContext::new(vec![&field_refs]);
+    let placeholders = ApplyContext::new(vec![&field_refs])
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
+    let rich_text = config.merger.rich_text;
 
     let data: Vec<CommonData> = Vec::read_file(config.io.output.join(MONSTER_DATA))?;
     let strings = Msg::read_file(config.io.output.join(MONSTER_STRINGS))?;
 
-    let mut context = RunContext::new(identifiers::create_identifier_map(config)?);
+    let mut context = RunContext::new(identifiers::create_identifier_map(config, diagnostics)?);
 
     for data in data {
         if data.large_monster_icon == 0 || IGNORED_IDS.contains(&data.id) {
             continue;
         }
 
-        let mut monster = LargeMonster::from(&data);
+        let mut monster = monster_from_data(&data);
         strings.populate(&data.name_guid, &mut monster.names);
 
         // Some monsters are not implemented yet, which can be detected by the monster entry having
@@ -77,17 +82,35 @@ pub(super) fn process(config: &Config) -> anyhow::Result<()> {
         }
 
         strings.populate(&data.description_guid, &mut monster.descriptions);
-        Placeholder::process(&mut monster.descriptions, &placeholders);
+        let errors = Placeholder::process(
+            &mut monster.descriptions,
+            rich_text.then_some(&mut monster.rich_descriptions),
+            &mut monster.descriptions_variants,
+            &placeholders,
+        );
+        diagnostics.warn_placeholders(MONSTER_STRINGS, monster.game_id, errors);
 
         strings.populate(&data.features_guid, &mut monster.features);
-        Placeholder::process(&mut monster.features, &placeholders);
+        let errors = Placeholder::process(
+            &mut monster.features,
+            rich_text.then_some(&mut monster.rich_features),
+            &mut monster.features_variants,
+            &placeholders,
+        );
+        diagnostics.warn_placeholders(MONSTER_STRINGS, monster.game_id, errors);
 
         strings.populate(&data.tips_guid, &mut monster.tips);
-        Placeholder::process(&mut monster.tips, &placeholders);
+        let errors = Placeholder::process(
+            &mut monster.tips,
+            rich_text.then_some(&mut monster.rich_tips),
+            &mut monster.tips_variants,
+            &placeholders,
+        );
+        diagnostics.warn_placeholders(MONSTER_STRINGS, monster.game_id, errors);
 
         for variant in VariantKind::iter() {
             let mut names = LanguageMap::new();
-            strings.populate(variant.get_guid(&data), &mut names);
+            strings.populate(variant_guid(variant, &data), &mut names);
 
             if !names.is_empty() {
                 monster.variants.push(Variant {
@@ -105,94 +128,51 @@ pub(super) fn process(config: &Config) -> anyhow::Result<()> {
 
     // Sequencing is important.
     size::process(config, &mut context)?;
-    locations::process(config, &mut context)?;
-    parts::process(config, &mut context)?;
-    rewards::process(config, &mut context)?;
+    locations::process(config, &mut context, db)?;
+    parts::process(config, &mut context, db, diagnostics)?;
+    rewards::process(config, &mut context, diagnostics)?;
 
     // Must come after parts, as it depends on part damage multiplier data
-    effectives::process(config, &mut context)?;
+    effectives::process(config, &mut context, diagnostics)?;
 
     let RunContext { mut monsters, .. } = context;
 
     monsters.sort_by_key(|v| v.game_id);
+    db.large_monsters = monsters;
 
-    monsters
-        .write_file(config.io.output.join(OUTPUT))
-        .context("Failed to write large monsters")
-}
-
-#[derive(Debug, Serialize)]
-pub(super) struct LargeMonster {
-    game_id: MonsterId,
-    species: SpeciesKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    features: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    tips: LanguageMap,
-    variants: Vec<Variant>,
-    size: Size,
-    base_health: u16,
-    locations: Vec<StageId>,
-    weaknesses: Vec<Weakness>,
-    resistances: Vec<Resistance>,
-    rewards: Vec<Reward>,
-    parts: Vec<Part>,
-}
-
-impl LargeMonster {
-    fn find_weakness_mut(&mut self, kind: SpecialKind) -> Option<&mut Weakness> {
-        self.weaknesses.iter_mut().find(|v| v.kind == kind)
-    }
+    Ok(())
 }
 
-impl From<&CommonData> for LargeMonster {
-    fn from(value: &CommonData) -> Self {
-        Self {
-            game_id: value.id,
-            species: value.species_kind,
-            names: LanguageMap::new(),
-            descriptions: LanguageMap::new(),
-            features: LanguageMap::new(),
-            tips: LanguageMap::new(),
-            variants: Vec::new(),
-            size: Size::default(),
-            base_health: 0,
-            locations: Vec::new(),
-            weaknesses: Vec::new(),
-            resistances: Vec::new(),
-            rewards: Vec::new(),
-            parts: Vec::new(),
-        }
+fn monster_from_data(value: &CommonData) -> LargeMonster {
+    LargeMonster {
+        game_id: value.id,
+        species: value.species_kind.into(),
+        names: LanguageMap::new(),
+        descriptions: LanguageMap::new(),
+        rich_descriptions: RichTextMap::new(),
+        descriptions_variants: VariantMap::new(),
+        features: LanguageMap::new(),
+        rich_features: RichTextMap::new(),
+        features_variants: VariantMap::new(),
+        tips: LanguageMap::new(),
+        rich_tips: RichTextMap::new(),
+        tips_variants: VariantMap::new(),
+        variants: Vec::new(),
+        size: Size::default(),
+        base_health: 0,
+        locations: Vec::new(),
+        weaknesses: Vec::new(),
+        resistances: Vec::new(),
+        rewards: Vec::new(),
+        parts: Vec::new(),
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Variant {
-    kind: VariantKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-}
-
-#[derive(Debug, Serialize, Ord, PartialOrd, Eq, PartialEq, EnumIter, Copy, Clone)]
-#[serde(rename_all = "kebab-case")]
-enum VariantKind {
-    Alpha,
-    Tempered,
-    ArchTempered,
-    Frenzied,
-}
-
-impl VariantKind {
-    fn get_guid<'a>(&self, data: &'a CommonData) -> &'a str {
-        match self {
-            Self::Alpha => &data.alpha_name_guid,
-            Self::Tempered => &data.tempered_name_guid,
-            Self::Frenzied => &data.frenzied_name_guid,
-            Self::ArchTempered => &data.archtempered_name_guid,
-        }
+fn variant_guid(variant: VariantKind, data: &CommonData) -> &str {
+    match variant {
+        VariantKind::Alpha => &data.alpha_name_guid,
+        VariantKind::Tempered => &data.tempered_name_guid,
+        VariantKind::Frenzied => &data.frenzied_name_guid,
+        VariantKind::ArchTempered => &data.archtempered_name_guid,
     }
 }
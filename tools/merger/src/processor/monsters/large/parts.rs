@@ -1,65 +1,91 @@
+use crate::diagnostics::Diagnostics;
 use crate::processor::monsters::large::RunContext;
-use crate::processor::weapons::insect_glaive::KinsectEssenceKind;
-use crate::processor::{LanguageMap, PopulateStrings, ReadFile, WriteFile};
-use crate::serde::ordered_map;
-use anyhow::Context;
+use crate::processor::weapons::insect_glaive::KinsectEssenceKindCode;
+use crate::processor::{LanguageMap, PopulateStrings, ReadFile};
+use mhdb_model::monsters::large::parts::{Multipliers, Part, PartKind, PartName};
+use mhdb_model::Database;
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::monsters::parts::{
+    BREAK_REWARDS_SUFFIX, DATA_PREFIX, DATA_SUFFIX, STRINGS, TYPE_DATA,
+};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
-const DATA_PREFIX: &str = "user/monsters/parts";
-const DATA_SUFFIX: &str = "_Param_Parts.json";
-const TYPE_DATA: &str = "user/monsters/EnemyPartsTypeData.json";
-const BREAK_REWARDS_SUFFIX: &str = "_Param_PartsBreakReward.json";
-
-const STRINGS: &str = "msg/EnemyPartsTypeName.json";
-
-const PART_NAMES_OUTPUT: &str = "merged/PartNames.json";
-
-pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Result<()> {
+pub(super) fn process(
+    config: &Config,
+    context: &mut RunContext,
+    db: &mut Database,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     let types: Vec<TypeData> = Vec::read_file(config.io.output.join(TYPE_DATA))?;
-    let types: HashMap<PartKind, TypeData> = types.into_iter().map(|v| (v.kind, v)).collect();
+    let types: HashMap<PartKind, TypeData> =
+        types.into_iter().map(|v| (v.kind.into(), v)).collect();
 
     let strings = Msg::read_file(config.io.output.join(STRINGS))?;
     let mut part_names: HashMap<PartKind, PartName> = HashMap::new();
 
     for monster in context.monsters.iter_mut() {
         let prefix = config.io.output.join(DATA_PREFIX);
-        let path = context
+
+        let Some(path) = context
             .identifiers
-            .get_path_to(monster.game_id, prefix, DATA_SUFFIX)?;
+            .get_path_to(monster.game_id, &prefix, DATA_SUFFIX)
+        else {
+            diagnostics
+                .error(
+                    DATA_PREFIX,
+                    format!("Could not find *{DATA_SUFFIX} for monster"),
+                )
+                .entity(monster.game_id);
 
-        let data = PartsData::read_file(path)?;
+            continue;
+        };
+
+        let file = path.strip_prefix(&config.io.output).unwrap_or(&path);
+        let data = PartsData::read_file(&path)?;
 
         monster.base_health = data.base_health;
 
+        // Parts are paired with the state that's only needed while merging, and are split apart
+        // again once break rewards have been resolved.
+        let mut parts: Vec<(PartState, Part)> = Vec::with_capacity(data.parts.len());
+
         for data in data.parts {
-            let part = Part::from(data);
+            let (state, part) = part_from_data(data);
 
             if let Entry::Vacant(entry) = part_names.entry(part.kind) {
-                let type_data = types
-                    .get(&part.kind)
-                    .context("Could not find part type data")?;
-
-                let mut part_name = PartName::new(part.kind);
-                strings.populate(&type_data.name_guid, &mut part_name.names);
-
-                entry.insert(part_name);
+                if let Some(type_data) = types.get(&part.kind) {
+                    let mut part_name = PartName {
+                        kind: part.kind,
+                        names: LanguageMap::new(),
+                    };
+
+                    strings.populate(&type_data.name_guid, &mut part_name.names);
+
+                    entry.insert(part_name);
+                } else {
+                    diagnostics
+                        .error(
+                            TYPE_DATA,
+                            format!("Could not find type data for {:?}", part.kind),
+                        )
+                        .entity(monster.game_id);
+                }
             }
 
-            monster.parts.push(part);
+            parts.push((state, part));
         }
 
-        monster.parts.sort_by_key(|v| v.kind);
+        parts.sort_by_key(|(_, part)| part.kind);
 
         for item in data.multipliers {
-            let mults = Multipliers::from(&item);
+            let mults = multipliers_from_data(&item);
 
-            for part in &mut monster.parts {
-                if part.meat_guid != item.guid {
+            for (state, part) in &mut parts {
+                if state.meat_guid != item.guid {
                     continue;
                 }
 
@@ -75,92 +101,110 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
 
         for item in data.breakables {
             let guid = match item.target_kind {
-                BreakTargetKind::Normal => &item.target,
-                BreakTargetKind::Linked => linked_lookup
-                    .get(&item.target)
-                    .context("Could not find linked GUID in lookup table")?,
+                BreakTargetKind::Normal => Some(&item.target),
+                BreakTargetKind::Linked => linked_lookup.get(&item.target),
             };
 
-            let part = monster
-                .parts
-                .iter_mut()
-                .find(|v| &v.guid == guid)
-                .context("Could not find part by GUID")?;
+            let Some(guid) = guid else {
+                diagnostics
+                    .error(file, format!("Could not find linked part {}", item.target))
+                    .entity(monster.game_id);
 
-            part.break_guids.push(item.guid);
+                continue;
+            };
+
+            let Some((state, _)) = parts.iter_mut().find(|(state, _)| &state.guid == guid) else {
+                diagnostics
+                    .error(file, format!("Could not find part by GUID {guid}"))
+                    .entity(monster.game_id);
+
+                continue;
+            };
+
+            state.break_guids.push(item.guid);
         }
 
-        let path = config.io.output.join(DATA_PREFIX);
         let path = context
             .identifiers
-            .get_path_to(monster.game_id, path, BREAK_REWARDS_SUFFIX)?;
+            .get_path_to(monster.game_id, &prefix, BREAK_REWARDS_SUFFIX);
 
-        let break_rewards: Vec<BreakRewardData> = Vec::read_file(path)?;
+        let Some(path) = path else {
+            diagnostics
+                .error(
+                    DATA_PREFIX,
+                    format!("Could not find *{BREAK_REWARDS_SUFFIX} for monster"),
+                )
+                .entity(monster.game_id);
+
+            monster.parts = parts.into_iter().map(|(_, part)| part).collect();
+            continue;
+        };
+
+        let file = path.strip_prefix(&config.io.output).unwrap_or(&path);
+        let break_rewards: Vec<BreakRewardData> = Vec::read_file(&path)?;
+
+        let mut break_reward_indexes: Vec<(i8, usize)> = Vec::new();
 
         for item in break_rewards {
             for target in item.targets {
-                let part = monster
-                    .parts
-                    .iter_mut()
-                    .find(|v| v.break_guids.contains(&target.guid))
-                    .context("Could not find part by break GUID for rewards")?;
+                let index = parts
+                    .iter()
+                    .position(|(state, _)| state.break_guids.contains(&target.guid));
+
+                let Some(index) = index else {
+                    diagnostics
+                        .error(
+                            file,
+                            format!("Could not find part by break GUID {}", target.guid),
+                        )
+                        .entity(monster.game_id);
+
+                    continue;
+                };
 
-                part.break_reward_indexes.push(item.index);
+                break_reward_indexes.push((item.index, index));
             }
         }
+
+        // If more than one part shares a reward index, the first part (in sorted order) wins.
+        let break_rewards = context.break_rewards.entry(monster.game_id).or_default();
+        break_reward_indexes.sort_by_key(|(_, index)| *index);
+
+        for (reward_index, index) in break_reward_indexes {
+            break_rewards
+                .entry(reward_index)
+                .or_insert(parts[index].1.kind);
+        }
+
+        monster.parts = parts.into_iter().map(|(_, part)| part).collect();
     }
 
-    let mut part_names: Vec<_> = part_names.values().collect();
+    let mut part_names: Vec<_> = part_names.into_values().collect();
     part_names.sort_by_key(|v| v.kind);
-    part_names.write_file(config.io.output.join(PART_NAMES_OUTPUT))?;
+    db.part_names = part_names;
 
     Ok(())
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct Part {
-    #[serde(skip)]
+/// Per-part state that's only needed to link parts to multipliers and break rewards.
+#[derive(Debug)]
+struct PartState {
     guid: String,
-    #[serde(skip)]
     meat_guid: String,
-    #[serde(skip)]
     break_guids: Vec<String>,
-    #[serde(skip)]
-    pub break_reward_indexes: Vec<i8>,
-
-    #[serde(flatten)]
-    pub kind: PartKind,
-    base_health: Option<u16>,
-    kinsect_essence: KinsectEssenceKind,
-    pub multipliers: Multipliers,
-}
-
-#[derive(Debug, Serialize, Default, Clone)]
-pub struct Multipliers {
-    pub slash: f32,
-    pub blunt: f32,
-    pub pierce: f32,
-    pub fire: f32,
-    pub water: f32,
-    pub thunder: f32,
-    pub ice: f32,
-    pub dragon: f32,
-    pub stun: f32,
 }
 
-impl From<&MultiplierData> for Multipliers {
-    fn from(value: &MultiplierData) -> Self {
-        Self {
-            slash: value.slash as f32 / 100.0,
-            blunt: value.blunt as f32 / 100.0,
-            pierce: value.pierce as f32 / 100.0,
-            fire: value.fire as f32 / 100.0,
-            water: value.water as f32 / 100.0,
-            thunder: value.thunder as f32 / 100.0,
-            ice: value.ice as f32 / 100.0,
-            dragon: value.dragon as f32 / 100.0,
-            stun: value.stun as f32 / 100.0,
-        }
+fn multipliers_from_data(value: &MultiplierData) -> Multipliers {
+    Multipliers {
+        slash: value.slash as f32 / 100.0,
+        blunt: value.blunt as f32 / 100.0,
+        pierce: value.pierce as f32 / 100.0,
+        fire: value.fire as f32 / 100.0,
+        water: value.water as f32 / 100.0,
+        thunder: value.thunder as f32 / 100.0,
+        ice: value.ice as f32 / 100.0,
+        dragon: value.dragon as f32 / 100.0,
+        stun: value.stun as f32 / 100.0,
     }
 }
 
@@ -188,24 +232,26 @@ struct MultiplierData {
     stun: u8,
 }
 
-impl From<PartData> for Part {
-    fn from(value: PartData) -> Self {
-        assert!(
-            value.health[0] >= 0.0,
-            "Part base health shouldn't be less than zero."
-        );
-
-        Self {
-            guid: value.guid,
-            meat_guid: value.meat_guid,
-            break_guids: Vec::new(),
-            break_reward_indexes: Vec::new(),
-            kind: value.kind,
-            base_health: value.has_health.then_some(value.health[0] as u16),
-            kinsect_essence: value.kinsect_essence,
-            multipliers: Multipliers::default(),
-        }
-    }
+fn part_from_data(value: PartData) -> (PartState, Part) {
+    assert!(
+        value.health[0] >= 0.0,
+        "Part base health shouldn't be less than zero."
+    );
+
+    let state = PartState {
+        guid: value.guid,
+        meat_guid: value.meat_guid,
+        break_guids: Vec::new(),
+    };
+
+    let part = Part {
+        kind: value.kind.into(),
+        base_health: value.has_health.then_some(value.health[0] as u16),
+        kinsect_essence: value.kinsect_essence.into(),
+        multipliers: Multipliers::default(),
+    };
+
+    (state, part)
 }
 
 #[derive(Debug, Deserialize)]
@@ -229,11 +275,11 @@ struct PartData {
     #[serde(rename = "_MeatGuidNormal")]
     meat_guid: String,
     #[serde(rename = "_PartsType")]
-    kind: PartKind,
+    kind: PartKindCode,
     #[serde(rename = "_Vital")]
     health: Vec<f32>,
     #[serde(rename = "_RodExtract")]
-    kinsect_essence: KinsectEssenceKind,
+    kinsect_essence: KinsectEssenceKindCode,
     #[serde(rename = "_IsEnablePartsVital")]
     has_health: bool,
 }
@@ -252,10 +298,9 @@ struct BreakRewardItem {
     guid: String,
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
-#[serde(rename_all = "kebab-case", tag = "part")]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(isize)]
-pub enum PartKind {
+enum PartKindCode {
     // region Variants
     StupidBarrelPuncher = -1,
     Invalid = 486590176,
@@ -377,10 +422,134 @@ pub enum PartKind {
     // endregion
 }
 
+impl From<PartKindCode> for PartKind {
+    fn from(value: PartKindCode) -> Self {
+        match value {
+            PartKindCode::StupidBarrelPuncher => Self::StupidBarrelPuncher,
+            PartKindCode::Invalid => Self::Invalid,
+            PartKindCode::FullBody => Self::FullBody,
+            PartKindCode::Head => Self::Head,
+            PartKindCode::UpperBody => Self::UpperBody,
+            PartKindCode::Body => Self::Body,
+            PartKindCode::Tail => Self::Tail,
+            PartKindCode::TailTip => Self::TailTip,
+            PartKindCode::Neck => Self::Neck,
+            PartKindCode::Torso => Self::Torso,
+            PartKindCode::Stomach => Self::Stomach,
+            PartKindCode::Back => Self::Back,
+            PartKindCode::FrontLegs => Self::FrontLegs,
+            PartKindCode::LeftFrontLeg => Self::LeftFrontLeg,
+            PartKindCode::RightFrontLeg => Self::RightFrontLeg,
+            PartKindCode::HindLegs => Self::HindLegs,
+            PartKindCode::LeftHindLeg => Self::LeftHindLeg,
+            PartKindCode::RightHindLeg => Self::RightHindLeg,
+            PartKindCode::LeftLeg => Self::LeftLeg,
+            PartKindCode::RightLeg => Self::RightLeg,
+            PartKindCode::LeftLegFrontAndRear => Self::LeftLegFrontAndRear,
+            PartKindCode::RightLegFrontAndRear => Self::RightLegFrontAndRear,
+            PartKindCode::LeftWing => Self::LeftWing,
+            PartKindCode::RightWing => Self::RightWing,
+            PartKindCode::Ass => Self::Ass,
+            PartKindCode::Nail => Self::Nail,
+            PartKindCode::LeftNail => Self::LeftNail,
+            PartKindCode::RightNail => Self::RightNail,
+            PartKindCode::Tongue => Self::Tongue,
+            PartKindCode::Petal => Self::Petal,
+            PartKindCode::Veil => Self::Veil,
+            PartKindCode::Saw => Self::Saw,
+            PartKindCode::Feather => Self::Feather,
+            PartKindCode::Tentacle => Self::Tentacle,
+            PartKindCode::Umbrella => Self::Umbrella,
+            PartKindCode::LeftFrontArm => Self::LeftFrontArm,
+            PartKindCode::RightFrontArm => Self::RightFrontArm,
+            PartKindCode::LeftSideArm => Self::LeftSideArm,
+            PartKindCode::RightSideArm => Self::RightSideArm,
+            PartKindCode::LeftHindArm => Self::LeftHindArm,
+            PartKindCode::RightHindArm => Self::RightHindArm,
+            PartKindCode::Head2 => Self::Head2,
+            PartKindCode::Chest => Self::Chest,
+            PartKindCode::Mantle => Self::Mantle,
+            PartKindCode::MantleUnder => Self::MantleUnder,
+            PartKindCode::PoisonousThorn => Self::PoisonousThorn,
+            PartKindCode::Antennae => Self::Antennae,
+            PartKindCode::LeftWingLegs => Self::LeftWingLegs,
+            PartKindCode::RightWingLegs => Self::RightWingLegs,
+            PartKindCode::WaterfilmRightHead => Self::WaterfilmRightHead,
+            PartKindCode::WaterfilmLeftHead => Self::WaterfilmLeftHead,
+            PartKindCode::WaterfilmRightBody => Self::WaterfilmRightBody,
+            PartKindCode::WaterfilmLeftBody => Self::WaterfilmLeftBody,
+            PartKindCode::WaterfilmRightFrontLeg => Self::WaterfilmRightFrontLeg,
+            PartKindCode::WaterfilmLeftFrontLeg => Self::WaterfilmLeftFrontLeg,
+            PartKindCode::WaterfilmTail => Self::WaterfilmTail,
+            PartKindCode::WaterfilmLeftTail => Self::WaterfilmLeftTail,
+            PartKindCode::Mouth => Self::Mouth,
+            PartKindCode::Trunk => Self::Trunk,
+            PartKindCode::LeftWingBlade => Self::LeftWingBlade,
+            PartKindCode::RightWingBlade => Self::RightWingBlade,
+            PartKindCode::FrozenCoreHead => Self::FrozenCoreHead,
+            PartKindCode::FrozenCoreTail => Self::FrozenCoreTail,
+            PartKindCode::FrozenCoreWaist => Self::FrozenCoreWaist,
+            PartKindCode::FrozenBigcoreBefore => Self::FrozenBigcoreBefore,
+            PartKindCode::FrozenBigcoreAfter => Self::FrozenBigcoreAfter,
+            PartKindCode::Nose => Self::Nose,
+            PartKindCode::HeadWear => Self::HeadWear,
+            PartKindCode::HeadHide => Self::HeadHide,
+            PartKindCode::WingArm => Self::WingArm,
+            PartKindCode::WingArmWear => Self::WingArmWear,
+            PartKindCode::LeftWingArmWear => Self::LeftWingArmWear,
+            PartKindCode::RightWingArmWear => Self::RightWingArmWear,
+            PartKindCode::LeftWingArm => Self::LeftWingArm,
+            PartKindCode::RightWingArm => Self::RightWingArm,
+            PartKindCode::LeftWingArmHide => Self::LeftWingArmHide,
+            PartKindCode::RightWingArmHide => Self::RightWingArmHide,
+            PartKindCode::Chelicerae => Self::Chelicerae,
+            PartKindCode::BothWings => Self::BothWings,
+            PartKindCode::BothWingsBlade => Self::BothWingsBlade,
+            PartKindCode::BothLeg => Self::BothLeg,
+            PartKindCode::Arm => Self::Arm,
+            PartKindCode::Leg => Self::Leg,
+            PartKindCode::Hide => Self::Hide,
+            PartKindCode::SharpCorners => Self::SharpCorners,
+            PartKindCode::NeedleHair => Self::NeedleHair,
+            PartKindCode::ParalysisCorners => Self::ParalysisCorners,
+            PartKindCode::HeadOil => Self::HeadOil,
+            PartKindCode::UmbrellaOil => Self::UmbrellaOil,
+            PartKindCode::TorsoOil => Self::TorsoOil,
+            PartKindCode::ArmOil => Self::ArmOil,
+            PartKindCode::WaterfilmRightTail => Self::WaterfilmRightTail,
+            PartKindCode::TailHair => Self::TailHair,
+            PartKindCode::StomachSecond => Self::StomachSecond,
+            PartKindCode::HeadSecond => Self::HeadSecond,
+            PartKindCode::PoisonousThornSecond => Self::PoisonousThornSecond,
+            PartKindCode::TailThird => Self::TailThird,
+            PartKindCode::TailFifth => Self::TailFifth,
+            PartKindCode::DorsalFin => Self::DorsalFin,
+            PartKindCode::HeadFirst => Self::HeadFirst,
+            PartKindCode::Corner => Self::Corner,
+            PartKindCode::Fang => Self::Fang,
+            PartKindCode::FangFirst => Self::FangFirst,
+            PartKindCode::FangSecond => Self::FangSecond,
+            PartKindCode::LeftFrontLegarmor => Self::LeftFrontLegarmor,
+            PartKindCode::RightFrontLegarmor => Self::RightFrontLegarmor,
+            PartKindCode::HeadArmor => Self::HeadArmor,
+            PartKindCode::LeftWingArmArmor => Self::LeftWingArmArmor,
+            PartKindCode::RightWingArmArmor => Self::RightWingArmArmor,
+            PartKindCode::Periscope => Self::Periscope,
+            PartKindCode::Equipment => Self::Equipment,
+            PartKindCode::EquipmentHead => Self::EquipmentHead,
+            PartKindCode::EquipmentChest => Self::EquipmentChest,
+            PartKindCode::EquipmentBack => Self::EquipmentBack,
+            PartKindCode::EquipmentLeft => Self::EquipmentLeft,
+            PartKindCode::EquipmentRight => Self::EquipmentRight,
+            PartKindCode::EquipmentTail => Self::EquipmentTail,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct TypeData {
     #[serde(rename = "_EmPartsType")]
-    kind: PartKind,
+    kind: PartKindCode,
     #[serde(rename = "_EmPartsName")]
     name_guid: String,
 }
@@ -409,20 +578,3 @@ struct LinkedPartsData {
     #[serde(rename = "_LinkPartsGuids")]
     targets: Vec<String>,
 }
-
-#[derive(Debug, Serialize)]
-struct PartName {
-    #[serde(flatten)]
-    kind: PartKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-}
-
-impl PartName {
-    fn new(kind: PartKind) -> Self {
-        Self {
-            kind,
-            names: LanguageMap::new(),
-        }
-    }
-}
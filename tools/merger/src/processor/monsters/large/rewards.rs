@@ -1,23 +1,41 @@
-use crate::processor::items::ItemId;
-use crate::processor::monsters::large::parts::PartKind;
+use crate::diagnostics::Diagnostics;
 use crate::processor::monsters::large::RunContext;
-use crate::processor::{HunterRank, ReadFile};
-use anyhow::{anyhow, Context};
+use crate::processor::ReadFile;
+use anyhow::anyhow;
+use mhdb_model::items::ItemId;
+use mhdb_model::monsters::large::rewards::{Reward, RewardSource};
+use mhdb_model::HunterRank;
 use rslib::config::Config;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::monsters::rewards::{DATA_PREFIX, DATA_SUFFIX};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
-const DATA_PREFIX: &str = "user/monsters/rewards";
-const DATA_SUFFIX: &str = "_0.json";
-
-pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Result<()> {
+pub(super) fn process(
+    config: &Config,
+    context: &mut RunContext,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     for monster in &mut context.monsters {
-        let id = context.identifiers.get(monster.game_id);
-
         let path = config.io.output.join(DATA_PREFIX);
-        let path = id.name.get_path_to(path, DATA_SUFFIX);
-        let data: Vec<RewardData> = Vec::read_file(path)?;
 
+        let Some(path) = context
+            .identifiers
+            .get_path_to(monster.game_id, path, DATA_SUFFIX)
+        else {
+            diagnostics
+                .error(
+                    DATA_PREFIX,
+                    format!("Could not find *{DATA_SUFFIX} for monster"),
+                )
+                .entity(monster.game_id);
+
+            continue;
+        };
+
+        let file = path.strip_prefix(&config.io.output).unwrap_or(&path);
+        let data: Vec<RewardData> = Vec::read_file(&path)?;
+
+        let break_rewards = context.break_rewards.get(&monster.game_id);
         let mut state = RewardKind::Inherit;
 
         for data in data {
@@ -26,15 +44,28 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
             }
 
             let source: RewardSource = if state == RewardKind::BrokenPart {
-                let part = monster
-                    .parts
-                    .iter()
-                    .find(|v| v.break_reward_indexes.contains(&data.part_index))
-                    .context("Could not find part by index")?;
+                let part = break_rewards.and_then(|v| v.get(&data.part_index));
 
-                RewardSource::BrokenPart(part.kind)
+                let Some(part) = part else {
+                    diagnostics
+                        .error(
+                            file,
+                            format!("Could not find part by index {}", data.part_index),
+                        )
+                        .entity(monster.game_id);
+
+                    continue;
+                };
+
+                RewardSource::BrokenPart(*part)
             } else {
-                state.try_into()?
+                match reward_source_from_kind(state) {
+                    Ok(v) => v,
+                    Err(error) => {
+                        diagnostics.error(file, error).entity(monster.game_id);
+                        continue;
+                    }
+                }
             };
 
             if data.low_rank_item_id != 0 {
@@ -68,16 +99,6 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
     Ok(())
 }
 
-#[derive(Debug, Serialize)]
-pub struct Reward {
-    rank: HunterRank,
-    #[serde(flatten)]
-    source: RewardSource,
-    item_id: ItemId,
-    amount: u8,
-    chance: u8,
-}
-
 #[derive(Debug, Deserialize_repr, Copy, Clone, Eq, PartialEq)]
 #[repr(isize)]
 enum RewardKind {
@@ -122,46 +143,25 @@ struct RewardData {
     high_rank_chances: [u8; 6],
 }
 
-#[derive(Debug, Serialize, Copy, Clone)]
-#[serde(tag = "kind", rename_all = "kebab-case")]
-enum RewardSource {
-    Carve,
-    CarveSevered,
-    EndemicCapture,
-    TargetReward,
-    BrokenPart(PartKind),
-    WoundDestroyed,
-    CarveRotten,
-    SlingerGather,
-    CarveRottenSevered,
-    TemperedWoundDestroyed,
-    CarveCrystallized,
-    BrokenFragment,
-}
-
-impl TryFrom<RewardKind> for RewardSource {
-    type Error = anyhow::Error;
-
-    fn try_from(value: RewardKind) -> Result<Self, Self::Error> {
-        let result = match value {
-            RewardKind::Carve => Self::Carve,
-            RewardKind::CarveSevered => Self::CarveSevered,
-            RewardKind::EndemicCapture => Self::EndemicCapture,
-            RewardKind::TargetReward => Self::TargetReward,
-            RewardKind::WoundDestroyed => Self::WoundDestroyed,
-            RewardKind::CarveRotten => Self::CarveRotten,
-            RewardKind::SlingerGather => Self::SlingerGather,
-            RewardKind::CarveRottenSevered => Self::CarveRottenSevered,
-            RewardKind::TemperedWoundDestroyed => Self::TemperedWoundDestroyed,
-            RewardKind::CarveCrystallized => Self::CarveCrystallized,
-            RewardKind::BrokenFragment => Self::BrokenFragment,
-            RewardKind::Inherit | RewardKind::BrokenPart => {
-                return Err(anyhow!(
-                    "Could not convert {value:?} directly into a reward source"
-                ));
-            }
-        };
+fn reward_source_from_kind(value: RewardKind) -> anyhow::Result<RewardSource> {
+    let result = match value {
+        RewardKind::Carve => RewardSource::Carve,
+        RewardKind::CarveSevered => RewardSource::CarveSevered,
+        RewardKind::EndemicCapture => RewardSource::EndemicCapture,
+        RewardKind::TargetReward => RewardSource::TargetReward,
+        RewardKind::WoundDestroyed => RewardSource::WoundDestroyed,
+        RewardKind::CarveRotten => RewardSource::CarveRotten,
+        RewardKind::SlingerGather => RewardSource::SlingerGather,
+        RewardKind::CarveRottenSevered => RewardSource::CarveRottenSevered,
+        RewardKind::TemperedWoundDestroyed => RewardSource::TemperedWoundDestroyed,
+        RewardKind::CarveCrystallized => RewardSource::CarveCrystallized,
+        RewardKind::BrokenFragment => RewardSource::BrokenFragment,
+        RewardKind::Inherit | RewardKind::BrokenPart => {
+            return Err(anyhow!(
+                "Could not convert {value:?} directly into a reward source"
+            ));
+        }
+    };
 
-        Ok(result)
-    }
+    Ok(result)
 }
@@ -1,10 +1,10 @@
 use crate::processor::monsters::large::RunContext;
-use crate::processor::monsters::MonsterId;
 use crate::processor::ReadFile;
+use mhdb_model::monsters::large::size::Size;
+use mhdb_model::monsters::MonsterId;
 use rslib::config::Config;
-use serde::{Deserialize, Serialize};
-
-const DATA: &str = "user/monsters/EmCommonSize.json";
+use rslib::processors::inputs::monsters::size::DATA;
+use serde::Deserialize;
 
 pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Result<()> {
     let data: Vec<SizeData> = Vec::read_file(config.io.output.join(DATA))?;
@@ -22,17 +22,6 @@ pub(super) fn process(config: &Config, context: &mut RunContext) -> anyhow::Resu
     Ok(())
 }
 
-#[derive(Debug, Serialize, Default)]
-pub(super) struct Size {
-    base: f32,
-    mini: f32,
-    mini_multiplier: f32,
-    silver: f32,
-    silver_multiplier: f32,
-    gold: f32,
-    gold_multiplier: f32,
-}
-
 #[derive(Debug, Deserialize)]
 struct SizeData {
     #[serde(rename = "_EmId")]
@@ -1,62 +1,56 @@
-use super::{LanguageMap, PopulateStrings, Processor, ReadFile, WriteFile};
-use crate::serde::ordered_map;
+use super::{LanguageMap, PopulateStrings, Processor, ReadFile};
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::should_run;
+use mhdb_model::monsters::{MonsterId, Species, SpeciesKind};
+use mhdb_model::Database;
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::monsters::{
+    MONSTER_DATA, MONSTER_STRINGS, REFS_FIELD, SPECIES_STRINGS,
+};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 use strum::{EnumIter, IntoEnumIterator};
 
 mod large;
 
-type MonsterId = isize;
-
-const REFS_FIELD: &str = "msg/RefEnvironment.json";
-
-const MONSTER_DATA: &str = "user/monsters/EnemyData.json";
-const MONSTER_STRINGS: &str = "msg/EnemyText.json";
-
-const SPECIES_STRINGS: &str = "msg/EnemySpeciesName.json";
-
-const SPECIES_OUTPUT: &str = "merged/Species.json";
-
-pub(in crate::processor) fn process(config: &Config, filters: &[Processor]) -> anyhow::Result<()> {
+pub(in crate::processor) fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> anyhow::Result<()> {
     should_run!(filters, Processor::Monsters);
 
     let species_strings = Msg::read_file(config.io.output.join(SPECIES_STRINGS))?;
     let mut species: Vec<Species> = Vec::with_capacity(species_strings.entries.len());
 
-    for kind in SpeciesKind::iter() {
-        if kind == SpeciesKind::None {
+    for code in SpeciesKindCode::iter() {
+        if code == SpeciesKindCode::None {
             continue;
         }
 
         let mut value = Species {
-            kind,
+            kind: code.into(),
             names: LanguageMap::new(),
         };
 
-        let name = String::from("EnemySpeciesName_") + &(kind as u8).to_string();
+        let name = String::from("EnemySpeciesName_") + &(code as u8).to_string();
         species_strings.populate_by_name(&name, &mut value.names);
 
         species.push(value);
     }
 
     species.sort_by_key(|v| v.kind);
-    species.write_file(config.io.output.join(SPECIES_OUTPUT))?;
+    db.species = species;
 
-    large::process(config)?;
+    large::process(config, db, references, diagnostics)?;
 
     Ok(())
 }
 
-#[derive(Debug, Serialize)]
-struct Species {
-    kind: SpeciesKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-}
-
 #[derive(Debug, Deserialize)]
 struct CommonData {
     #[serde(rename = "_enemyId")]
@@ -80,15 +74,12 @@ struct CommonData {
     #[serde(rename = "_BossIconType")]
     large_monster_icon: u8,
     #[serde(rename = "_Species")]
-    species_kind: SpeciesKind,
+    species_kind: SpeciesKindCode,
 }
 
-#[derive(
-    Debug, Deserialize_repr, Serialize, EnumIter, Copy, Clone, Ord, PartialOrd, Eq, PartialEq,
-)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Deserialize_repr, EnumIter, Copy, Clone, Eq, PartialEq)]
 #[repr(u8)]
-enum SpeciesKind {
+enum SpeciesKindCode {
     None = 0,
     FlyingWyvern = 1,
     Fish = 2,
@@ -112,3 +103,32 @@ enum SpeciesKind {
     DemiElder = 20,
     Machine = 21,
 }
+
+impl From<SpeciesKindCode> for SpeciesKind {
+    fn from(value: SpeciesKindCode) -> Self {
+        match value {
+            SpeciesKindCode::None => Self::None,
+            SpeciesKindCode::FlyingWyvern => Self::FlyingWyvern,
+            SpeciesKindCode::Fish => Self::Fish,
+            SpeciesKindCode::Herbivore => Self::Herbivore,
+            SpeciesKindCode::Lynian => Self::Lynian,
+            SpeciesKindCode::Neopteron => Self::Neopteron,
+            SpeciesKindCode::Carapaceon => Self::Carapaceon,
+            SpeciesKindCode::FangedBeast => Self::FangedBeast,
+            SpeciesKindCode::BirdWyvern => Self::BirdWyvern,
+            SpeciesKindCode::PiscineWyvern => Self::PiscineWyvern,
+            SpeciesKindCode::Leviathan => Self::Leviathan,
+            SpeciesKindCode::BruteWyvern => Self::BruteWyvern,
+            SpeciesKindCode::FangedWyvern => Self::FangedWyvern,
+            SpeciesKindCode::Amphibian => Self::Amphibian,
+            SpeciesKindCode::Temnoceran => Self::Temnoceran,
+            SpeciesKindCode::SnakeWyvern => Self::SnakeWyvern,
+            SpeciesKindCode::ElderDragon => Self::ElderDragon,
+            SpeciesKindCode::Cephalopod => Self::Cephalopod,
+            SpeciesKindCode::Construct => Self::Construct,
+            SpeciesKindCode::Wingdrake => Self::Wingdrake,
+            SpeciesKindCode::DemiElder => Self::DemiElder,
+            SpeciesKindCode::Machine => Self::Machine,
+        }
+    }
+}
@@ -1,26 +1,27 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
-use crate::processor::armor::{Bonus, BonusRank};
 use crate::processor::{
-    armor, LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile, Result, WriteFile,
+    LanguageMap, Lookup, LookupMap, PopulateStrings, Processor, ReadFile, Result,
 };
-use crate::serde::is_default;
-use crate::serde::ordered_map;
 use crate::should_run;
 use indicatif::ProgressBar;
+use mhdb_model::armor::{Bonus, BonusRank};
+use mhdb_model::skills::{IconKind, Rank, Skill, SkillKind};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::skills::{RANK_DATA, RANK_STRINGS, SKILL_DATA, SKILL_STRINGS};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
-const SKILL_DATA: &str = "user/SkillCommonData.json";
-const RANK_DATA: &str = "user/SkillData.json";
-
-const SKILL_STRINGS: &str = "msg/SkillCommon.json";
-const RANK_STRINGS: &str = "msg/Skill.json";
-
-const OUTPUT: &str = "merged/Skill.json";
-
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
+pub fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> Result {
     should_run!(filters, Processor::Skill);
 
     let data: Vec<SkillData> = Vec::read_file(config.io.output.join(SKILL_DATA))?;
@@ -31,7 +32,10 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
     let mut merged: Vec<Skill> = Vec::with_capacity(data.len());
     let mut lookup = LookupMap::with_capacity(data.len());
 
-    let placeholder_context = ApplyContext::empty();
+    let placeholder_context = ApplyContext::empty()
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
+    let rich_text = config.merger.rich_text;
 
     for data in data {
         progress.inc(1);
@@ -46,7 +50,13 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         }
 
         strings.populate(&data.description_guid, &mut skill.descriptions);
-        Placeholder::process(&mut skill.descriptions, &placeholder_context);
+        let errors = Placeholder::process(
+            &mut skill.descriptions,
+            rich_text.then_some(&mut skill.rich_descriptions),
+            &mut skill.descriptions_variants,
+            &placeholder_context,
+        );
+        diagnostics.warn_placeholders(SKILL_STRINGS, skill.game_id, errors);
 
         lookup.insert(skill.game_id, merged.len());
         merged.push(skill);
@@ -73,7 +83,13 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         strings.populate(&data.name_guid, &mut rank.names);
 
         strings.populate(&data.description_guid, &mut rank.descriptions);
-        Placeholder::process(&mut rank.descriptions, &placeholder_context);
+        let errors = Placeholder::process(
+            &mut rank.descriptions,
+            rich_text.then_some(&mut rank.rich_descriptions),
+            &mut rank.descriptions_variants,
+            &placeholder_context.with_values(&rank.values),
+        );
+        diagnostics.warn_placeholders(RANK_STRINGS, data.skill_id, errors);
 
         skill.ranks.push(rank);
     }
@@ -93,10 +109,11 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
 
     progress.finish_and_clear();
 
-    let mut data: Vec<armor::Set> = Vec::read_file(config.io.output.join(armor::OUTPUT))?;
-    let progress = ProgressBar::new(data.len() as u64);
+    // Armor always runs along with skills (see `Processor::dependencies()`), so set and group
+    // bonuses can be filled in on the armor merged in this run.
+    let progress = ProgressBar::new(db.armor.len() as u64);
 
-    for data in &mut data {
+    for data in &mut db.armor {
         progress.inc(1);
 
         // We only need to consider the first piece of armor in the set, since every piece should
@@ -125,6 +142,7 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
 
             // TODO Deprecated
             // region Deprecated: To be removed after 2026-05-01
+            #[allow(deprecated)]
             let deprecated_bonus = match skill.kind {
                 SkillKind::Group => &mut data.group_bonus,
                 SkillKind::Set => &mut data.set_bonus,
@@ -148,28 +166,12 @@ pub fn process(config: &Config, filters: &[Processor]) -> Result {
         }
     }
 
-    data.write_file(config.io.output.join(armor::OUTPUT))?;
-
     progress.finish_and_clear();
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(OUTPUT))
-}
+    db.skills = merged;
 
-#[derive(Debug, Serialize)]
-struct Skill {
-    game_id: isize,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(
-        serialize_with = "ordered_map",
-        skip_serializing_if = "LanguageMap::is_empty"
-    )]
-    descriptions: LanguageMap,
-    ranks: Vec<Rank>,
-    kind: SkillKind,
-    icon: IconKind,
-    icon_id: u8,
+    Ok(())
 }
 
 impl From<&SkillData> for Skill {
@@ -178,28 +180,16 @@ impl From<&SkillData> for Skill {
             game_id: value.id,
             names: LanguageMap::new(),
             descriptions: LanguageMap::new(),
+            rich_descriptions: RichTextMap::new(),
+            descriptions_variants: VariantMap::new(),
             ranks: Vec::new(),
-            kind: value.kind,
-            icon: value.icon,
+            kind: value.kind.into(),
+            icon: value.icon.into(),
             icon_id: value.icon as u8,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Rank {
-    level: u8,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    #[serde(
-        skip_serializing_if = "LanguageMap::is_empty",
-        serialize_with = "ordered_map"
-    )]
-    names: LanguageMap,
-    #[serde(skip_serializing_if = "is_default")]
-    set_pieces_required: u8,
-}
-
 impl From<&RankData> for Rank {
     fn from(value: &RankData) -> Self {
         Self {
@@ -207,6 +197,15 @@ impl From<&RankData> for Rank {
             set_pieces_required: 0,
             names: LanguageMap::new(),
             descriptions: LanguageMap::new(),
+            rich_descriptions: RichTextMap::new(),
+            descriptions_variants: VariantMap::new(),
+            values: value.values.clone(),
+            open_skill_ids: value
+                .open_skill_ids
+                .iter()
+                .copied()
+                .filter(|v| *v != 0)
+                .collect(),
         }
     }
 }
@@ -220,9 +219,9 @@ struct SkillData {
     #[serde(rename = "_skillExplain")]
     description_guid: String,
     #[serde(rename = "_skillCategory")]
-    kind: SkillKind,
+    kind: SkillKindCode,
     #[serde(rename = "_SkillIconType")]
-    icon: IconKind,
+    icon: IconKindCode,
 }
 
 #[derive(Debug, Deserialize)]
@@ -235,28 +234,35 @@ struct RankData {
     name_guid: String,
     #[serde(rename = "_skillExplain")]
     description_guid: String,
+    #[serde(rename = "_value", default)]
+    values: Vec<i32>,
+    #[serde(rename = "_openSkill", default)]
+    open_skill_ids: Vec<isize>,
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all(serialize = "kebab-case"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-pub enum SkillKind {
+enum SkillKindCode {
     Armor,
     Set,
     Group,
     Weapon,
 }
 
-impl SkillKind {
-    fn is_armor_bonus(&self) -> bool {
-        matches!(self, Self::Set | Self::Group)
+impl From<SkillKindCode> for SkillKind {
+    fn from(value: SkillKindCode) -> Self {
+        match value {
+            SkillKindCode::Armor => Self::Armor,
+            SkillKindCode::Set => Self::Set,
+            SkillKindCode::Group => Self::Group,
+            SkillKindCode::Weapon => Self::Weapon,
+        }
     }
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all(serialize = "kebab-case"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-pub enum IconKind {
+enum IconKindCode {
     None = 0,
     Attack = 1,
     Affinity = 2,
@@ -273,3 +279,25 @@ pub enum IconKind {
     Group = 13,
     Set = 14,
 }
+
+impl From<IconKindCode> for IconKind {
+    fn from(value: IconKindCode) -> Self {
+        match value {
+            IconKindCode::None => Self::None,
+            IconKindCode::Attack => Self::Attack,
+            IconKindCode::Affinity => Self::Affinity,
+            IconKindCode::Element => Self::Element,
+            IconKindCode::Handicraft => Self::Handicraft,
+            IconKindCode::Ranged => Self::Ranged,
+            IconKindCode::Defense => Self::Defense,
+            IconKindCode::Health => Self::Health,
+            IconKindCode::Stamina => Self::Stamina,
+            IconKindCode::Offense => Self::Offense,
+            IconKindCode::Utility => Self::Utility,
+            IconKindCode::Item => Self::Item,
+            IconKindCode::Gathering => Self::Gathering,
+            IconKindCode::Group => Self::Group,
+            IconKindCode::Set => Self::Set,
+        }
+    }
+}
@@ -1,27 +1,22 @@
 use crate::is_weapon;
 use crate::processor::weapons::{ProcessorDefinition, WeaponKindCode};
 use crate::processor::Processor;
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::bow::{Bow, Coating};
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::Bow,
-        input_prefix: "Bow",
-        output_prefix: None,
+        output: |db| &mut db.bows,
         callback: None,
         kind: WeaponKindCode::Bow,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct Bow {
-    coatings: Vec<Coating>,
-}
-
 impl From<&BowData> for Bow {
     fn from(value: &BowData) -> Self {
         Self {
-            coatings: Coating::from_data(value.coatings),
+            coatings: coatings_from_data(value.coatings),
         }
     }
 }
@@ -38,37 +33,22 @@ is_weapon!(is_bow() => WeaponKindCode::Bow);
 
 type CoatingData = [bool; 8];
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "kebab-case")]
-pub(super) enum Coating {
-    CloseRange,
-    Power,
-    Pierce,
-    Paralysis,
-    Poison,
-    Sleep,
-    Blast,
-    Exhaust,
-}
-
-impl Coating {
-    fn from_data(values: CoatingData) -> Vec<Self> {
-        values
-            .into_iter()
-            .enumerate()
-            .filter_map(|(index, value)| {
-                value.then_some(match index {
-                    0 => Self::CloseRange,
-                    1 => Self::Power,
-                    2 => Self::Pierce,
-                    3 => Self::Paralysis,
-                    4 => Self::Poison,
-                    5 => Self::Sleep,
-                    6 => Self::Blast,
-                    7 => Self::Exhaust,
-                    x => panic!("Unrecognized coating index {x}"),
-                })
+fn coatings_from_data(values: CoatingData) -> Vec<Coating> {
+    values
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, value)| {
+            value.then_some(match index {
+                0 => Coating::CloseRange,
+                1 => Coating::Power,
+                2 => Coating::Pierce,
+                3 => Coating::Paralysis,
+                4 => Coating::Poison,
+                5 => Coating::Sleep,
+                6 => Coating::Blast,
+                7 => Coating::Exhaust,
+                x => panic!("Unrecognized coating index {x}"),
             })
-            .collect()
-    }
+        })
+        .collect()
 }
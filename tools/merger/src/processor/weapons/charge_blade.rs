@@ -1,33 +1,26 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::charge_blade::{ChargeBlade, PhialKind};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::ChargeBlade,
-        input_prefix: "ChargeAxe",
-        output_prefix: Some("ChargeBlade"),
+        output: |db| &mut db.charge_blades,
         callback: None,
         kind: WeaponKindCode::ChargeBlade,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct ChargeBlade {
-    phial: PhialKind,
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 impl From<&ChargeBladeData> for ChargeBlade {
     fn from(value: &ChargeBladeData) -> Self {
         Self {
-            phial: value.phial,
-            sharpness: Sharpness::from_data(value.sharpness),
+            phial: value.phial.into(),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
@@ -38,7 +31,7 @@ pub(super) struct ChargeBladeData {
     #[serde(rename = "_Type", deserialize_with = "is_charge_blade")]
     _type: WeaponKindCode,
     #[serde(rename = "_Wp09BinType")]
-    phial: PhialKind,
+    phial: PhialKindCode,
     #[serde(rename = "_SharpnessValList")]
     sharpness: SharpnessData,
     #[serde(rename = "_TakumiValList")]
@@ -47,10 +40,18 @@ pub(super) struct ChargeBladeData {
 
 is_weapon!(is_charge_blade() => WeaponKindCode::ChargeBlade);
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all(serialize = "lowercase"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-enum PhialKind {
+enum PhialKindCode {
     Impact = 0,
     Element,
 }
+
+impl From<PhialKindCode> for PhialKind {
+    fn from(value: PhialKindCode) -> Self {
+        match value {
+            PhialKindCode::Impact => Self::Impact,
+            PhialKindCode::Element => Self::Element,
+        }
+    }
+}
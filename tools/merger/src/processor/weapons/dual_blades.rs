@@ -1,26 +1,20 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::dual_blades::DualBlades;
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::DualBlades,
-        input_prefix: "TwinSword",
-        output_prefix: Some("DualBlades"),
+        output: |db| &mut db.dual_blades,
         callback: None,
         kind: WeaponKindCode::DualBlades,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct DualBlades {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct DualBladesData {
     #[serde(rename = "_Type", deserialize_with = "is_dual_blades")]
@@ -36,7 +30,7 @@ is_weapon!(is_dual_blades() => WeaponKindCode::DualBlades);
 impl From<&DualBladesData> for DualBlades {
     fn from(value: &DualBladesData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
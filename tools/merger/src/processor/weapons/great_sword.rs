@@ -1,26 +1,20 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::great_sword::GreatSword;
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::GreatSword,
-        input_prefix: "LongSword",
-        output_prefix: Some("GreatSword"),
+        output: |db| &mut db.great_swords,
         callback: None,
         kind: WeaponKindCode::GreatSword,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct GreatSword {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct GreatSwordData {
     #[serde(rename = "_Type", deserialize_with = "is_great_sword")]
@@ -36,7 +30,7 @@ is_weapon!(is_great_sword() => WeaponKindCode::GreatSword);
 impl From<&GreatSwordData> for GreatSword {
     fn from(value: &GreatSwordData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
@@ -1,35 +1,27 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::gunlance::{Gunlance, ShellKind};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::Gunlance,
-        input_prefix: "GunLance",
-        output_prefix: Some("Gunlance"),
+        output: |db| &mut db.gunlances,
         callback: None,
         kind: WeaponKindCode::Gunlance,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct Gunlance {
-    shell: ShellKind,
-    shell_level: u8,
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct GunlanceData {
     #[serde(rename = "_Type", deserialize_with = "is_gunlance")]
     _type: WeaponKindCode,
     #[serde(rename = "_Wp07ShellType")]
-    shell: ShellKind,
+    shell: ShellKindCode,
     #[serde(rename = "_Wp07ShellLv")]
     shell_level: ShellLevel,
     #[serde(rename = "_SharpnessValList")]
@@ -41,9 +33,9 @@ pub(super) struct GunlanceData {
 impl From<&GunlanceData> for Gunlance {
     fn from(value: &GunlanceData) -> Self {
         Self {
-            shell: value.shell,
+            shell: value.shell.into(),
             shell_level: value.shell_level.as_level_number(),
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
@@ -51,16 +43,25 @@ impl From<&GunlanceData> for Gunlance {
 
 is_weapon!(is_gunlance() => WeaponKindCode::Gunlance);
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all(serialize = "lowercase"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(isize)]
-enum ShellKind {
+enum ShellKindCode {
     Normal = -324406336,
     Wide = -1732758016,
     Long = 203273856,
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
+impl From<ShellKindCode> for ShellKind {
+    fn from(value: ShellKindCode) -> Self {
+        match value {
+            ShellKindCode::Normal => Self::Normal,
+            ShellKindCode::Wide => Self::Wide,
+            ShellKindCode::Long => Self::Long,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(isize)]
 enum ShellLevel {
     LV1 = 1226920576,
@@ -1,26 +1,20 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::hammer::Hammer;
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::Hammer,
-        input_prefix: "Hammer",
-        output_prefix: None,
+        output: |db| &mut db.hammers,
         callback: None,
         kind: WeaponKindCode::Hammer,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct Hammer {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct HammerData {
     #[serde(rename = "_Type", deserialize_with = "is_hammer")]
@@ -34,7 +28,7 @@ pub(super) struct HammerData {
 impl From<&HammerData> for Hammer {
     fn from(value: &HammerData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
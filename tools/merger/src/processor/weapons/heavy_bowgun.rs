@@ -1,24 +1,19 @@
 use crate::is_weapon;
 use crate::processor::weapons::{ProcessorDefinition, WeaponKindCode};
 use crate::processor::Processor;
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::heavy_bowgun::{Ammo, AmmoKind, HeavyBowgun};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::HeavyBowgun,
-        input_prefix: "HeavyBowgun",
-        output_prefix: None,
+        output: |db| &mut db.heavy_bowguns,
         callback: None,
         kind: WeaponKindCode::HeavyBowgun,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct HeavyBowgun {
-    ammo: Vec<Ammo>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct HeavyBowgunData {
     #[serde(rename = "_Type", deserialize_with = "is_heavy_bowgun")]
@@ -32,96 +27,60 @@ pub(super) struct HeavyBowgunData {
 impl From<&HeavyBowgunData> for HeavyBowgun {
     fn from(value: &HeavyBowgunData) -> Self {
         Self {
-            ammo: Ammo::from_data(value.ammo_levels, value.ammo_capacities),
+            ammo: ammo_from_data(value.ammo_levels, value.ammo_capacities),
         }
     }
 }
 
 is_weapon!(is_heavy_bowgun() => WeaponKindCode::HeavyBowgun);
 
-#[derive(Debug, Serialize, Hash, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
-#[serde(rename_all = "lowercase")]
-pub(super) enum AmmoKind {
-    Normal,
-    Pierce,
-    Spread,
-    Slicing,
-    Sticky,
-    Cluster,
-    Wyvern,
-    Poison,
-    Paralysis,
-    Sleep,
-    Flaming,
-    Water,
-    Freeze,
-    Thunder,
-    Dragon,
-    Recover,
-    Demon,
-    Armor,
-    Exhaust,
-    Tranq,
-}
-
-impl AmmoKind {
-    pub fn from_index(index: usize) -> Self {
-        assert!(index < 20);
+pub(super) fn ammo_kind_from_index(index: usize) -> AmmoKind {
+    assert!(index < 20);
 
-        match index {
-            0 => Self::Normal,
-            1 => Self::Pierce,
-            2 => Self::Spread,
-            3 => Self::Sticky,
-            4 => Self::Cluster,
-            5 => Self::Slicing,
-            6 => Self::Wyvern,
-            7 => Self::Flaming,
-            8 => Self::Water,
-            9 => Self::Thunder,
-            10 => Self::Freeze,
-            11 => Self::Dragon,
-            12 => Self::Poison,
-            13 => Self::Paralysis,
-            14 => Self::Sleep,
-            15 => Self::Demon,
-            16 => Self::Armor,
-            17 => Self::Recover,
-            18 => Self::Exhaust,
-            19 => Self::Tranq,
-            _ => unreachable!("Value out of range: {index}"),
-        }
+    match index {
+        0 => AmmoKind::Normal,
+        1 => AmmoKind::Pierce,
+        2 => AmmoKind::Spread,
+        3 => AmmoKind::Sticky,
+        4 => AmmoKind::Cluster,
+        5 => AmmoKind::Slicing,
+        6 => AmmoKind::Wyvern,
+        7 => AmmoKind::Flaming,
+        8 => AmmoKind::Water,
+        9 => AmmoKind::Thunder,
+        10 => AmmoKind::Freeze,
+        11 => AmmoKind::Dragon,
+        12 => AmmoKind::Poison,
+        13 => AmmoKind::Paralysis,
+        14 => AmmoKind::Sleep,
+        15 => AmmoKind::Demon,
+        16 => AmmoKind::Armor,
+        17 => AmmoKind::Recover,
+        18 => AmmoKind::Exhaust,
+        19 => AmmoKind::Tranq,
+        _ => unreachable!("Value out of range: {index}"),
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct Ammo {
-    pub kind: AmmoKind,
-    pub level: u8,
-    pub capacity: u8,
-}
-
-impl Ammo {
-    pub fn from_data(levels: AmmoLevelData, capacities: AmmoCapacityData) -> Vec<Self> {
-        levels
-            .iter()
-            .zip(capacities)
-            .enumerate()
-            .filter_map(|(index, (level, capacity))| {
-                let level = level.as_level_number();
+fn ammo_from_data(levels: AmmoLevelData, capacities: AmmoCapacityData) -> Vec<Ammo> {
+    levels
+        .iter()
+        .zip(capacities)
+        .enumerate()
+        .filter_map(|(index, (level, capacity))| {
+            let level = level.as_level_number();
 
-                if level == 0 {
-                    return None;
-                }
+            if level == 0 {
+                return None;
+            }
 
-                Some(Ammo {
-                    kind: AmmoKind::from_index(index),
-                    level,
-                    capacity,
-                })
+            Some(Ammo {
+                kind: ammo_kind_from_index(index),
+                level,
+                capacity,
             })
-            .collect()
-    }
+        })
+        .collect()
 }
 
 pub(super) type AmmoLevelData = [AmmoLevel; 20];
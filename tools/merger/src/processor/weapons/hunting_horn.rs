@@ -1,18 +1,26 @@
+use crate::diagnostics::Diagnostics;
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, SubProcess, Weapon, WeaponData,
-    WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, SubProcess,
+    WeaponData, WeaponDataKind, WeaponKindCode,
 };
 use crate::processor::{
     values_until_first_zero, LanguageMap, LookupMap, PopulateStrings, Processor, ReadFile, Result,
-    WriteFile,
 };
-use crate::serde::ordered_map;
+use mhdb_model::weapons::hunting_horn::{
+    EchoBubble, EchoBubbleId, EchoBubbleKind, EchoWave, EchoWaveId, EchoWaveKind, HuntingHorn,
+    Melody, MelodyId, Note, Song, SongEffectId,
+};
+use mhdb_model::weapons::Weapon;
+use mhdb_model::Database;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::weapons::hunting_horn::{
+    BUBBLE_STRINGS, SONGS, SONG_STRINGS, TONES, WAVE_STRINGS,
+};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 use std::cell::OnceCell;
 use strum::{EnumCount, EnumIter, IntoEnumIterator};
@@ -20,31 +28,12 @@ use strum::{EnumCount, EnumIter, IntoEnumIterator};
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::HuntingHorn,
-        input_prefix: "Whistle",
-        output_prefix: Some("HuntingHorn"),
+        output: |db| &mut db.hunting_horns,
         callback: Some(Box::new(Process::default())),
         kind: WeaponKindCode::HuntingHorn,
     }
 }
 
-type EchoWaveId = u8;
-type EchoBubbleId = u8;
-type MelodyId = u8;
-type SongEffectId = u16;
-
-const TONES: &str = "user/weapons/Wp05MusicSkillToneTable.json";
-
-const SONGS: &str = "user/weapons/Wp05MusicSkillToneColorTable.json";
-const SONG_STRINGS: &str = "msg/MusicSkillDataText_Wp05.json";
-
-const WAVE_STRINGS: &str = "msg/HighFreqDataText_Wp05.json";
-const BUBBLE_STRINGS: &str = "msg/HibikiDataText_Wp05.json";
-
-const OUTPUT_WAVES: &str = "merged/weapons/HuntingHornEchoWaves.json";
-const OUTPUT_BUBBLES: &str = "merged/weapons/HuntingHornEchoBubbles.json";
-const OUTPUT_SONGS: &str = "merged/weapons/HuntingHornSongs.json";
-const OUTPUT_MELODIES: &str = "merged/weapons/HuntingHornMelodies.json";
-
 #[derive(Default)]
 struct Process {
     processed: bool,
@@ -54,18 +43,31 @@ impl SubProcess for Process {
     fn process(
         &mut self,
         config: &Config,
-        _weapon: &mut Weapon,
-        _weapon_data: WeaponData,
+        db: &mut Database,
+        diagnostics: &mut Diagnostics,
+        weapon: &mut Weapon,
+        weapon_data: WeaponData,
     ) -> Result {
+        if let WeaponDataKind::HuntingHorn(data) = &weapon_data.kind
+            && get_melody_sequential_id_from_uid(data.note_set_uid).is_none()
+        {
+            diagnostics
+                .error(
+                    definition().data_path(),
+                    format!("Unknown note set ID {}", data.note_set_uid),
+                )
+                .entity(weapon.game_id);
+        }
+
         if self.processed {
             return Ok(());
         }
 
         let strings = Msg::read_file(config.io.output.join(WAVE_STRINGS))?;
-        let mut waves: Vec<EchoWave> = Vec::with_capacity(EchoWaveKind::COUNT);
+        let mut waves: Vec<EchoWave> = Vec::with_capacity(EchoWaveKindCode::COUNT);
 
-        for wave in EchoWaveKind::iter() {
-            let Some(mut wave) = EchoWave::from_data(wave) else {
+        for wave in EchoWaveKindCode::iter() {
+            let Some(mut wave) = echo_wave_from_data(wave) else {
                 continue;
             };
 
@@ -76,10 +78,10 @@ impl SubProcess for Process {
         }
 
         let strings = Msg::read_file(config.io.output.join(BUBBLE_STRINGS))?;
-        let mut bubbles: Vec<EchoBubble> = Vec::with_capacity(EchoBubbleKind::COUNT);
+        let mut bubbles: Vec<EchoBubble> = Vec::with_capacity(EchoBubbleKindCode::COUNT);
 
-        for bubble in EchoBubbleKind::iter() {
-            let Some(mut bubble) = EchoBubble::from_data(bubble) else {
+        for bubble in EchoBubbleKindCode::iter() {
+            let Some(mut bubble) = echo_bubble_from_data(bubble) else {
                 continue;
             };
 
@@ -132,26 +134,19 @@ impl SubProcess for Process {
         self.processed = true;
 
         waves.sort_by_key(|v| v.game_id);
-        waves.write_file(config.io.output.join(OUTPUT_WAVES))?;
+        db.echo_waves = waves;
 
         bubbles.sort_by_key(|v| v.game_id);
-        bubbles.write_file(config.io.output.join(OUTPUT_BUBBLES))?;
+        db.echo_bubbles = bubbles;
 
         songs.sort_by_key(|v| v.effect_id);
-        songs.write_file(config.io.output.join(OUTPUT_SONGS))?;
+        db.songs = songs;
 
         melodies.sort_by_key(|v| v.game_id);
-        melodies.write_file(config.io.output.join(OUTPUT_MELODIES))
-    }
-}
+        db.melodies = melodies;
 
-#[derive(Debug, Serialize)]
-pub(super) struct HuntingHorn {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-    melody_id: MelodyId,
-    echo_wave_id: Option<EchoWaveId>,
-    echo_bubble_id: Option<EchoBubbleId>,
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -165,9 +160,9 @@ pub(super) struct HuntingHornData {
     #[serde(rename = "_Wp05UniqueType")]
     note_set_uid: isize,
     #[serde(rename = "_Wp05MusicSkillHighFreqType")]
-    echo_wave: EchoWaveKind,
+    echo_wave: EchoWaveKindCode,
     #[serde(rename = "_Wp05HibikiSkillType")]
-    echo_bubble: EchoBubbleKind,
+    echo_bubble: EchoBubbleKindCode,
 }
 
 is_weapon!(is_hunting_horn() => WeaponKindCode::HuntingHorn);
@@ -175,19 +170,20 @@ is_weapon!(is_hunting_horn() => WeaponKindCode::HuntingHorn);
 impl From<&HuntingHornData> for HuntingHorn {
     fn from(value: &HuntingHornData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
             echo_wave_id: value.echo_wave.as_sequential_id(),
             echo_bubble_id: value.echo_bubble.as_sequential_id(),
-            melody_id: get_melody_sequential_id_from_uid(value.note_set_uid),
+            // Unknown note sets are reported by the hunting horn sub-process.
+            melody_id: get_melody_sequential_id_from_uid(value.note_set_uid).unwrap_or_default(),
         }
     }
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone, Hash, Eq, PartialEq)]
-#[serde(rename_all(serialize = "lowercase"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone, Default)]
 #[repr(u8)]
-enum Note {
+enum NoteCode {
+    #[default]
     None = 0,
     Purple = 1,
     Red = 2,
@@ -199,28 +195,25 @@ enum Note {
     White = 8,
 }
 
-impl Note {
-    fn is_present(&self) -> bool {
-        !matches!(self, Self::None)
-    }
-}
-
-impl Default for Note {
-    fn default() -> Self {
-        Self::None
+impl From<NoteCode> for Note {
+    fn from(value: NoteCode) -> Self {
+        match value {
+            NoteCode::None => Self::None,
+            NoteCode::Purple => Self::Purple,
+            NoteCode::Red => Self::Red,
+            NoteCode::Orange => Self::Orange,
+            NoteCode::Yellow => Self::Yellow,
+            NoteCode::Green => Self::Green,
+            NoteCode::Blue => Self::Blue,
+            NoteCode::Aqua => Self::Aqua,
+            NoteCode::White => Self::White,
+        }
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Melody {
-    game_id: MelodyId,
-    notes: [Note; 3],
-    songs: Vec<u16>,
-}
-
-impl Melody {
-    fn can_play(&self, song: &Song) -> bool {
-        song.notes.iter().all(|note| self.notes.contains(note))
+impl NoteCode {
+    fn is_present(&self) -> bool {
+        !matches!(self, Self::None)
     }
 }
 
@@ -228,25 +221,17 @@ impl From<&NoteSet> for Melody {
     fn from(value: &NoteSet) -> Self {
         Self {
             game_id: value.id,
-            notes: [value.note1, value.note2, value.note3],
+            notes: [value.note1.into(), value.note2.into(), value.note3.into()],
             songs: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Song {
-    effect_id: SongEffectId,
-    notes: Vec<Note>,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-}
-
 impl From<&SongData> for Song {
     fn from(value: &SongData) -> Self {
         Self {
             effect_id: value.song_id,
-            notes: value.notes().to_owned(),
+            notes: value.notes().iter().copied().map(Note::from).collect(),
             names: LanguageMap::new(),
         }
     }
@@ -257,20 +242,20 @@ struct SongData {
     #[serde(rename = "_MusicSkill")]
     song_id: SongEffectId,
     #[serde(rename = "_ToneColor1")]
-    note1: Note,
+    note1: NoteCode,
     #[serde(rename = "_ToneColor2")]
-    note2: Note,
+    note2: NoteCode,
     #[serde(rename = "_ToneColor3")]
-    note3: Note,
+    note3: NoteCode,
     #[serde(rename = "_ToneColor4")]
-    note4: Note,
+    note4: NoteCode,
 
     #[serde(skip)]
-    _notes: OnceCell<Vec<Note>>,
+    _notes: OnceCell<Vec<NoteCode>>,
 }
 
 impl SongData {
-    fn notes(&self) -> &Vec<Note> {
+    fn notes(&self) -> &Vec<NoteCode> {
         self._notes.get_or_init(|| {
             let mut notes = vec![self.note1, self.note2];
 
@@ -292,15 +277,15 @@ struct NoteSet {
     #[serde(rename = "_UniqueType")]
     id: MelodyId,
     #[serde(rename = "_ToneColor1")]
-    note1: Note,
+    note1: NoteCode,
     #[serde(rename = "_ToneColor2")]
-    note2: Note,
+    note2: NoteCode,
     #[serde(rename = "_ToneColor3")]
-    note3: Note,
+    note3: NoteCode,
 }
 
-fn get_melody_sequential_id_from_uid(uid: isize) -> MelodyId {
-    match uid {
+fn get_melody_sequential_id_from_uid(uid: isize) -> Option<MelodyId> {
+    let id = match uid {
         -1373429760 => 0,
         1244670208 => 1,
         1440765696 => 2,
@@ -332,32 +317,23 @@ fn get_melody_sequential_id_from_uid(uid: isize) -> MelodyId {
         -526658144 => 28,
         -616761024 => 29,
         1705688832 => 30,
-        _ => panic!("Unknown note set ID {uid}"),
-    }
-}
+        _ => return None,
+    };
 
-#[derive(Debug, Serialize)]
-struct EchoWave {
-    game_id: EchoWaveId,
-    kind: EchoWaveKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
+    Some(id)
 }
 
-impl EchoWave {
-    fn from_data(kind: EchoWaveKind) -> Option<Self> {
-        Some(Self {
-            game_id: kind.as_sequential_id()?,
-            names: LanguageMap::new(),
-            kind,
-        })
-    }
+fn echo_wave_from_data(kind: EchoWaveKindCode) -> Option<EchoWave> {
+    Some(EchoWave {
+        game_id: kind.as_sequential_id()?,
+        names: LanguageMap::new(),
+        kind: kind.into(),
+    })
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone, EnumIter, EnumCount)]
-#[serde(rename_all(serialize = "lowercase"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone, EnumIter, EnumCount)]
 #[repr(isize)]
-enum EchoWaveKind {
+enum EchoWaveKindCode {
     None = -903091968,
     Blunt = 60540128,
     Slash = -1868362112,
@@ -372,7 +348,26 @@ enum EchoWaveKind {
     Blast = -1732136192,
 }
 
-impl EchoWaveKind {
+impl From<EchoWaveKindCode> for EchoWaveKind {
+    fn from(value: EchoWaveKindCode) -> Self {
+        match value {
+            EchoWaveKindCode::None => Self::None,
+            EchoWaveKindCode::Blunt => Self::Blunt,
+            EchoWaveKindCode::Slash => Self::Slash,
+            EchoWaveKindCode::Fire => Self::Fire,
+            EchoWaveKindCode::Water => Self::Water,
+            EchoWaveKindCode::Thunder => Self::Thunder,
+            EchoWaveKindCode::Ice => Self::Ice,
+            EchoWaveKindCode::Dragon => Self::Dragon,
+            EchoWaveKindCode::Poison => Self::Poison,
+            EchoWaveKindCode::Paralyze => Self::Paralyze,
+            EchoWaveKindCode::Sleep => Self::Sleep,
+            EchoWaveKindCode::Blast => Self::Blast,
+        }
+    }
+}
+
+impl EchoWaveKindCode {
     fn as_sequential_id(&self) -> Option<EchoWaveId> {
         let id = match self {
             Self::None => return None,
@@ -393,28 +388,17 @@ impl EchoWaveKind {
     }
 }
 
-#[derive(Debug, Serialize)]
-struct EchoBubble {
-    game_id: EchoBubbleId,
-    kind: EchoBubbleKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-}
-
-impl EchoBubble {
-    fn from_data(kind: EchoBubbleKind) -> Option<Self> {
-        Some(Self {
-            game_id: kind.as_sequential_id()?,
-            names: LanguageMap::new(),
-            kind,
-        })
-    }
+fn echo_bubble_from_data(kind: EchoBubbleKindCode) -> Option<EchoBubble> {
+    Some(EchoBubble {
+        game_id: kind.as_sequential_id()?,
+        names: LanguageMap::new(),
+        kind: kind.into(),
+    })
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, EnumIter, EnumCount)]
-#[serde(rename_all(serialize = "lowercase"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone, EnumIter, EnumCount)]
 #[repr(isize)]
-enum EchoBubbleKind {
+enum EchoBubbleKindCode {
     None = -1286112512,
     Evasion = 2134793984,
     Regen = -555195648,
@@ -424,7 +408,21 @@ enum EchoBubbleKind {
     Immunity = 650049344,
 }
 
-impl EchoBubbleKind {
+impl From<EchoBubbleKindCode> for EchoBubbleKind {
+    fn from(value: EchoBubbleKindCode) -> Self {
+        match value {
+            EchoBubbleKindCode::None => Self::None,
+            EchoBubbleKindCode::Evasion => Self::Evasion,
+            EchoBubbleKindCode::Regen => Self::Regen,
+            EchoBubbleKindCode::Stamina => Self::Stamina,
+            EchoBubbleKindCode::Damage => Self::Damage,
+            EchoBubbleKindCode::Defense => Self::Defense,
+            EchoBubbleKindCode::Immunity => Self::Immunity,
+        }
+    }
+}
+
+impl EchoBubbleKindCode {
     fn as_sequential_id(&self) -> Option<EchoBubbleId> {
         let id = match self {
             Self::None => return None,
@@ -1,28 +1,21 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize, Serializer};
+use mhdb_model::weapons::insect_glaive::{InsectGlaive, KinsectEssenceKind};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::InsectGlaive,
-        input_prefix: "Rod",
-        output_prefix: Some("InsectGlaive"),
+        output: |db| &mut db.insect_glaives,
         callback: None,
         kind: WeaponKindCode::InsectGlaive,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct InsectGlaive {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-    kinsect_level: u8,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct InsectGlaiveData {
     #[serde(rename = "_Type", deserialize_with = "is_insect_glaive")]
@@ -40,7 +33,7 @@ is_weapon!(is_insect_glaive() => WeaponKindCode::InsectGlaive);
 impl From<&InsectGlaiveData> for InsectGlaive {
     fn from(value: &InsectGlaiveData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
             kinsect_level: value.kinsect_level.to_level_number(),
         }
@@ -79,21 +72,24 @@ impl KinsectLevel {
     }
 }
 
-#[derive(Debug, Deserialize_repr, Serialize)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(u8)]
-pub enum KinsectEssenceKind {
+pub enum KinsectEssenceKindCode {
     Red = 1,
     Orange = 2,
     White = 3,
     Green = 4,
-    #[serde(serialize_with = "serialize_as_null", untagged)]
     None = 0,
 }
 
-fn serialize_as_null<S>(serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: Serializer,
-{
-    serializer.serialize_none()
+impl From<KinsectEssenceKindCode> for KinsectEssenceKind {
+    fn from(value: KinsectEssenceKindCode) -> Self {
+        match value {
+            KinsectEssenceKindCode::Red => Self::Red,
+            KinsectEssenceKindCode::Orange => Self::Orange,
+            KinsectEssenceKindCode::White => Self::White,
+            KinsectEssenceKindCode::Green => Self::Green,
+            KinsectEssenceKindCode::None => Self::None,
+        }
+    }
 }
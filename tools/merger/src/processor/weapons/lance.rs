@@ -1,26 +1,20 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::lance::Lance;
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::Lance,
-        input_prefix: "Lance",
-        output_prefix: None,
+        output: |db| &mut db.lances,
         callback: None,
         kind: WeaponKindCode::Lance,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct Lance {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct LanceData {
     #[serde(rename = "_Type", deserialize_with = "is_lance")]
@@ -36,7 +30,7 @@ is_weapon!(is_lance() => WeaponKindCode::Lance);
 impl From<&LanceData> for Lance {
     fn from(value: &LanceData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
@@ -1,32 +1,28 @@
 use crate::is_weapon;
-use crate::processor::weapons::heavy_bowgun::{self, AmmoCapacityData, AmmoKind, AmmoLevelData};
+use crate::processor::weapons::heavy_bowgun::{
+    ammo_kind_from_index, AmmoCapacityData, AmmoLevelData,
+};
 use crate::processor::weapons::{ProcessorDefinition, WeaponKindCode};
 use crate::processor::Processor;
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::light_bowgun::{Ammo, LightBowgun, SpecialAmmo};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::LightBowgun,
-        input_prefix: "LightBowgun",
-        output_prefix: None,
+        output: |db| &mut db.light_bowguns,
         callback: None,
         kind: WeaponKindCode::LightBowgun,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct LightBowgun {
-    ammo: Vec<Ammo>,
-    special_ammo: SpecialAmmo,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct LightBowgunData {
     #[serde(rename = "_Type", deserialize_with = "is_light_bowgun")]
     _type: WeaponKindCode,
     #[serde(rename = "_Wp13SpecialAmmo")]
-    special_ammo: SpecialAmmo,
+    special_ammo: SpecialAmmoCode,
     #[serde(rename = "_ShellLv")]
     ammo_levels: AmmoLevelData,
     #[serde(rename = "_ShellNum")]
@@ -40,69 +36,56 @@ is_weapon!(is_light_bowgun() => WeaponKindCode::LightBowgun);
 impl From<&LightBowgunData> for LightBowgun {
     fn from(value: &LightBowgunData) -> Self {
         Self {
-            ammo: Ammo::from_data(value.ammo_levels, value.ammo_capacities, value.ammo_rapid),
-            special_ammo: value.special_ammo,
+            ammo: ammo_from_data(value.ammo_levels, value.ammo_capacities, value.ammo_rapid),
+            special_ammo: value.special_ammo.into(),
         }
     }
 }
 
-#[derive(Debug, Deserialize_repr, Serialize, Copy, Clone)]
-#[serde(rename_all(serialize = "lowercase"))]
+#[derive(Debug, Deserialize_repr, Copy, Clone)]
 #[repr(isize)]
-enum SpecialAmmo {
+enum SpecialAmmoCode {
     Wyvernblast = 1685175680,
     Adhesive = -1626714112,
 }
 
-#[derive(Debug, Serialize)]
-struct Ammo {
-    kind: AmmoKind,
-    level: u8,
-    capacity: u8,
-    rapid: bool,
+impl From<SpecialAmmoCode> for SpecialAmmo {
+    fn from(value: SpecialAmmoCode) -> Self {
+        match value {
+            SpecialAmmoCode::Wyvernblast => Self::Wyvernblast,
+            SpecialAmmoCode::Adhesive => Self::Adhesive,
+        }
+    }
 }
 
 type AmmoRapidData = [bool; 20];
 
-impl Ammo {
-    fn from_data(
-        levels: AmmoLevelData,
-        capacities: AmmoCapacityData,
-        rapid: AmmoRapidData,
-    ) -> Vec<Self> {
-        let mut ammo: Vec<_> = levels
-            .iter()
-            .zip(capacities)
-            .zip(rapid)
-            .enumerate()
-            .filter_map(|(index, ((level, capacity), rapid))| {
-                let level = level.as_level_number();
+fn ammo_from_data(
+    levels: AmmoLevelData,
+    capacities: AmmoCapacityData,
+    rapid: AmmoRapidData,
+) -> Vec<Ammo> {
+    let mut ammo: Vec<_> = levels
+        .iter()
+        .zip(capacities)
+        .zip(rapid)
+        .enumerate()
+        .filter_map(|(index, ((level, capacity), rapid))| {
+            let level = level.as_level_number();
 
-                if level == 0 {
-                    return None;
-                }
+            if level == 0 {
+                return None;
+            }
 
-                Some(Ammo {
-                    kind: AmmoKind::from_index(index),
-                    level,
-                    capacity,
-                    rapid,
-                })
+            Some(Ammo {
+                kind: ammo_kind_from_index(index),
+                level,
+                capacity,
+                rapid,
             })
-            .collect();
-
-        ammo.sort_by_key(|v| v.kind);
-        ammo
-    }
-}
+        })
+        .collect();
 
-impl From<heavy_bowgun::Ammo> for Ammo {
-    fn from(value: heavy_bowgun::Ammo) -> Self {
-        Self {
-            kind: value.kind,
-            level: value.level,
-            capacity: value.capacity,
-            rapid: false,
-        }
-    }
+    ammo.sort_by_key(|v| v.kind);
+    ammo
 }
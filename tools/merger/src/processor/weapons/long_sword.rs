@@ -1,26 +1,20 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::long_sword::LongSword;
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::LongSword,
-        input_prefix: "Tachi",
-        output_prefix: Some("LongSword"),
+        output: |db| &mut db.long_swords,
         callback: None,
         kind: WeaponKindCode::LongSword,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct LongSword {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct LongSwordData {
     #[serde(rename = "_Type", deserialize_with = "is_long_sword")]
@@ -36,7 +30,7 @@ is_weapon!(is_long_sword() => WeaponKindCode::LongSword);
 impl From<&LongSwordData> for LongSword {
     fn from(value: &LongSwordData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
@@ -1,14 +1,19 @@
+use crate::diagnostics::Diagnostics;
+use crate::placeholders::index::ReferenceIndex;
 use crate::placeholders::{ApplyContext, Placeholder};
-use crate::processor::weapons::series::SeriesId;
 use crate::processor::{
-    create_id_map, to_ingame_rarity, values_until_first_zero, IdMap, LanguageMap, Lookup, LookupMap, PopulateStrings, Processor,
-    ReadFile, Result, WriteFile,
+    create_id_map, to_ingame_rarity, values_until_first_zero, LanguageMap, Lookup, LookupMap,
+    PopulateStrings, Processor, ReadFile, Result,
 };
-use crate::serde::ordered_map;
 use crate::should_run;
+use mhdb_model::weapons::{
+    Crafting, Element, Sharpness, Special, SpecialKind, Status, Weapon, WeaponKind,
+};
+use mhdb_model::{Database, RichTextMap, VariantMap};
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{de, Deserialize, Deserializer, Serialize};
+use rslib::processors::inputs::weapons;
+use serde::{Deserialize, Deserializer, de};
 use serde_repr::Deserialize_repr;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -29,38 +34,63 @@ mod series;
 mod switch_axe;
 mod sword_shield;
 
-pub fn process(config: &Config, filters: &[Processor]) -> Result {
-    series::process(config, filters)?;
-
-    do_process(config, filters, bow::definition())?;
-    do_process(config, filters, charge_blade::definition())?;
-    do_process(config, filters, gunlance::definition())?;
-    do_process(config, filters, hammer::definition())?;
-    do_process(config, filters, heavy_bowgun::definition())?;
-    do_process(config, filters, lance::definition())?;
-    do_process(config, filters, light_bowgun::definition())?;
-    do_process(config, filters, great_sword::definition())?;
-    do_process(config, filters, insect_glaive::definition())?;
-    do_process(config, filters, sword_shield::definition())?;
-    do_process(config, filters, switch_axe::definition())?;
-    do_process(config, filters, long_sword::definition())?;
-    do_process(config, filters, dual_blades::definition())?;
-    do_process(config, filters, hunting_horn::definition())?;
+pub fn process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+) -> Result {
+    series::process(config, filters, db)?;
+
+    for def in definitions() {
+        do_process(config, filters, db, references, diagnostics, def)?;
+    }
 
     Ok(())
 }
 
-fn do_process(config: &Config, filters: &[Processor], mut def: ProcessorDefinition) -> Result {
+fn definitions() -> [ProcessorDefinition; 14] {
+    [
+        bow::definition(),
+        charge_blade::definition(),
+        gunlance::definition(),
+        hammer::definition(),
+        heavy_bowgun::definition(),
+        lance::definition(),
+        light_bowgun::definition(),
+        great_sword::definition(),
+        insect_glaive::definition(),
+        sword_shield::definition(),
+        switch_axe::definition(),
+        long_sword::definition(),
+        dual_blades::definition(),
+        hunting_horn::definition(),
+    ]
+}
+
+fn do_process(
+    config: &Config,
+    filters: &[Processor],
+    db: &mut Database,
+    references: &ReferenceIndex,
+    diagnostics: &mut Diagnostics,
+    mut def: ProcessorDefinition,
+) -> Result {
     should_run!(filters, def.processor);
 
     let data: Vec<WeaponData> = Vec::read_file(config.io.output.join(def.data_path()))?;
     let strings = Msg::read_file(config.io.output.join(def.strings_path()))?;
+    let rich_text = config.merger.rich_text;
+    let placeholder_context = ApplyContext::empty()
+        .with_references(references)
+        .with_listener_options(&config.merger.listener_options);
 
     let mut merged: Vec<Weapon> = Vec::new();
     let mut lookup: LookupMap<u32> = LookupMap::new();
 
     for data in data {
-        let mut weapon = Weapon::from_data(def.kind, &data);
+        let mut weapon = weapon_from_data(def.kind, &data);
 
         strings.populate(&data.name_guid, &mut weapon.names);
 
@@ -69,7 +99,13 @@ fn do_process(config: &Config, filters: &[Processor], mut def: ProcessorDefiniti
         }
 
         strings.populate(&data.description_guid, &mut weapon.descriptions);
-        Placeholder::process(&mut weapon.descriptions, &ApplyContext::empty());
+        let errors = Placeholder::process(
+            &mut weapon.descriptions,
+            rich_text.then_some(&mut weapon.rich_descriptions),
+            &mut weapon.descriptions_variants,
+            &placeholder_context,
+        );
+        diagnostics.warn_placeholders(def.strings_path(), weapon.game_id, errors);
 
         if data.attribute.is_present() {
             weapon.specials.push(Special {
@@ -90,7 +126,7 @@ fn do_process(config: &Config, filters: &[Processor], mut def: ProcessorDefiniti
         weapon.crafting.zenny_cost = data.price;
 
         if let Some(callback) = def.callback.as_mut() {
-            callback.process(config, &mut weapon, data)?;
+            callback.process(config, db, diagnostics, &mut weapon, data)?;
         }
 
         lookup.insert(weapon.game_id, merged.len());
@@ -100,7 +136,14 @@ fn do_process(config: &Config, filters: &[Processor], mut def: ProcessorDefiniti
     let data: Vec<RecipeData> = Vec::read_file(config.io.output.join(def.recipe_path()))?;
 
     for data in data {
-        let weapon = lookup.find_or_panic_mut(data.weapon_id.value(def.kind), &mut merged);
+        let Some(weapon) = lookup.find_or_report_mut(
+            data.weapon_id.value(def.kind),
+            &mut merged,
+            diagnostics,
+            def.recipe_path(),
+        ) else {
+            continue;
+        };
 
         weapon.crafting.inputs = create_id_map(&data.item_ids, &data.item_amounts);
         weapon.crafting.is_shortcut = data.is_shortcut;
@@ -112,35 +155,53 @@ fn do_process(config: &Config, filters: &[Processor], mut def: ProcessorDefiniti
         .map(|v| (v.guid.as_ref(), v.weapon_id))
         .collect();
 
-    let row_lookup = series::get_id_map(config, &def.series_path())?;
+    let row_lookup = series::get_id_map(config, &def.series_path(), diagnostics)?;
+    let tree_path = def.tree_path();
 
     for data in &data {
-        let weapon = lookup.find_or_panic_mut(data.weapon_id, &mut merged);
+        let Some(weapon) =
+            lookup.find_or_report_mut(data.weapon_id, &mut merged, diagnostics, &tree_path)
+        else {
+            continue;
+        };
 
         weapon.crafting.column = data.column;
         weapon.crafting.row = data.row;
 
-        let Some(series_id) = row_lookup.get(&weapon.crafting.row) else {
-            panic!(
-                "Weapon series must exist; something is very wrong (for weapon {})",
-                weapon.game_id
-            );
-        };
-
-        // Series ID of zero indicates that the weapon does not belong to a series.
-        if *series_id != 0 {
-            weapon.series_id = Some(*series_id);
+        match row_lookup.get(&weapon.crafting.row) {
+            // Series ID of zero indicates that the weapon does not belong to a series.
+            Some(0) => (),
+            Some(series_id) => weapon.series_id = Some(*series_id),
+            None => {
+                diagnostics
+                    .error(
+                        def.series_path(),
+                        format!("Could not find series for row {}", data.row),
+                    )
+                    .entity(weapon.game_id);
+            }
         }
 
-        if !data.previous_guid.is_empty() {
-            // The unwrap() here ensures we don't accidentally assign None if the GUID couldn't be
-            // found.
-            let previous_id = tree_guids.get(&data.previous_guid[0].as_ref()).unwrap();
-            weapon.crafting.previous_id = Some(*previous_id);
+        if let Some(guid) = data.previous_guid.first() {
+            match tree_guids.get(guid.as_str()) {
+                Some(previous_id) => weapon.crafting.previous_id = Some(*previous_id),
+                None => {
+                    diagnostics
+                        .error(&tree_path, format!("Could not find previous weapon {guid}"))
+                        .entity(weapon.game_id);
+                }
+            }
         }
 
         for guid in &data.branch_guids {
-            let branch_id = tree_guids.get(guid.as_str()).unwrap();
+            let Some(branch_id) = tree_guids.get(guid.as_str()) else {
+                diagnostics
+                    .error(&tree_path, format!("Could not find branch weapon {guid}"))
+                    .entity(weapon.game_id);
+
+                continue;
+            };
+
             weapon.crafting.branches.push(*branch_id);
         }
 
@@ -148,111 +209,76 @@ fn do_process(config: &Config, filters: &[Processor], mut def: ProcessorDefiniti
     }
 
     merged.sort_by_key(|v| v.game_id);
-    merged.write_file(config.io.output.join(def.output_path()))
+    *(def.output)(db) = merged;
+
+    Ok(())
 }
 
 trait SubProcess {
-    fn process(&mut self, config: &Config, weapon: &mut Weapon, weapon_data: WeaponData) -> Result;
+    fn process(
+        &mut self,
+        config: &Config,
+        db: &mut Database,
+        diagnostics: &mut Diagnostics,
+        weapon: &mut Weapon,
+        weapon_data: WeaponData,
+    ) -> Result;
 }
 
 struct ProcessorDefinition {
     processor: Processor,
-    input_prefix: &'static str,
-    output_prefix: Option<&'static str>,
+    /// Selects the section of the [`Database`] that merged weapons are stored in.
+    output: fn(&mut Database) -> &mut Vec<Weapon>,
     callback: Option<Box<dyn SubProcess>>,
     kind: WeaponKindCode,
 }
 
 impl ProcessorDefinition {
+    fn input_prefix(&self) -> &'static str {
+        weapons::prefix(self.processor).expect("weapon processors always have an input prefix")
+    }
+
     fn data_path(&self) -> PathBuf {
-        PathBuf::from(format!("user/weapons/{}.json", self.input_prefix))
+        weapons::data_path(self.input_prefix()).into()
     }
 
     fn recipe_path(&self) -> PathBuf {
-        PathBuf::from(format!("user/weapons/{}Recipe.json", self.input_prefix))
+        weapons::recipe_path(self.input_prefix()).into()
     }
 
     fn tree_path(&self) -> PathBuf {
-        PathBuf::from(format!("user/weapons/{}Tree_2.json", self.input_prefix))
+        weapons::tree_path(self.input_prefix()).into()
     }
 
     fn series_path(&self) -> PathBuf {
-        PathBuf::from(format!("user/weapons/{}Tree_4.json", self.input_prefix))
+        weapons::series_path(self.input_prefix()).into()
     }
 
     fn strings_path(&self) -> PathBuf {
-        PathBuf::from(format!("msg/{}.json", self.input_prefix))
-    }
-
-    fn output_path(&self) -> PathBuf {
-        PathBuf::from(format!(
-            "merged/weapons/{}.json",
-            self.output_prefix.unwrap_or(self.input_prefix)
-        ))
+        weapons::strings_path(self.input_prefix()).into()
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Weapon {
-    game_id: u32,
-    #[serde(flatten)]
-    kind: WeaponKind,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-    #[serde(serialize_with = "ordered_map")]
-    descriptions: LanguageMap,
-    rarity: u8,
-    attack_raw: u8,
-    affinity: i8,
-    defense: u8,
-    slots: Vec<u8>,
-    specials: Vec<Special>,
-    crafting: Crafting,
-    #[serde(serialize_with = "ordered_map")]
-    skills: IdMap,
-    series_id: Option<SeriesId>,
-}
-
-impl Weapon {
-    fn from_data(kind: WeaponKindCode, value: &WeaponData) -> Self {
-        Self {
-            game_id: value.id.value(kind),
-            kind: WeaponKind::from(&value.kind),
-            names: LanguageMap::new(),
-            descriptions: LanguageMap::new(),
-            rarity: to_ingame_rarity(value.rarity),
-            attack_raw: value.attack_raw,
-            affinity: value.affinity,
-            defense: value.defense,
-            slots: values_until_first_zero(&value.slots),
-            specials: Vec::new(),
-            crafting: Crafting::default(),
-            skills: create_id_map(&value.skill_ids, &value.skill_levels),
-            series_id: None,
-        }
+fn weapon_from_data(kind: WeaponKindCode, value: &WeaponData) -> Weapon {
+    Weapon {
+        game_id: value.id.value(kind),
+        kind: WeaponKind::from(&value.kind),
+        names: LanguageMap::new(),
+        descriptions: LanguageMap::new(),
+        rich_descriptions: RichTextMap::new(),
+        descriptions_variants: VariantMap::new(),
+        rarity: to_ingame_rarity(value.rarity),
+        attack_raw: value.attack_raw,
+        affinity: value.affinity,
+        defense: value.defense,
+        slots: values_until_first_zero(&value.slots),
+        specials: Vec::new(),
+        crafting: Crafting::default(),
+        skills: create_id_map(&value.skill_ids, &value.skill_levels),
+        series_id: None,
     }
 }
 
-#[derive(Debug, Serialize, derive_more::Unwrap)]
-#[unwrap(ref_mut)]
-#[serde(tag = "kind", rename_all = "kebab-case")]
-enum WeaponKind {
-    Bow(bow::Bow),
-    ChargeBlade(charge_blade::ChargeBlade),
-    Gunlance(gunlance::Gunlance),
-    Hammer(hammer::Hammer),
-    HeavyBowgun(heavy_bowgun::HeavyBowgun),
-    Lance(lance::Lance),
-    LightBowgun(light_bowgun::LightBowgun),
-    GreatSword(great_sword::GreatSword),
-    InsectGlaive(insect_glaive::InsectGlaive),
-    SwordShield(sword_shield::SwordShield),
-    SwitchAxe(switch_axe::SwitchAxe),
-    LongSword(long_sword::LongSword),
-    DualBlades(dual_blades::DualBlades),
-    HuntingHorn(hunting_horn::HuntingHorn),
-}
-
 #[derive(Debug, Deserialize, derive_more::Unwrap)]
 #[unwrap(ref)]
 #[serde(untagged)]
@@ -410,21 +436,6 @@ impl AttributeData {
     }
 }
 
-#[derive(Debug, Serialize)]
-struct Special {
-    #[serde(flatten)]
-    kind: SpecialKind,
-    raw: u8,
-    hidden: bool,
-}
-
-#[derive(Debug, Serialize, Copy, Clone, PartialEq, Eq)]
-#[serde(tag = "kind", rename_all = "lowercase")]
-pub enum SpecialKind {
-    Element(Element),
-    Status(Status),
-}
-
 impl From<AttributeData> for SpecialKind {
     fn from(value: AttributeData) -> Self {
         use AttributeData::*;
@@ -446,37 +457,6 @@ impl From<AttributeData> for SpecialKind {
     }
 }
 
-#[derive(Debug, Serialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-#[serde(tag = "element", rename_all = "lowercase")]
-pub enum Element {
-    Fire,
-    Water,
-    Thunder,
-    Ice,
-    Dragon,
-}
-
-#[derive(Debug, Serialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
-#[serde(tag = "status", rename_all = "lowercase")]
-pub enum Status {
-    Paralysis,
-    Poison,
-    Sleep,
-    Blastblight,
-}
-
-#[derive(Debug, Serialize, Default)]
-struct Crafting {
-    zenny_cost: u16,
-    #[serde(serialize_with = "ordered_map")]
-    inputs: IdMap,
-    previous_id: Option<u32>,
-    branches: Vec<u32>,
-    is_shortcut: bool,
-    column: u8,
-    row: u8,
-}
-
 #[derive(Debug, Deserialize)]
 struct RecipeData {
     #[serde(flatten)]
@@ -507,28 +487,15 @@ struct CraftingTreeData {
 
 type SharpnessData = [u16; 7];
 
-#[derive(Debug, Serialize)]
-struct Sharpness {
-    red: u16,
-    orange: u16,
-    yellow: u16,
-    green: u16,
-    blue: u16,
-    white: u16,
-    purple: u16,
-}
-
-impl Sharpness {
-    fn from_data(data: SharpnessData) -> Self {
-        Self {
-            red: data[0],
-            orange: data[1],
-            yellow: data[2],
-            green: data[3],
-            blue: data[4],
-            white: data[5],
-            purple: data[6],
-        }
+fn sharpness_from_data(data: SharpnessData) -> Sharpness {
+    Sharpness {
+        red: data[0],
+        orange: data[1],
+        yellow: data[2],
+        green: data[3],
+        blue: data[4],
+        white: data[5],
+        purple: data[6],
     }
 }
 
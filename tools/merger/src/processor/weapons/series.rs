@@ -1,21 +1,16 @@
-use crate::processor::{LanguageMap, PopulateStrings, Processor, ReadFile, Result, WriteFile};
-use crate::serde::ordered_map;
+use crate::diagnostics::Diagnostics;
+use crate::processor::{LanguageMap, PopulateStrings, Processor, ReadFile, Result};
 use crate::should_run;
+use mhdb_model::weapons::series::{Series, SeriesId};
+use mhdb_model::Database;
 use rslib::config::Config;
 use rslib::formats::msg::Msg;
-use serde::{Deserialize, Serialize};
+use rslib::processors::inputs::weapons::series::{DATA, ID_DATA, STRINGS};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
 
-const DATA: &str = "user/weapons/WeaponSeriesData.json";
-const ID_DATA: &str = "user/weapons/WeaponSeries.json";
-const STRINGS: &str = "msg/WeaponSeries.json";
-
-const OUTPUT: &str = "merged/WeaponSeries.json";
-
-pub(super) type SeriesId = isize;
-
-pub(super) fn process(config: &Config, filters: &[Processor]) -> Result<()> {
+pub(super) fn process(config: &Config, filters: &[Processor], db: &mut Database) -> Result<()> {
     should_run!(filters, Processor::WeaponSeries);
 
     let data: Vec<SeriesData> = Vec::read_file(config.io.output.join(DATA))?;
@@ -35,12 +30,18 @@ pub(super) fn process(config: &Config, filters: &[Processor]) -> Result<()> {
     }
 
     series.sort_by_key(|v| v.game_id);
-    series.write_file(config.io.output.join(OUTPUT))?;
+    db.weapon_series = series;
 
     Ok(())
 }
 
-pub(super) fn get_id_map(config: &Config, series_path: &Path) -> Result<HashMap<u8, SeriesId>> {
+/// Maps crafting tree rows to the ID of the series displayed in that row. Rows whose series can't be
+/// found are left out of the map.
+pub(super) fn get_id_map(
+    config: &Config,
+    series_path: &Path,
+    diagnostics: &mut Diagnostics,
+) -> Result<HashMap<u8, SeriesId>> {
     let path = config.io.output.join(ID_DATA);
     let id_lookup: Vec<SeriesIdData> = Vec::read_file(path)?;
     let id_lookup: HashMap<u16, SeriesId> =
@@ -51,23 +52,23 @@ pub(super) fn get_id_map(config: &Config, series_path: &Path) -> Result<HashMap<
 
     Ok(row_lookup
         .into_iter()
-        .map(|v| {
+        .filter_map(|v| {
             let Some(series_id) = id_lookup.get(&v.simple_id) else {
-                panic!("Could not find series ID from index {}", v.simple_id);
+                diagnostics
+                    .error(
+                        ID_DATA,
+                        format!("Could not find series ID from index {}", v.simple_id),
+                    )
+                    .entity(format!("row {}", v.row));
+
+                return None;
             };
 
-            (v.row, *series_id)
+            Some((v.row, *series_id))
         })
         .collect())
 }
 
-#[derive(Debug, Serialize)]
-struct Series {
-    game_id: SeriesId,
-    #[serde(serialize_with = "ordered_map")]
-    names: LanguageMap,
-}
-
 impl From<&SeriesData> for Series {
     fn from(value: &SeriesData) -> Self {
         Self {
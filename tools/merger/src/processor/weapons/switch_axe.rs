@@ -1,28 +1,21 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::switch_axe::{Phial, PhialKind, SwitchAxe};
+use serde::Deserialize;
 use serde_repr::Deserialize_repr;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::SwitchAxe,
-        input_prefix: "SlashAxe",
-        output_prefix: Some("SwitchAxe"),
+        output: |db| &mut db.switch_axes,
         callback: None,
         kind: WeaponKindCode::SwitchAxe,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct SwitchAxe {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-    phial: Phial,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct SwitchAxeData {
     #[serde(rename = "_Type", deserialize_with = "is_switch_axe")]
@@ -42,9 +35,11 @@ is_weapon!(is_switch_axe() => WeaponKindCode::SwitchAxe);
 impl From<&SwitchAxeData> for SwitchAxe {
     fn from(value: &SwitchAxeData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
-            phial: Phial::from_data(value.phial, value.phial_raw),
+            phial: Phial {
+                kind: phial_kind_from_data(value.phial, value.phial_raw),
+            },
         }
     }
 }
@@ -60,42 +55,15 @@ enum PhialDataKind {
     Poison,
 }
 
-#[derive(Debug, Serialize)]
-struct Phial {
-    #[serde(flatten)]
-    kind: PhialKind,
-}
+fn phial_kind_from_data(kind: PhialDataKind, raw: u8) -> PhialKind {
+    use PhialDataKind::*;
 
-impl Phial {
-    fn from_data(kind: PhialDataKind, raw: u8) -> Self {
-        Self {
-            kind: PhialKind::from_data(kind, raw),
-        }
-    }
-}
-
-#[derive(Debug, Serialize)]
-#[serde(tag = "kind", content = "raw", rename_all = "lowercase")]
-enum PhialKind {
-    Power,
-    Element,
-    Dragon(u8),
-    Exhaust(u8),
-    Paralyze(u8),
-    Poison(u8),
-}
-
-impl PhialKind {
-    fn from_data(kind: PhialDataKind, raw: u8) -> Self {
-        use PhialDataKind::*;
-
-        match kind {
-            Power => Self::Power,
-            Element => Self::Element,
-            Dragon => Self::Dragon(raw),
-            Exhaust => Self::Exhaust(raw),
-            Paralyze => Self::Paralyze(raw),
-            Poison => Self::Poison(raw),
-        }
+    match kind {
+        Power => PhialKind::Power,
+        Element => PhialKind::Element,
+        Dragon => PhialKind::Dragon(raw),
+        Exhaust => PhialKind::Exhaust(raw),
+        Paralyze => PhialKind::Paralyze(raw),
+        Poison => PhialKind::Poison(raw),
     }
 }
@@ -1,26 +1,20 @@
 use crate::is_weapon;
 use crate::processor::weapons::{
-    HandicraftData, ProcessorDefinition, Sharpness, SharpnessData, WeaponKindCode,
+    sharpness_from_data, HandicraftData, ProcessorDefinition, SharpnessData, WeaponKindCode,
 };
 use crate::processor::{values_until_first_zero, Processor};
-use serde::{Deserialize, Serialize};
+use mhdb_model::weapons::sword_shield::SwordShield;
+use serde::Deserialize;
 
 pub(super) fn definition() -> ProcessorDefinition {
     ProcessorDefinition {
         processor: Processor::SwordShield,
-        input_prefix: "ShortSword",
-        output_prefix: Some("SwordShield"),
+        output: |db| &mut db.sword_shields,
         callback: None,
         kind: WeaponKindCode::SwordShield,
     }
 }
 
-#[derive(Debug, Serialize)]
-pub(super) struct SwordShield {
-    sharpness: Sharpness,
-    handicraft: Vec<u8>,
-}
-
 #[derive(Debug, Deserialize)]
 pub(super) struct SwordShieldData {
     #[serde(rename = "_Type", deserialize_with = "is_sword_shield")]
@@ -36,7 +30,7 @@ is_weapon!(is_sword_shield() => WeaponKindCode::SwordShield);
 impl From<&SwordShieldData> for SwordShield {
     fn from(value: &SwordShieldData) -> Self {
         Self {
-            sharpness: Sharpness::from_data(value.sharpness),
+            sharpness: sharpness_from_data(value.sharpness),
             handicraft: values_until_first_zero(&value.handicraft),
         }
     }
@@ -0,0 +1,1105 @@
+//! Exports a merged [`Database`] to a SQLite file.
+//!
+//! Rows are built directly from the model types, and every enum that carries data (foundry
+//! values, weapon kinds, phials, specials, weaknesses, and reward sources) is matched
+//! exhaustively, so a new variant in `mhdb_model` shows up here as a compile error instead of
+//! silently falling out of the export. Enum values are stored using the same names they serialize
+//! to in the JSON output; enums without data are named through serde, and monster parts through
+//! their `IntoStaticStr` derive.
+
+use mhdb_model::items::{Foundry, FoundryMaterial, FoundryOutput};
+use mhdb_model::monsters::large::effectives::{Effect, SpecialKind as EffectiveKind};
+use mhdb_model::monsters::large::rewards::RewardSource;
+use mhdb_model::monsters::large::LargeMonster;
+use mhdb_model::weapons::switch_axe::PhialKind;
+use mhdb_model::weapons::{Element, Sharpness, SpecialKind, Status, Weapon, WeaponKind};
+use mhdb_model::{Database, IdMap, LanguageMap};
+use rusqlite::{params, Connection, Params, Transaction};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// Writes `db` to a new SQLite database at `path`, replacing the file if it already exists.
+pub fn export<P: AsRef<Path>>(db: &Database, path: P) -> anyhow::Result<()> {
+    let path = path.as_ref();
+
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+
+    let mut conn = Connection::open(path)?;
+    let tx = conn.transaction()?;
+    tx.execute_batch(SCHEMA)?;
+
+    let exporter = Exporter { tx: &tx };
+    exporter.items(db)?;
+    exporter.skills(db)?;
+    exporter.accessories(db)?;
+    exporter.charms(db)?;
+    exporter.amulets(db)?;
+    exporter.armor(db)?;
+    exporter.weapons(db)?;
+    exporter.hunting_horn_data(db)?;
+    exporter.monsters(db)?;
+    exporter.stages(db)?;
+
+    tx.commit()?;
+
+    Ok(())
+}
+
+struct Exporter<'a> {
+    tx: &'a Transaction<'a>,
+}
+
+impl Exporter<'_> {
+    fn insert<P: Params>(&self, sql: &str, params: P) -> rusqlite::Result<i64> {
+        self.tx.prepare_cached(sql)?.insert(params)
+    }
+
+    fn translations(
+        &self,
+        entity: &str,
+        id: i64,
+        field: &str,
+        strings: &LanguageMap,
+    ) -> rusqlite::Result<()> {
+        for (language, value) in strings {
+            self.insert(
+                "INSERT INTO translations (entity, entity_id, field, language, value)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![entity, id, field, name(language), value],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn id_map(&self, sql: &str, owner: i64, values: &IdMap) -> rusqlite::Result<()> {
+        for (id, amount) in values {
+            self.insert(sql, params![owner, id, amount])?;
+        }
+
+        Ok(())
+    }
+
+    fn items(&self, db: &Database) -> rusqlite::Result<()> {
+        for item in &db.items {
+            let (foundry_kind, armor_sphere_value, ore_value, cost) = match &item.foundry {
+                Some(Foundry::Material(FoundryMaterial {
+                    armor_sphere_value,
+                    ore_value,
+                })) => (
+                    Some("material"),
+                    Some(armor_sphere_value),
+                    Some(ore_value),
+                    None,
+                ),
+                Some(Foundry::Output(FoundryOutput { cost })) => {
+                    (Some("output"), None, None, Some(cost))
+                }
+                None => (None, None, None, None),
+            };
+
+            self.insert(
+                "INSERT INTO items (
+                    game_id, kind, rarity, max_count, sell_price, buy_price, out_box, icon,
+                    icon_id, icon_color, icon_color_id, foundry_kind, foundry_armor_sphere_value,
+                    foundry_ore_value, foundry_cost
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    item.game_id,
+                    name(&item.kind),
+                    item.rarity,
+                    item.max_count,
+                    item.sell_price,
+                    item.buy_price,
+                    item.out_box,
+                    name(&item.icon),
+                    item.icon_id,
+                    name(&item.icon_color),
+                    item.icon_color_id,
+                    foundry_kind,
+                    armor_sphere_value,
+                    ore_value,
+                    cost,
+                ],
+            )?;
+
+            let id = item.game_id as i64;
+            self.translations("items", id, "names", &item.names)?;
+            self.translations("items", id, "descriptions", &item.descriptions)?;
+
+            for recipe in &item.recipes {
+                let recipe_id = self.insert(
+                    "INSERT INTO item_recipes (item_id, amount) VALUES (?1, ?2)",
+                    params![item.game_id, recipe.amount],
+                )?;
+
+                for (position, input) in recipe.inputs.iter().enumerate() {
+                    self.insert(
+                        "INSERT INTO item_recipe_inputs (recipe_id, position, item_id)
+                        VALUES (?1, ?2, ?3)",
+                        params![recipe_id, position, input],
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn skills(&self, db: &Database) -> rusqlite::Result<()> {
+        for skill in &db.skills {
+            self.insert(
+                "INSERT INTO skills (game_id, kind, icon, icon_id) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    skill.game_id,
+                    name(&skill.kind),
+                    name(&skill.icon),
+                    skill.icon_id
+                ],
+            )?;
+
+            let id = skill.game_id as i64;
+            self.translations("skills", id, "names", &skill.names)?;
+            self.translations("skills", id, "descriptions", &skill.descriptions)?;
+
+            for rank in &skill.ranks {
+                let rank_id = self.insert(
+                    "INSERT INTO skill_ranks (skill_id, level, set_pieces_required)
+                    VALUES (?1, ?2, ?3)",
+                    params![skill.game_id, rank.level, rank.set_pieces_required],
+                )?;
+
+                self.translations("skill_ranks", rank_id, "names", &rank.names)?;
+                self.translations("skill_ranks", rank_id, "descriptions", &rank.descriptions)?;
+
+                for (position, value) in rank.values.iter().enumerate() {
+                    self.insert(
+                        "INSERT INTO skill_rank_values (rank_id, position, value)
+                        VALUES (?1, ?2, ?3)",
+                        params![rank_id, position, value],
+                    )?;
+                }
+
+                for (position, skill_id) in rank.open_skill_ids.iter().enumerate() {
+                    self.insert(
+                        "INSERT INTO skill_rank_open_skills (rank_id, position, skill_id)
+                        VALUES (?1, ?2, ?3)",
+                        params![rank_id, position, skill_id],
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn accessories(&self, db: &Database) -> rusqlite::Result<()> {
+        for accessory in &db.accessories {
+            self.insert(
+                "INSERT INTO accessories (
+                    game_id, rarity, price, level, allowed_on, icon_color, icon_color_id
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    accessory.game_id,
+                    accessory.rarity,
+                    accessory.price,
+                    accessory.level,
+                    name(&accessory.allowed_on),
+                    name(&accessory.icon_color),
+                    accessory.icon_color_id,
+                ],
+            )?;
+
+            let id = accessory.game_id as i64;
+            self.translations("accessories", id, "names", &accessory.names)?;
+            self.translations("accessories", id, "descriptions", &accessory.descriptions)?;
+
+            self.id_map(
+                "INSERT INTO accessory_skills (accessory_id, skill_id, level) VALUES (?1, ?2, ?3)",
+                id,
+                &accessory.skills,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn charms(&self, db: &Database) -> rusqlite::Result<()> {
+        for charm in &db.charms {
+            self.insert(
+                "INSERT INTO charms (game_id) VALUES (?1)",
+                params![charm.game_id],
+            )?;
+
+            self.translations("charms", charm.game_id as i64, "names", &charm.names)?;
+        }
+
+        Ok(())
+    }
+
+    fn amulets(&self, db: &Database) -> rusqlite::Result<()> {
+        for amulet in &db.amulets {
+            self.insert(
+                "INSERT INTO amulets (game_id, is_random) VALUES (?1, ?2)",
+                params![amulet.game_id, amulet.is_random],
+            )?;
+
+            for rank in &amulet.ranks {
+                let rank_id = self.insert(
+                    "INSERT INTO amulet_ranks (amulet_id, rarity, level, price)
+                    VALUES (?1, ?2, ?3, ?4)",
+                    params![amulet.game_id, rank.rarity, rank.level, rank.price],
+                )?;
+
+                self.translations("amulet_ranks", rank_id, "names", &rank.names)?;
+                self.translations("amulet_ranks", rank_id, "descriptions", &rank.descriptions)?;
+
+                self.id_map(
+                    "INSERT INTO amulet_rank_skills (rank_id, skill_id, level) VALUES (?1, ?2, ?3)",
+                    rank_id,
+                    &rank.skills,
+                )?;
+
+                self.id_map(
+                    "INSERT INTO amulet_rank_recipe_inputs (rank_id, item_id, amount)
+                    VALUES (?1, ?2, ?3)",
+                    rank_id,
+                    &rank.recipe.inputs,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn armor(&self, db: &Database) -> rusqlite::Result<()> {
+        for set in &db.armor {
+            self.insert(
+                "INSERT INTO armor_sets (game_id, rarity, set_bonus_id, group_bonus_id, model_id)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    set.game_id,
+                    set.rarity,
+                    set.set_bonus_id,
+                    set.group_bonus_id,
+                    set.model_id,
+                ],
+            )?;
+
+            self.translations("armor_sets", set.game_id as i64, "names", &set.names)?;
+
+            for piece in &set.pieces {
+                let armor_id = self.insert(
+                    "INSERT INTO armor (
+                        set_id, kind, defense_base, defense_max, resistance_fire,
+                        resistance_water, resistance_thunder, resistance_ice, resistance_dragon,
+                        crafting_price
+                    ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    params![
+                        set.game_id,
+                        name(&piece.kind),
+                        piece.defense.base,
+                        piece.defense.max,
+                        piece.resistances.fire,
+                        piece.resistances.water,
+                        piece.resistances.thunder,
+                        piece.resistances.ice,
+                        piece.resistances.dragon,
+                        piece.crafting.price,
+                    ],
+                )?;
+
+                self.translations("armor", armor_id, "names", &piece.names)?;
+                self.translations("armor", armor_id, "descriptions", &piece.descriptions)?;
+
+                for (position, level) in piece.slots.iter().enumerate() {
+                    self.insert(
+                        "INSERT INTO armor_slots (armor_id, position, level) VALUES (?1, ?2, ?3)",
+                        params![armor_id, position, level],
+                    )?;
+                }
+
+                self.id_map(
+                    "INSERT INTO armor_skills (armor_id, skill_id, level) VALUES (?1, ?2, ?3)",
+                    armor_id,
+                    &piece.skills,
+                )?;
+
+                self.id_map(
+                    "INSERT INTO armor_crafting_inputs (armor_id, item_id, amount)
+                    VALUES (?1, ?2, ?3)",
+                    armor_id,
+                    &piece.crafting.inputs,
+                )?;
+            }
+        }
+
+        for upgrade in &db.armor_upgrades {
+            for step in &upgrade.steps {
+                self.insert(
+                    "INSERT INTO armor_upgrades (
+                        rarity, level, extra_defense, point_cost, zenny_cost
+                    ) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        upgrade.rarity,
+                        step.level,
+                        step.extra_defense,
+                        step.point_cost,
+                        step.zenny_cost,
+                    ],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn weapons(&self, db: &Database) -> rusqlite::Result<()> {
+        for series in &db.weapon_series {
+            self.insert(
+                "INSERT INTO weapon_series (game_id) VALUES (?1)",
+                params![series.game_id],
+            )?;
+
+            self.translations(
+                "weapon_series",
+                series.game_id as i64,
+                "names",
+                &series.names,
+            )?;
+        }
+
+        let weapons = [
+            &db.bows,
+            &db.charge_blades,
+            &db.dual_blades,
+            &db.great_swords,
+            &db.gunlances,
+            &db.hammers,
+            &db.heavy_bowguns,
+            &db.hunting_horns,
+            &db.insect_glaives,
+            &db.lances,
+            &db.light_bowguns,
+            &db.long_swords,
+            &db.switch_axes,
+            &db.sword_shields,
+        ];
+
+        for weapon in weapons.into_iter().flatten() {
+            self.weapon(weapon)?;
+        }
+
+        Ok(())
+    }
+
+    fn weapon(&self, weapon: &Weapon) -> rusqlite::Result<()> {
+        let crafting = &weapon.crafting;
+
+        let weapon_id = self.insert(
+            "INSERT INTO weapons (
+                kind, game_id, rarity, attack_raw, affinity, defense, series_id,
+                crafting_zenny_cost, crafting_previous_id, crafting_is_shortcut, crafting_column,
+                crafting_row
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                weapon_kind(&weapon.kind),
+                weapon.game_id,
+                weapon.rarity,
+                weapon.attack_raw,
+                weapon.affinity,
+                weapon.defense,
+                weapon.series_id,
+                crafting.zenny_cost,
+                crafting.previous_id,
+                crafting.is_shortcut,
+                crafting.column,
+                crafting.row,
+            ],
+        )?;
+
+        self.translations("weapons", weapon_id, "names", &weapon.names)?;
+        self.translations("weapons", weapon_id, "descriptions", &weapon.descriptions)?;
+
+        for (position, level) in weapon.slots.iter().enumerate() {
+            self.insert(
+                "INSERT INTO weapon_slots (weapon_id, position, level) VALUES (?1, ?2, ?3)",
+                params![weapon_id, position, level],
+            )?;
+        }
+
+        for special in &weapon.specials {
+            let (kind, value) = match special.kind {
+                SpecialKind::Element(element) => ("element", element_name(element)),
+                SpecialKind::Status(status) => ("status", status_name(status)),
+            };
+
+            self.insert(
+                "INSERT INTO weapon_specials (weapon_id, kind, value, raw, hidden)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![weapon_id, kind, value, special.raw, special.hidden],
+            )?;
+        }
+
+        self.id_map(
+            "INSERT INTO weapon_skills (weapon_id, skill_id, level) VALUES (?1, ?2, ?3)",
+            weapon_id,
+            &weapon.skills,
+        )?;
+
+        self.id_map(
+            "INSERT INTO weapon_crafting_inputs (weapon_id, item_id, amount) VALUES (?1, ?2, ?3)",
+            weapon_id,
+            &crafting.inputs,
+        )?;
+
+        for branch in &crafting.branches {
+            self.insert(
+                "INSERT INTO weapon_crafting_branches (weapon_id, game_id) VALUES (?1, ?2)",
+                params![weapon_id, branch],
+            )?;
+        }
+
+        match &weapon.kind {
+            WeaponKind::Bow(bow) => {
+                for coating in &bow.coatings {
+                    self.insert(
+                        "INSERT INTO bow_coatings (weapon_id, coating) VALUES (?1, ?2)",
+                        params![weapon_id, name(coating)],
+                    )?;
+                }
+            }
+            WeaponKind::ChargeBlade(blade) => {
+                self.melee(weapon_id, &blade.sharpness, &blade.handicraft)?;
+                self.insert(
+                    "INSERT INTO charge_blades (weapon_id, phial) VALUES (?1, ?2)",
+                    params![weapon_id, name(&blade.phial)],
+                )?;
+            }
+            WeaponKind::Gunlance(gunlance) => {
+                self.melee(weapon_id, &gunlance.sharpness, &gunlance.handicraft)?;
+                self.insert(
+                    "INSERT INTO gunlances (weapon_id, shell, shell_level) VALUES (?1, ?2, ?3)",
+                    params![weapon_id, name(&gunlance.shell), gunlance.shell_level],
+                )?;
+            }
+            WeaponKind::HeavyBowgun(bowgun) => {
+                for ammo in &bowgun.ammo {
+                    self.insert(
+                        "INSERT INTO heavy_bowgun_ammo (weapon_id, kind, level, capacity)
+                        VALUES (?1, ?2, ?3, ?4)",
+                        params![weapon_id, name(&ammo.kind), ammo.level, ammo.capacity],
+                    )?;
+                }
+            }
+            WeaponKind::HuntingHorn(horn) => {
+                self.melee(weapon_id, &horn.sharpness, &horn.handicraft)?;
+                self.insert(
+                    "INSERT INTO hunting_horns (weapon_id, melody_id, echo_wave_id, echo_bubble_id)
+                    VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        weapon_id,
+                        horn.melody_id,
+                        horn.echo_wave_id,
+                        horn.echo_bubble_id
+                    ],
+                )?;
+            }
+            WeaponKind::InsectGlaive(glaive) => {
+                self.melee(weapon_id, &glaive.sharpness, &glaive.handicraft)?;
+                self.insert(
+                    "INSERT INTO insect_glaives (weapon_id, kinsect_level) VALUES (?1, ?2)",
+                    params![weapon_id, glaive.kinsect_level],
+                )?;
+            }
+            WeaponKind::LightBowgun(bowgun) => {
+                self.insert(
+                    "INSERT INTO light_bowguns (weapon_id, special_ammo) VALUES (?1, ?2)",
+                    params![weapon_id, name(&bowgun.special_ammo)],
+                )?;
+
+                for ammo in &bowgun.ammo {
+                    self.insert(
+                        "INSERT INTO light_bowgun_ammo (weapon_id, kind, level, capacity, rapid)
+                        VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            weapon_id,
+                            name(&ammo.kind),
+                            ammo.level,
+                            ammo.capacity,
+                            ammo.rapid
+                        ],
+                    )?;
+                }
+            }
+            WeaponKind::SwitchAxe(axe) => {
+                self.melee(weapon_id, &axe.sharpness, &axe.handicraft)?;
+
+                let (phial, raw) = match axe.phial.kind {
+                    PhialKind::Power => ("power", None),
+                    PhialKind::Element => ("element", None),
+                    PhialKind::Dragon(raw) => ("dragon", Some(raw)),
+                    PhialKind::Exhaust(raw) => ("exhaust", Some(raw)),
+                    PhialKind::Paralyze(raw) => ("paralyze", Some(raw)),
+                    PhialKind::Poison(raw) => ("poison", Some(raw)),
+                };
+
+                self.insert(
+                    "INSERT INTO switch_axes (weapon_id, phial, phial_raw) VALUES (?1, ?2, ?3)",
+                    params![weapon_id, phial, raw],
+                )?;
+            }
+            WeaponKind::DualBlades(blades) => {
+                self.melee(weapon_id, &blades.sharpness, &blades.handicraft)?
+            }
+            WeaponKind::GreatSword(sword) => {
+                self.melee(weapon_id, &sword.sharpness, &sword.handicraft)?
+            }
+            WeaponKind::Hammer(hammer) => {
+                self.melee(weapon_id, &hammer.sharpness, &hammer.handicraft)?
+            }
+            WeaponKind::Lance(lance) => {
+                self.melee(weapon_id, &lance.sharpness, &lance.handicraft)?
+            }
+            WeaponKind::LongSword(sword) => {
+                self.melee(weapon_id, &sword.sharpness, &sword.handicraft)?
+            }
+            WeaponKind::SwordShield(sword) => {
+                self.melee(weapon_id, &sword.sharpness, &sword.handicraft)?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn melee(
+        &self,
+        weapon_id: i64,
+        sharpness: &Sharpness,
+        handicraft: &[u8],
+    ) -> rusqlite::Result<()> {
+        self.insert(
+            "INSERT INTO weapon_sharpness (
+                weapon_id, red, orange, yellow, green, blue, white, purple
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                weapon_id,
+                sharpness.red,
+                sharpness.orange,
+                sharpness.yellow,
+                sharpness.green,
+                sharpness.blue,
+                sharpness.white,
+                sharpness.purple,
+            ],
+        )?;
+
+        for (position, hits) in handicraft.iter().enumerate() {
+            self.insert(
+                "INSERT INTO weapon_handicraft (weapon_id, position, hits) VALUES (?1, ?2, ?3)",
+                params![weapon_id, position, hits],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn hunting_horn_data(&self, db: &Database) -> rusqlite::Result<()> {
+        for melody in &db.melodies {
+            let [first, second, third] = &melody.notes;
+
+            self.insert(
+                "INSERT INTO melodies (game_id, note_1, note_2, note_3) VALUES (?1, ?2, ?3, ?4)",
+                params![melody.game_id, name(first), name(second), name(third)],
+            )?;
+
+            for song in &melody.songs {
+                self.insert(
+                    "INSERT INTO melody_songs (melody_id, song_effect_id) VALUES (?1, ?2)",
+                    params![melody.game_id, song],
+                )?;
+            }
+        }
+
+        for song in &db.songs {
+            let song_id = self.insert(
+                "INSERT INTO songs (effect_id) VALUES (?1)",
+                params![song.effect_id],
+            )?;
+
+            self.translations("songs", song_id, "names", &song.names)?;
+
+            for (position, note) in song.notes.iter().enumerate() {
+                self.insert(
+                    "INSERT INTO song_notes (song_id, position, note) VALUES (?1, ?2, ?3)",
+                    params![song_id, position, name(note)],
+                )?;
+            }
+        }
+
+        for wave in &db.echo_waves {
+            self.insert(
+                "INSERT INTO echo_waves (game_id, kind) VALUES (?1, ?2)",
+                params![wave.game_id, name(&wave.kind)],
+            )?;
+
+            self.translations("echo_waves", wave.game_id as i64, "names", &wave.names)?;
+        }
+
+        for bubble in &db.echo_bubbles {
+            self.insert(
+                "INSERT INTO echo_bubbles (game_id, kind) VALUES (?1, ?2)",
+                params![bubble.game_id, name(&bubble.kind)],
+            )?;
+
+            self.translations(
+                "echo_bubbles",
+                bubble.game_id as i64,
+                "names",
+                &bubble.names,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn monsters(&self, db: &Database) -> rusqlite::Result<()> {
+        for species in &db.species {
+            let id = self.insert(
+                "INSERT INTO species (kind) VALUES (?1)",
+                params![name(&species.kind)],
+            )?;
+
+            self.translations("species", id, "names", &species.names)?;
+        }
+
+        for part in &db.part_names {
+            let id = self.insert(
+                "INSERT INTO part_names (part) VALUES (?1)",
+                params![<&str>::from(part.kind)],
+            )?;
+
+            self.translations("part_names", id, "names", &part.names)?;
+        }
+
+        for monster in &db.large_monsters {
+            self.monster(monster)?;
+        }
+
+        Ok(())
+    }
+
+    fn monster(&self, monster: &LargeMonster) -> rusqlite::Result<()> {
+        let size = &monster.size;
+
+        self.insert(
+            "INSERT INTO large_monsters (
+                game_id, species, base_health, size_base, size_mini, size_mini_multiplier,
+                size_silver, size_silver_multiplier, size_gold, size_gold_multiplier
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                monster.game_id,
+                name(&monster.species),
+                monster.base_health,
+                real(size.base),
+                real(size.mini),
+                real(size.mini_multiplier),
+                real(size.silver),
+                real(size.silver_multiplier),
+                real(size.gold),
+                real(size.gold_multiplier),
+            ],
+        )?;
+
+        let id = monster.game_id as i64;
+        self.translations("large_monsters", id, "names", &monster.names)?;
+        self.translations("large_monsters", id, "descriptions", &monster.descriptions)?;
+        self.translations("large_monsters", id, "features", &monster.features)?;
+        self.translations("large_monsters", id, "tips", &monster.tips)?;
+
+        for variant in &monster.variants {
+            let variant_id = self.insert(
+                "INSERT INTO monster_variants (monster_id, kind) VALUES (?1, ?2)",
+                params![monster.game_id, name(&variant.kind)],
+            )?;
+
+            self.translations("monster_variants", variant_id, "names", &variant.names)?;
+        }
+
+        for stage in &monster.locations {
+            self.insert(
+                "INSERT INTO monster_locations (monster_id, stage_id) VALUES (?1, ?2)",
+                params![monster.game_id, stage],
+            )?;
+        }
+
+        for weakness in &monster.weaknesses {
+            let (kind, value) = effective_kind(weakness.kind);
+
+            let weakness_id = self.insert(
+                "INSERT INTO monster_weaknesses (monster_id, kind, value, level)
+                VALUES (?1, ?2, ?3, ?4)",
+                params![monster.game_id, kind, value, weakness.level],
+            )?;
+
+            if let Some(condition) = &weakness.condition {
+                self.translations("monster_weaknesses", weakness_id, "condition", condition)?;
+            }
+        }
+
+        for resistance in &monster.resistances {
+            let (kind, value) = effective_kind(resistance.kind);
+
+            self.insert(
+                "INSERT INTO monster_resistances (monster_id, kind, value) VALUES (?1, ?2, ?3)",
+                params![monster.game_id, kind, value],
+            )?;
+        }
+
+        for reward in &monster.rewards {
+            let (source, part) = reward_source(reward.source);
+
+            self.insert(
+                "INSERT INTO monster_rewards (
+                    monster_id, rank, source, part, item_id, amount, chance
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    monster.game_id,
+                    name(&reward.rank),
+                    source,
+                    part,
+                    reward.item_id,
+                    reward.amount,
+                    reward.chance,
+                ],
+            )?;
+        }
+
+        for part in &monster.parts {
+            let multipliers = &part.multipliers;
+
+            self.insert(
+                "INSERT INTO monster_parts (
+                    monster_id, part, base_health, kinsect_essence, slash, blunt, pierce, fire,
+                    water, thunder, ice, dragon, stun
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    monster.game_id,
+                    <&str>::from(part.kind),
+                    part.base_health,
+                    name(&part.kinsect_essence),
+                    real(multipliers.slash),
+                    real(multipliers.blunt),
+                    real(multipliers.pierce),
+                    real(multipliers.fire),
+                    real(multipliers.water),
+                    real(multipliers.thunder),
+                    real(multipliers.ice),
+                    real(multipliers.dragon),
+                    real(multipliers.stun),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn stages(&self, db: &Database) -> rusqlite::Result<()> {
+        for stage in &db.stages {
+            self.insert(
+                "INSERT INTO stages (game_id, areas, bitmask_value) VALUES (?1, ?2, ?3)",
+                params![stage.game_id, stage.areas, stage.bitmask_value],
+            )?;
+
+            self.translations("stages", stage.game_id as i64, "names", &stage.names)?;
+
+            for camp in &stage.camps {
+                let camp_id = self.insert(
+                    "INSERT INTO camps (game_id, stage_id, area, floor, risk, x, y, z)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        camp.game_id,
+                        stage.game_id,
+                        camp.area,
+                        camp.floor,
+                        name(&camp.risk),
+                        real(camp.position.x),
+                        real(camp.position.y),
+                        real(camp.position.z),
+                    ],
+                )?;
+
+                self.translations("camps", camp_id, "names", &camp.names)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Widens an `f32` to the value it's written as in the JSON output, so that e.g. `0.3` doesn't end
+/// up stored as `0.30000001192092896`.
+fn real(value: f32) -> f64 {
+    value.to_string().parse().unwrap_or(value as f64)
+}
+
+/// Returns the name an enum value serializes to, or `None` if it serializes to anything other than
+/// a plain string (such as `null`).
+fn name<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_value(value).expect("model types should always serialize") {
+        Value::String(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn weapon_kind(kind: &WeaponKind) -> &'static str {
+    match kind {
+        WeaponKind::Bow(_) => "bow",
+        WeaponKind::ChargeBlade(_) => "charge-blade",
+        WeaponKind::Gunlance(_) => "gunlance",
+        WeaponKind::Hammer(_) => "hammer",
+        WeaponKind::HeavyBowgun(_) => "heavy-bowgun",
+        WeaponKind::Lance(_) => "lance",
+        WeaponKind::LightBowgun(_) => "light-bowgun",
+        WeaponKind::GreatSword(_) => "great-sword",
+        WeaponKind::InsectGlaive(_) => "insect-glaive",
+        WeaponKind::SwordShield(_) => "sword-shield",
+        WeaponKind::SwitchAxe(_) => "switch-axe",
+        WeaponKind::LongSword(_) => "long-sword",
+        WeaponKind::DualBlades(_) => "dual-blades",
+        WeaponKind::HuntingHorn(_) => "hunting-horn",
+    }
+}
+
+fn element_name(element: Element) -> &'static str {
+    match element {
+        Element::Fire => "fire",
+        Element::Water => "water",
+        Element::Thunder => "thunder",
+        Element::Ice => "ice",
+        Element::Dragon => "dragon",
+    }
+}
+
+fn status_name(status: Status) -> &'static str {
+    match status {
+        Status::Paralysis => "paralysis",
+        Status::Poison => "poison",
+        Status::Sleep => "sleep",
+        Status::Blastblight => "blastblight",
+    }
+}
+
+/// Splits a monster weakness or resistance into its kind (e.g. "element") and the name of the
+/// specific element, status, or effect.
+fn effective_kind(kind: EffectiveKind) -> (&'static str, &'static str) {
+    match kind {
+        EffectiveKind::Element(element) => ("element", element_name(element)),
+        EffectiveKind::Status(status) => ("status", status_name(status)),
+        EffectiveKind::Effect(effect) => (
+            "effect",
+            match effect {
+                Effect::Noise => "noise",
+                Effect::Flash => "flash",
+                Effect::Stun => "stun",
+                Effect::Exhaust => "exhaust",
+            },
+        ),
+    }
+}
+
+/// Splits a reward source into its kind and, for broken part rewards, the part that was broken.
+fn reward_source(source: RewardSource) -> (&'static str, Option<&'static str>) {
+    let kind = match source {
+        RewardSource::Carve => "carve",
+        RewardSource::CarveSevered => "carve-severed",
+        RewardSource::EndemicCapture => "endemic-capture",
+        RewardSource::TargetReward => "target-reward",
+        RewardSource::BrokenPart(part) => return ("broken-part", Some(part.into())),
+        RewardSource::WoundDestroyed => "wound-destroyed",
+        RewardSource::CarveRotten => "carve-rotten",
+        RewardSource::SlingerGather => "slinger-gather",
+        RewardSource::CarveRottenSevered => "carve-rotten-severed",
+        RewardSource::TemperedWoundDestroyed => "tempered-wound-destroyed",
+        RewardSource::CarveCrystallized => "carve-crystallized",
+        RewardSource::BrokenFragment => "broken-fragment",
+    };
+
+    (kind, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::export;
+    use mhdb_model::Database;
+    use rusqlite::Connection;
+    use serde_json::json;
+
+    fn database() -> Database {
+        let item = json!({
+            "game_id": 1,
+            "names": {},
+            "descriptions": {},
+            "kind": "material",
+            "rarity": 1,
+            "max_count": 99,
+            "sell_price": 10,
+            "buy_price": 0,
+            "recipes": [],
+            "out_box": false,
+            "icon": "question",
+            "icon_id": 0,
+            "icon_color": "none",
+            "icon_color_id": 0,
+            "foundry": { "kind": "material", "armor_sphere_value": 5, "ore_value": 10 },
+        });
+
+        let axe = json!({
+            "game_id": 2,
+            "kind": "switch-axe",
+            "sharpness": {
+                "red": 0, "orange": 0, "yellow": 0, "green": 0, "blue": 0, "white": 0, "purple": 0,
+            },
+            "handicraft": [],
+            "phial": { "kind": "dragon", "raw": 30 },
+            "names": {},
+            "descriptions": {},
+            "rarity": 1,
+            "attack_raw": 100,
+            "affinity": 0,
+            "defense": 0,
+            "slots": [],
+            "specials": [{ "kind": "element", "element": "fire", "raw": 15, "hidden": false }],
+            "crafting": {
+                "zenny_cost": 0, "inputs": {}, "previous_id": null, "branches": [],
+                "is_shortcut": false, "column": 0, "row": 0,
+            },
+            "skills": {},
+            "series_id": null,
+        });
+
+        let monster = json!({
+            "game_id": 3,
+            "species": "flying-wyvern",
+            "names": {},
+            "descriptions": {},
+            "features": {},
+            "tips": {},
+            "variants": [],
+            "size": {
+                "base": 1.0, "mini": 0.9, "mini_multiplier": 0.9, "silver": 1.1,
+                "silver_multiplier": 1.1, "gold": 1.2, "gold_multiplier": 1.2,
+            },
+            "base_health": 1000,
+            "locations": [],
+            "weaknesses": [{ "kind": "effect", "effect": "flash", "level": 1, "condition": null }],
+            "resistances": [{ "kind": "status", "status": "poison" }],
+            "rewards": [
+                {
+                    "rank": "high", "kind": "broken-part", "part": "head2", "item_id": 1,
+                    "amount": 1, "chance": 20,
+                },
+                { "rank": "low", "kind": "carve-rotten-severed", "item_id": 1, "amount": 1, "chance": 5 },
+            ],
+            "parts": [{
+                "part": "left-leg-front-and-rear",
+                "base_health": 100,
+                "kinsect_essence": "orange",
+                "multipliers": {
+                    "slash": 1.0, "blunt": 1.0, "pierce": 1.0, "fire": 1.0, "water": 1.0,
+                    "thunder": 1.0, "ice": 1.0, "dragon": 1.0, "stun": 1.0,
+                },
+            }],
+        });
+
+        Database {
+            items: vec![serde_json::from_value(item).unwrap()],
+            switch_axes: vec![serde_json::from_value(axe).unwrap()],
+            large_monsters: vec![serde_json::from_value(monster).unwrap()],
+            part_names: vec![
+                serde_json::from_value(json!({ "part": "tail-tip", "names": {} })).unwrap(),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn column(conn: &Connection, sql: &str) -> Vec<Option<String>> {
+        let mut statement = conn.prepare(sql).unwrap();
+
+        statement
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn row(conn: &Connection, sql: &str) -> Vec<String> {
+        conn.query_row(sql, [], |row| {
+            (0..row.as_ref().column_count())
+                .map(|index| {
+                    row.get::<_, Option<String>>(index)
+                        .map(Option::unwrap_or_default)
+                })
+                .collect()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn exports_tagged_enum_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mhdb.sqlite");
+        export(&database(), &path).unwrap();
+
+        let conn = Connection::open(&path).unwrap();
+
+        assert_eq!(row(&conn, "SELECT foundry_kind FROM items"), ["material"]);
+        assert_eq!(row(&conn, "SELECT kind FROM weapons"), ["switch-axe"]);
+        assert_eq!(
+            row(
+                &conn,
+                "SELECT phial, CAST(phial_raw AS TEXT) FROM switch_axes"
+            ),
+            ["dragon", "30"]
+        );
+        assert_eq!(
+            row(&conn, "SELECT kind, value FROM weapon_specials"),
+            ["element", "fire"]
+        );
+        assert_eq!(
+            row(&conn, "SELECT kind, value FROM monster_weaknesses"),
+            ["effect", "flash"]
+        );
+        assert_eq!(
+            row(&conn, "SELECT kind, value FROM monster_resistances"),
+            ["status", "poison"]
+        );
+        assert_eq!(row(&conn, "SELECT part FROM part_names"), ["tail-tip"]);
+        assert_eq!(
+            row(&conn, "SELECT part FROM monster_parts"),
+            ["left-leg-front-and-rear"]
+        );
+        assert_eq!(
+            column(
+                &conn,
+                "SELECT source FROM monster_rewards ORDER BY chance DESC"
+            ),
+            [
+                Some("broken-part".to_owned()),
+                Some("carve-rotten-severed".to_owned())
+            ]
+        );
+        assert_eq!(
+            column(
+                &conn,
+                "SELECT part FROM monster_rewards ORDER BY chance DESC"
+            ),
+            [Some("head2".to_owned()), None]
+        );
+    }
+}
@@ -0,0 +1,434 @@
+-- Localized strings for every table below. `entity` is the name of the table holding the object,
+-- and `entity_id` is the value of that table's primary key.
+CREATE TABLE translations (
+    entity TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    field TEXT NOT NULL,
+    language TEXT NOT NULL,
+    value TEXT NOT NULL,
+    PRIMARY KEY (entity, entity_id, field, language)
+);
+
+CREATE TABLE items (
+    game_id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    rarity INTEGER NOT NULL,
+    max_count INTEGER NOT NULL,
+    sell_price INTEGER NOT NULL,
+    buy_price INTEGER NOT NULL,
+    out_box INTEGER NOT NULL,
+    icon TEXT NOT NULL,
+    icon_id INTEGER NOT NULL,
+    icon_color TEXT NOT NULL,
+    icon_color_id INTEGER NOT NULL,
+    foundry_kind TEXT,
+    foundry_armor_sphere_value INTEGER,
+    foundry_ore_value INTEGER,
+    foundry_cost INTEGER
+);
+
+CREATE TABLE item_recipes (
+    id INTEGER PRIMARY KEY,
+    item_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL
+);
+
+CREATE TABLE item_recipe_inputs (
+    recipe_id INTEGER NOT NULL REFERENCES item_recipes (id),
+    position INTEGER NOT NULL,
+    item_id INTEGER NOT NULL,
+    PRIMARY KEY (recipe_id, position)
+);
+
+CREATE TABLE skills (
+    game_id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    icon TEXT NOT NULL,
+    icon_id INTEGER NOT NULL
+);
+
+CREATE TABLE skill_ranks (
+    id INTEGER PRIMARY KEY,
+    skill_id INTEGER NOT NULL REFERENCES skills (game_id),
+    level INTEGER NOT NULL,
+    set_pieces_required INTEGER NOT NULL
+);
+
+CREATE TABLE skill_rank_values (
+    rank_id INTEGER NOT NULL REFERENCES skill_ranks (id),
+    position INTEGER NOT NULL,
+    value INTEGER NOT NULL,
+    PRIMARY KEY (rank_id, position)
+);
+
+CREATE TABLE skill_rank_open_skills (
+    rank_id INTEGER NOT NULL REFERENCES skill_ranks (id),
+    position INTEGER NOT NULL,
+    skill_id INTEGER NOT NULL REFERENCES skills (game_id),
+    PRIMARY KEY (rank_id, position)
+);
+
+CREATE TABLE accessories (
+    game_id INTEGER PRIMARY KEY,
+    rarity INTEGER NOT NULL,
+    price INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    allowed_on TEXT NOT NULL,
+    icon_color TEXT NOT NULL,
+    icon_color_id INTEGER NOT NULL
+);
+
+CREATE TABLE accessory_skills (
+    accessory_id INTEGER NOT NULL REFERENCES accessories (game_id),
+    skill_id INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (accessory_id, skill_id)
+);
+
+CREATE TABLE charms (
+    game_id INTEGER PRIMARY KEY
+);
+
+CREATE TABLE amulets (
+    game_id INTEGER PRIMARY KEY,
+    is_random INTEGER NOT NULL
+);
+
+CREATE TABLE amulet_ranks (
+    id INTEGER PRIMARY KEY,
+    amulet_id INTEGER NOT NULL REFERENCES amulets (game_id),
+    rarity INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    price INTEGER NOT NULL
+);
+
+CREATE TABLE amulet_rank_skills (
+    rank_id INTEGER NOT NULL REFERENCES amulet_ranks (id),
+    skill_id INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (rank_id, skill_id)
+);
+
+CREATE TABLE amulet_rank_recipe_inputs (
+    rank_id INTEGER NOT NULL REFERENCES amulet_ranks (id),
+    item_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    PRIMARY KEY (rank_id, item_id)
+);
+
+CREATE TABLE armor_sets (
+    game_id INTEGER PRIMARY KEY,
+    rarity INTEGER NOT NULL,
+    set_bonus_id INTEGER,
+    group_bonus_id INTEGER,
+    model_id INTEGER NOT NULL
+);
+
+CREATE TABLE armor (
+    id INTEGER PRIMARY KEY,
+    set_id INTEGER NOT NULL REFERENCES armor_sets (game_id),
+    kind TEXT NOT NULL,
+    defense_base INTEGER NOT NULL,
+    defense_max INTEGER NOT NULL,
+    resistance_fire INTEGER NOT NULL,
+    resistance_water INTEGER NOT NULL,
+    resistance_thunder INTEGER NOT NULL,
+    resistance_ice INTEGER NOT NULL,
+    resistance_dragon INTEGER NOT NULL,
+    crafting_price INTEGER NOT NULL
+);
+
+CREATE TABLE armor_slots (
+    armor_id INTEGER NOT NULL REFERENCES armor (id),
+    position INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (armor_id, position)
+);
+
+CREATE TABLE armor_skills (
+    armor_id INTEGER NOT NULL REFERENCES armor (id),
+    skill_id INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (armor_id, skill_id)
+);
+
+CREATE TABLE armor_crafting_inputs (
+    armor_id INTEGER NOT NULL REFERENCES armor (id),
+    item_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    PRIMARY KEY (armor_id, item_id)
+);
+
+CREATE TABLE armor_upgrades (
+    rarity INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    extra_defense INTEGER NOT NULL,
+    point_cost INTEGER NOT NULL,
+    zenny_cost INTEGER NOT NULL,
+    PRIMARY KEY (rarity, level)
+);
+
+CREATE TABLE weapon_series (
+    game_id INTEGER PRIMARY KEY
+);
+
+-- Weapon IDs are only unique within a single weapon kind, so weapons get a surrogate key. The
+-- `crafting_previous_id` column and the `weapon_crafting_branches` table hold game IDs, which
+-- should be resolved against weapons of the same kind.
+CREATE TABLE weapons (
+    id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    game_id INTEGER NOT NULL,
+    rarity INTEGER NOT NULL,
+    attack_raw INTEGER NOT NULL,
+    affinity INTEGER NOT NULL,
+    defense INTEGER NOT NULL,
+    series_id INTEGER,
+    crafting_zenny_cost INTEGER NOT NULL,
+    crafting_previous_id INTEGER,
+    crafting_is_shortcut INTEGER NOT NULL,
+    crafting_column INTEGER NOT NULL,
+    crafting_row INTEGER NOT NULL,
+    UNIQUE (kind, game_id)
+);
+
+CREATE TABLE weapon_slots (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    position INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (weapon_id, position)
+);
+
+CREATE TABLE weapon_specials (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    raw INTEGER NOT NULL,
+    hidden INTEGER NOT NULL
+);
+
+CREATE TABLE weapon_skills (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    skill_id INTEGER NOT NULL,
+    level INTEGER NOT NULL,
+    PRIMARY KEY (weapon_id, skill_id)
+);
+
+CREATE TABLE weapon_crafting_inputs (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    item_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    PRIMARY KEY (weapon_id, item_id)
+);
+
+CREATE TABLE weapon_crafting_branches (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    game_id INTEGER NOT NULL,
+    PRIMARY KEY (weapon_id, game_id)
+);
+
+-- Only populated for melee weapons.
+CREATE TABLE weapon_sharpness (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    red INTEGER NOT NULL,
+    orange INTEGER NOT NULL,
+    yellow INTEGER NOT NULL,
+    green INTEGER NOT NULL,
+    blue INTEGER NOT NULL,
+    white INTEGER NOT NULL,
+    purple INTEGER NOT NULL
+);
+
+CREATE TABLE weapon_handicraft (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    position INTEGER NOT NULL,
+    hits INTEGER NOT NULL,
+    PRIMARY KEY (weapon_id, position)
+);
+
+CREATE TABLE bow_coatings (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    coating TEXT NOT NULL,
+    PRIMARY KEY (weapon_id, coating)
+);
+
+CREATE TABLE charge_blades (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    phial TEXT NOT NULL
+);
+
+CREATE TABLE gunlances (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    shell TEXT NOT NULL,
+    shell_level INTEGER NOT NULL
+);
+
+CREATE TABLE heavy_bowgun_ammo (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    kind TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    capacity INTEGER NOT NULL
+);
+
+CREATE TABLE hunting_horns (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    melody_id INTEGER NOT NULL,
+    echo_wave_id INTEGER,
+    echo_bubble_id INTEGER
+);
+
+CREATE TABLE insect_glaives (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    kinsect_level INTEGER NOT NULL
+);
+
+CREATE TABLE light_bowguns (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    special_ammo TEXT NOT NULL
+);
+
+CREATE TABLE light_bowgun_ammo (
+    weapon_id INTEGER NOT NULL REFERENCES weapons (id),
+    kind TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    capacity INTEGER NOT NULL,
+    rapid INTEGER NOT NULL
+);
+
+CREATE TABLE switch_axes (
+    weapon_id INTEGER PRIMARY KEY REFERENCES weapons (id),
+    phial TEXT NOT NULL,
+    phial_raw INTEGER
+);
+
+CREATE TABLE melodies (
+    game_id INTEGER PRIMARY KEY,
+    note_1 TEXT NOT NULL,
+    note_2 TEXT NOT NULL,
+    note_3 TEXT NOT NULL
+);
+
+CREATE TABLE melody_songs (
+    melody_id INTEGER NOT NULL REFERENCES melodies (game_id),
+    song_effect_id INTEGER NOT NULL,
+    PRIMARY KEY (melody_id, song_effect_id)
+);
+
+-- Song effect IDs aren't unique, since the same effect can be played by more than one set of
+-- notes.
+CREATE TABLE songs (
+    id INTEGER PRIMARY KEY,
+    effect_id INTEGER NOT NULL
+);
+
+CREATE TABLE song_notes (
+    song_id INTEGER NOT NULL REFERENCES songs (id),
+    position INTEGER NOT NULL,
+    note TEXT NOT NULL,
+    PRIMARY KEY (song_id, position)
+);
+
+CREATE TABLE echo_waves (
+    game_id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL
+);
+
+CREATE TABLE echo_bubbles (
+    game_id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL
+);
+
+CREATE TABLE species (
+    id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE part_names (
+    id INTEGER PRIMARY KEY,
+    part TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE large_monsters (
+    game_id INTEGER PRIMARY KEY,
+    species TEXT NOT NULL,
+    base_health INTEGER NOT NULL,
+    size_base REAL NOT NULL,
+    size_mini REAL NOT NULL,
+    size_mini_multiplier REAL NOT NULL,
+    size_silver REAL NOT NULL,
+    size_silver_multiplier REAL NOT NULL,
+    size_gold REAL NOT NULL,
+    size_gold_multiplier REAL NOT NULL
+);
+
+CREATE TABLE monster_variants (
+    id INTEGER PRIMARY KEY,
+    monster_id INTEGER NOT NULL REFERENCES large_monsters (game_id),
+    kind TEXT NOT NULL
+);
+
+CREATE TABLE monster_locations (
+    monster_id INTEGER NOT NULL REFERENCES large_monsters (game_id),
+    stage_id INTEGER NOT NULL,
+    PRIMARY KEY (monster_id, stage_id)
+);
+
+CREATE TABLE monster_weaknesses (
+    id INTEGER PRIMARY KEY,
+    monster_id INTEGER NOT NULL REFERENCES large_monsters (game_id),
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    level INTEGER NOT NULL
+);
+
+CREATE TABLE monster_resistances (
+    monster_id INTEGER NOT NULL REFERENCES large_monsters (game_id),
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL
+);
+
+-- `part` is only set for rewards with a `source` of "broken-part".
+CREATE TABLE monster_rewards (
+    monster_id INTEGER NOT NULL REFERENCES large_monsters (game_id),
+    rank TEXT NOT NULL,
+    source TEXT NOT NULL,
+    part TEXT,
+    item_id INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    chance INTEGER NOT NULL
+);
+
+CREATE TABLE monster_parts (
+    id INTEGER PRIMARY KEY,
+    monster_id INTEGER NOT NULL REFERENCES large_monsters (game_id),
+    part TEXT NOT NULL,
+    base_health INTEGER,
+    kinsect_essence TEXT,
+    slash REAL NOT NULL,
+    blunt REAL NOT NULL,
+    pierce REAL NOT NULL,
+    fire REAL NOT NULL,
+    water REAL NOT NULL,
+    thunder REAL NOT NULL,
+    ice REAL NOT NULL,
+    dragon REAL NOT NULL,
+    stun REAL NOT NULL
+);
+
+CREATE TABLE stages (
+    game_id INTEGER PRIMARY KEY,
+    areas INTEGER NOT NULL,
+    bitmask_value INTEGER NOT NULL
+);
+
+CREATE TABLE camps (
+    id INTEGER PRIMARY KEY,
+    game_id INTEGER NOT NULL,
+    stage_id INTEGER NOT NULL REFERENCES stages (game_id),
+    area INTEGER NOT NULL,
+    floor INTEGER NOT NULL,
+    risk TEXT NOT NULL,
+    x REAL NOT NULL,
+    y REAL NOT NULL,
+    z REAL NOT NULL
+);
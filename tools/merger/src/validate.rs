@@ -0,0 +1,341 @@
+use mhdb_model::weapons::{Weapon, WeaponKind};
+use mhdb_model::Database;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// The result of checking a [`Database`] for consistency.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub dangling: Vec<DanglingReference>,
+    pub duplicates: Vec<DuplicateId>,
+    pub orphans: Vec<Orphan>,
+}
+
+impl Report {
+    /// Returns `true` if the report contains dangling references or duplicate IDs. Orphans aren't
+    /// considered errors, since plenty of objects (such as most consumable items) are never
+    /// referenced by anything else in the data set.
+    pub fn has_errors(&self) -> bool {
+        !self.dangling.is_empty() || !self.duplicates.is_empty()
+    }
+
+    fn references(&mut self, file: &'static str, owner: i64) -> References<'_> {
+        References {
+            report: self,
+            file,
+            owner,
+        }
+    }
+}
+
+/// A reference to an ID that doesn't exist in the file it points into.
+#[derive(Debug, Serialize)]
+pub struct DanglingReference {
+    /// The file containing the reference.
+    pub file: &'static str,
+    /// The ID of the object holding the reference.
+    pub owner: i64,
+    /// The field holding the reference.
+    pub field: &'static str,
+    /// The file the reference should resolve to.
+    pub target: &'static str,
+    pub id: i64,
+}
+
+/// An ID used by more than one object in the same file.
+#[derive(Debug, Serialize)]
+pub struct DuplicateId {
+    pub file: &'static str,
+    pub id: i64,
+    pub count: usize,
+}
+
+/// An object that isn't referenced by any other file.
+#[derive(Debug, Serialize)]
+pub struct Orphan {
+    pub file: &'static str,
+    pub id: i64,
+}
+
+/// Checks every cross-file reference in `db`, as well as each file's IDs for duplicates.
+///
+/// Checks against a file with no objects are skipped, since that usually means the file wasn't
+/// merged (or was filtered out) rather than that every reference into it is broken.
+pub fn validate(db: &Database) -> Report {
+    let mut report = Report::default();
+
+    let mut items = Target::new(Database::ITEMS, db.items.iter().map(|v| v.game_id as i64));
+    let mut skills = Target::new(Database::SKILLS, db.skills.iter().map(|v| v.game_id as i64));
+    let mut stages = Target::new(Database::STAGES, db.stages.iter().map(|v| v.game_id as i64));
+    let mut series = Target::new(
+        Database::WEAPON_SERIES,
+        db.weapon_series.iter().map(|v| v.game_id as i64),
+    );
+    let mut melodies = Target::new(
+        Database::MELODIES,
+        db.melodies.iter().map(|v| v.game_id as i64),
+    );
+    let mut songs = Target::new(Database::SONGS, db.songs.iter().map(|v| v.effect_id as i64));
+    let mut echo_waves = Target::new(
+        Database::ECHO_WAVES,
+        db.echo_waves.iter().map(|v| v.game_id as i64),
+    );
+    let mut echo_bubbles = Target::new(
+        Database::ECHO_BUBBLES,
+        db.echo_bubbles.iter().map(|v| v.game_id as i64),
+    );
+
+    for item in &db.items {
+        let mut refs = report.references(Database::ITEMS, item.game_id as i64);
+
+        for recipe in &item.recipes {
+            refs.check_all(&mut items, "recipes.inputs", &recipe.inputs);
+        }
+    }
+
+    for accessory in &db.accessories {
+        let mut refs = report.references(Database::ACCESSORIES, accessory.game_id as i64);
+        refs.check_all(&mut skills, "skills", accessory.skills.keys());
+    }
+
+    for amulet in &db.amulets {
+        let mut refs = report.references(Database::AMULETS, amulet.game_id as i64);
+
+        for rank in &amulet.ranks {
+            refs.check_all(&mut skills, "ranks.skills", rank.skills.keys());
+            refs.check_all(&mut items, "ranks.recipe.inputs", rank.recipe.inputs.keys());
+        }
+    }
+
+    for skill in &db.skills {
+        let mut refs = report.references(Database::SKILLS, skill.game_id as i64);
+
+        for rank in &skill.ranks {
+            // Ranks almost always enable their own skill, which shouldn't stop it being an orphan.
+            let open_skill_ids = rank.open_skill_ids.iter().filter(|v| **v != skill.game_id);
+            refs.check_all(&mut skills, "ranks.open_skill_ids", open_skill_ids);
+        }
+    }
+
+    for set in &db.armor {
+        let mut refs = report.references(Database::ARMOR, set.game_id as i64);
+        refs.check_all(&mut skills, "set_bonus_id", set.set_bonus_id);
+        refs.check_all(&mut skills, "group_bonus_id", set.group_bonus_id);
+
+        for piece in &set.pieces {
+            refs.check_all(&mut skills, "pieces.skills", piece.skills.keys());
+            refs.check_all(
+                &mut items,
+                "pieces.crafting.inputs",
+                piece.crafting.inputs.keys(),
+            );
+        }
+    }
+
+    for (file, weapons) in weapon_files(db) {
+        let mut siblings = Target::new(file, weapons.iter().map(|v| v.game_id as i64));
+
+        for weapon in weapons {
+            let mut refs = report.references(file, weapon.game_id as i64);
+            refs.check_all(&mut items, "crafting.inputs", weapon.crafting.inputs.keys());
+            refs.check_all(&mut skills, "skills", weapon.skills.keys());
+            refs.check_all(&mut series, "series_id", weapon.series_id);
+            refs.check_all(
+                &mut siblings,
+                "crafting.previous_id",
+                weapon.crafting.previous_id,
+            );
+            refs.check_all(
+                &mut siblings,
+                "crafting.branches",
+                &weapon.crafting.branches,
+            );
+
+            if let WeaponKind::HuntingHorn(horn) = &weapon.kind {
+                refs.check(&mut melodies, "melody_id", horn.melody_id as i64);
+                refs.check_all(&mut echo_waves, "echo_wave_id", horn.echo_wave_id);
+                refs.check_all(&mut echo_bubbles, "echo_bubble_id", horn.echo_bubble_id);
+            }
+        }
+
+        report.duplicates.extend(siblings.duplicates());
+    }
+
+    for melody in &db.melodies {
+        let mut refs = report.references(Database::MELODIES, melody.game_id as i64);
+        refs.check_all(&mut songs, "songs", &melody.songs);
+    }
+
+    for monster in &db.large_monsters {
+        let mut refs = report.references(Database::LARGE_MONSTERS, monster.game_id as i64);
+        refs.check_all(&mut stages, "locations", &monster.locations);
+
+        for reward in &monster.rewards {
+            refs.check(&mut items, "rewards.item_id", reward.item_id as i64);
+        }
+    }
+
+    for target in [&items, &skills] {
+        report.orphans.extend(target.orphans());
+    }
+
+    let others = [
+        Target::new(
+            Database::ACCESSORIES,
+            db.accessories.iter().map(|v| v.game_id as i64),
+        ),
+        Target::new(
+            Database::AMULETS,
+            db.amulets.iter().map(|v| v.game_id as i64),
+        ),
+        Target::new(Database::ARMOR, db.armor.iter().map(|v| v.game_id as i64)),
+        Target::new(Database::CHARMS, db.charms.iter().map(|v| v.game_id as i64)),
+        Target::new(
+            Database::LARGE_MONSTERS,
+            db.large_monsters.iter().map(|v| v.game_id as i64),
+        ),
+    ];
+
+    // Songs are skipped here, since the same effect can be played by more than one set of notes.
+    for target in [
+        &items,
+        &skills,
+        &stages,
+        &series,
+        &melodies,
+        &echo_waves,
+        &echo_bubbles,
+    ]
+    .into_iter()
+    .chain(&others)
+    {
+        report.duplicates.extend(target.duplicates());
+    }
+
+    report
+}
+
+fn weapon_files(db: &Database) -> [(&'static str, &Vec<Weapon>); 14] {
+    [
+        (Database::BOWS, &db.bows),
+        (Database::CHARGE_BLADES, &db.charge_blades),
+        (Database::DUAL_BLADES, &db.dual_blades),
+        (Database::GREAT_SWORDS, &db.great_swords),
+        (Database::GUNLANCES, &db.gunlances),
+        (Database::HAMMERS, &db.hammers),
+        (Database::HEAVY_BOWGUNS, &db.heavy_bowguns),
+        (Database::HUNTING_HORNS, &db.hunting_horns),
+        (Database::INSECT_GLAIVES, &db.insect_glaives),
+        (Database::LANCES, &db.lances),
+        (Database::LIGHT_BOWGUNS, &db.light_bowguns),
+        (Database::LONG_SWORDS, &db.long_swords),
+        (Database::SWITCH_AXES, &db.switch_axes),
+        (Database::SWORD_SHIELDS, &db.sword_shields),
+    ]
+}
+
+/// The IDs of every object in a single file, along with which of them have been referenced.
+struct Target {
+    file: &'static str,
+    ids: BTreeMap<i64, usize>,
+    referenced: HashSet<i64>,
+}
+
+impl Target {
+    fn new<I: IntoIterator<Item = i64>>(file: &'static str, ids: I) -> Self {
+        let mut counts = BTreeMap::new();
+
+        for id in ids {
+            *counts.entry(id).or_default() += 1;
+        }
+
+        Self {
+            file,
+            ids: counts,
+            referenced: HashSet::new(),
+        }
+    }
+
+    fn duplicates(&self) -> impl Iterator<Item = DuplicateId> + '_ {
+        self.ids
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(id, count)| DuplicateId {
+                file: self.file,
+                id: *id,
+                count: *count,
+            })
+    }
+
+    fn orphans(&self) -> impl Iterator<Item = Orphan> + '_ {
+        self.ids
+            .keys()
+            .filter(|id| !self.referenced.contains(id))
+            .map(|id| Orphan {
+                file: self.file,
+                id: *id,
+            })
+    }
+}
+
+/// Checks the references held by a single object.
+struct References<'a> {
+    report: &'a mut Report,
+    file: &'static str,
+    owner: i64,
+}
+
+impl References<'_> {
+    fn check(&mut self, target: &mut Target, field: &'static str, id: i64) {
+        if target.ids.is_empty() {
+            return;
+        }
+
+        target.referenced.insert(id);
+
+        if !target.ids.contains_key(&id) {
+            self.report.dangling.push(DanglingReference {
+                file: self.file,
+                owner: self.owner,
+                field,
+                target: target.file,
+                id,
+            });
+        }
+    }
+
+    fn check_all<I, T>(&mut self, target: &mut Target, field: &'static str, ids: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: AsId,
+    {
+        for id in ids {
+            self.check(target, field, id.as_id());
+        }
+    }
+}
+
+/// Converts the various integer types used for IDs in the model to a common type.
+trait AsId {
+    fn as_id(&self) -> i64;
+}
+
+macro_rules! as_id {
+    ($( $kind:ty ),+) => {
+        $(
+            impl AsId for $kind {
+                fn as_id(&self) -> i64 {
+                    *self as i64
+                }
+            }
+
+            impl AsId for &$kind {
+                fn as_id(&self) -> i64 {
+                    **self as i64
+                }
+            }
+        )+
+    };
+}
+
+as_id!(u8, u16, u32, isize);
@@ -0,0 +1,255 @@
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use console::Style;
+use merger::diagnostics::Diagnostics;
+use merger::localize::Localization;
+use merger::Processor;
+use mhdb_model::Database;
+use rslib::config::Config;
+use rslib::tools::hash_file;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use wax::Glob;
+
+/// The name of the build manifest, relative to the configured output directory.
+const MANIFEST: &str = "build-manifest.json";
+
+/// Records the hash of every input read by each processor as of the last successful build.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    /// processor name -> input path -> input
+    processors: BTreeMap<String, BTreeMap<String, Input>>,
+}
+
+/// An input file as of the last successful build. The size and modification time are only used to
+/// skip re-hashing files that haven't been touched; whether a processor needs to run again is
+/// decided by the hash alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Input {
+    /// The SHA-256 hash of the file's contents.
+    hash: String,
+    size: u64,
+    /// Nanoseconds since the Unix epoch.
+    modified: u128,
+}
+
+/// Returns true if both sets of inputs list the same files with the same contents.
+fn same_hashes(a: &BTreeMap<String, Input>, b: &BTreeMap<String, Input>) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|((a_key, a), (b_key, b))| a_key == b_key && a.hash == b.hash)
+}
+
+impl Manifest {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Could not parse {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Merges the output of every processor whose inputs changed since the last build (or every
+/// processor, if `all` is set), then updates the build manifest. The manifest is left alone if
+/// merging reported any errors (or any warnings, if `strict` is set).
+///
+/// A `merged/` directory can't mix string layouts, so changing the layout re-runs every processor.
+pub fn build(config: &Config, all: bool, strict: bool) -> anyhow::Result<()> {
+    let style = Style::new().bold().dim();
+    let manifest_path = config.io.output.join(MANIFEST);
+    let previous = Manifest::load(&manifest_path)?;
+
+    let merged_dir = config.io.output.join(Database::DIRECTORY);
+    let localization = Localization::new(&config.merger, None);
+    let all = all || Localization::current(&merged_dir)? != localization;
+
+    let mut hashes = Hasher {
+        root: &config.io.output,
+        previous: &previous,
+        cache: HashMap::new(),
+    };
+
+    let mut manifest = Manifest::default();
+    let mut dirty = BTreeSet::new();
+
+    for processor in processors() {
+        let name = name(processor);
+        let inputs = hashes.inputs(processor, &name)?;
+
+        let unchanged = previous
+            .processors
+            .get(&name)
+            .is_some_and(|v| same_hashes(v, &inputs));
+
+        if all || !unchanged {
+            dirty.insert(processor);
+        }
+
+        manifest.processors.insert(name, inputs);
+    }
+
+    // Keep going until nothing new is marked dirty, since dependencies can be chained.
+    loop {
+        let before = dirty.len();
+
+        for processor in processors() {
+            if processor.dependencies().iter().any(|v| dirty.contains(v)) {
+                dirty.insert(processor);
+            }
+        }
+
+        if dirty.len() == before {
+            break;
+        }
+    }
+
+    if dirty.is_empty() {
+        println!("{} Merged output is up to date.", style.apply_to("[build]"));
+        return Ok(());
+    }
+
+    let dirty: Vec<Processor> = dirty.into_iter().collect();
+    let names: Vec<String> = dirty.iter().map(|v| name(*v)).collect();
+
+    println!(
+        "{} Running processors: {}",
+        style.apply_to("[build]"),
+        names.join(", ")
+    );
+
+    let mut diagnostics = Diagnostics::new();
+    let db = merger::run(config, &dirty, &mut diagnostics)?;
+
+    let partial = dirty.len() < processors().count();
+    merger::localize::write(&db, merged_dir, localization, partial)?;
+    Database::write_schemas(config.io.output.join(Database::SCHEMA_DIRECTORY))?;
+
+    diagnostics.print_report();
+
+    if diagnostics.is_failure(strict) {
+        bail!("Merging finished with problems; see the report above");
+    }
+
+    manifest.save(&manifest_path)?;
+
+    Ok(())
+}
+
+/// Returns every processor that merges its own set of files. [`Processor::Weapons`] is skipped,
+/// since it only stands in for the individual weapon processors.
+fn processors() -> impl Iterator<Item = Processor> {
+    Processor::value_variants()
+        .iter()
+        .copied()
+        .filter(|v| *v != Processor::Weapons)
+}
+
+fn name(processor: Processor) -> String {
+    processor
+        .to_possible_value()
+        .expect("processors are never skipped")
+        .get_name()
+        .to_owned()
+}
+
+/// Hashes processor inputs, reusing hashes from the previous manifest for files whose size and
+/// modification time still match what it recorded.
+struct Hasher<'a> {
+    root: &'a Path,
+    previous: &'a Manifest,
+    cache: HashMap<PathBuf, Input>,
+}
+
+impl Hasher<'_> {
+    fn inputs(
+        &mut self,
+        processor: Processor,
+        name: &str,
+    ) -> anyhow::Result<BTreeMap<String, Input>> {
+        let mut inputs = BTreeMap::new();
+
+        for pattern in processor.inputs() {
+            for path in expand(self.root, &pattern)? {
+                let key = path
+                    .strip_prefix(self.root)
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+
+                let input = self.hash(&path, name, &key)?;
+                inputs.insert(key, input);
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    fn hash(&mut self, path: &Path, name: &str, key: &str) -> anyhow::Result<Input> {
+        if let Some(input) = self.cache.get(path) {
+            return Ok(input.clone());
+        }
+
+        let metadata = fs::metadata(path)?;
+        let size = metadata.len();
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH)?.as_nanos();
+
+        let previous = self.previous.processors.get(name).and_then(|v| v.get(key));
+
+        let hash = match previous {
+            Some(v) if v.size == size && v.modified == modified => v.hash.clone(),
+            _ => hash_file(path)?,
+        };
+
+        let input = Input {
+            hash,
+            size,
+            modified,
+        };
+
+        self.cache.insert(path.to_owned(), input.clone());
+
+        Ok(input)
+    }
+}
+
+/// Resolves a glob relative to `root`. Missing files are skipped, and will cause the processor to
+/// re-run once they exist.
+fn expand(root: &Path, pattern: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let path = root.join(pattern);
+
+    if path.is_file() {
+        return Ok(vec![path]);
+    }
+
+    let Ok(glob) = Glob::new(pattern) else {
+        return Ok(Vec::new());
+    };
+
+    let (base, glob) = glob.partition();
+    let base = root.join(base);
+
+    if !base.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = glob
+        .walk(base)
+        .map(|v| v.map(|v| v.into_path()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    paths.sort();
+
+    Ok(paths)
+}
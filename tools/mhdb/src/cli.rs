@@ -0,0 +1,88 @@
+use clap::{Parser, Subcommand};
+use extractor::plan::Format;
+use extractor::Options;
+use merger::Processor;
+use mhdb_model::Language;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    #[arg(short, long, global = true)]
+    pub config: Option<PathBuf>,
+
+    #[arg(long, global = true)]
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Extracts `user` and `msg` files from the game data. Equivalent to running the `extractor`.
+    Extract {
+        #[command(flatten)]
+        options: Options,
+    },
+
+    /// Prints what `extract` would do, along with any likely mistakes in the config file. Equivalent
+    /// to running `extractor plan`.
+    Plan {
+        #[command(flatten)]
+        options: Options,
+
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+
+    /// Lists RSZ fields that aren't in the schema baseline. Equivalent to running `extractor schema`.
+    Schema {
+        #[command(flatten)]
+        options: Options,
+
+        #[arg(long)]
+        update: bool,
+    },
+
+    /// Merges extracted files into the final output. Equivalent to running the `merger` with no
+    /// subcommand.
+    Merge {
+        #[arg(long, short)]
+        filter: Vec<Processor>,
+
+        /// Flattens localized fields to plain strings in a single language (e.g. `en`), overriding
+        /// the `[merger]` section of the config file.
+        #[arg(long)]
+        language: Option<Language>,
+
+        /// Exits with an error if any warnings were reported while merging.
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Searches extracted files for a value. Equivalent to running the `finder`.
+    Find {
+        #[command(subcommand)]
+        command: finder::Command,
+
+        #[command(flatten)]
+        options: finder::Options,
+    },
+
+    /// Extracts the game data, then re-runs only the processors whose inputs changed since the last
+    /// build.
+    Build {
+        #[command(flatten)]
+        options: Options,
+
+        /// Re-runs every processor, even if its inputs haven't changed.
+        #[arg(long)]
+        all: bool,
+
+        /// Exits with an error if any warnings were reported while merging. The build manifest
+        /// isn't updated after a failed build, so the same processors will run again next time.
+        #[arg(long)]
+        strict: bool,
+    },
+}
@@ -0,0 +1,68 @@
+use crate::cli::{Cli, Command};
+use anyhow::bail;
+use clap::Parser;
+use merger::diagnostics::Diagnostics;
+use merger::localize::Localization;
+use merger::Processor;
+use mhdb_model::{Database, Language};
+use rslib::config::Config;
+
+mod build;
+mod cli;
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    if let Some(cwd) = &cli.cwd {
+        std::env::set_current_dir(cwd).expect("--cwd option specified an invalid path");
+    }
+
+    let config = Config::load(cli.config.as_deref());
+
+    match cli.command {
+        Command::Extract { options } => extractor::run(&config, &options),
+        Command::Plan { options, format } => extractor::plan::run(&config, &options, format),
+        Command::Schema { options, update } => extractor::schema::run(&config, &options, update),
+        Command::Merge {
+            filter,
+            language,
+            strict,
+        } => merge(&config, &filter, language, strict),
+        Command::Find { command, options } => finder::run(config, command, options),
+        Command::Build {
+            options,
+            all,
+            strict,
+        } => {
+            extractor::run(&config, &options)?;
+            build::build(&config, all, strict)
+        }
+    }
+}
+
+/// Runs the processors allowed by `filters`, then writes the merged output and its schemas.
+fn merge(
+    config: &Config,
+    filters: &[Processor],
+    language: Option<Language>,
+    strict: bool,
+) -> anyhow::Result<()> {
+    let mut diagnostics = Diagnostics::new();
+    let db = merger::run(config, filters, &mut diagnostics)?;
+
+    merger::localize::write(
+        &db,
+        config.io.output.join(Database::DIRECTORY),
+        Localization::new(&config.merger, language),
+        !filters.is_empty(),
+    )?;
+    Database::write_schemas(config.io.output.join(Database::SCHEMA_DIRECTORY))?;
+
+    diagnostics.print_report();
+
+    if diagnostics.is_failure(strict) {
+        bail!("Merging finished with problems; see the report above");
+    }
+
+    Ok(())
+}
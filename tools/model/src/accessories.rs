@@ -0,0 +1,40 @@
+use crate::serde::ordered_map;
+use crate::{IconColor, IdMap, LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Accessory {
+    pub game_id: isize,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    pub rarity: u8,
+    pub price: u16,
+    pub level: u8,
+    #[serde(serialize_with = "ordered_map")]
+    pub skills: IdMap,
+    pub allowed_on: AllowedOn,
+    pub icon_color: IconColor,
+    pub icon_color_id: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AllowedOn {
+    Armor,
+    Weapon,
+}
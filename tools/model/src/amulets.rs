@@ -0,0 +1,43 @@
+use crate::serde::ordered_map;
+use crate::{IdMap, LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Amulet {
+    pub game_id: isize,
+    pub is_random: bool,
+    pub ranks: Vec<Rank>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Rank {
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    pub rarity: u8,
+    pub level: u8,
+    pub price: usize,
+    #[serde(serialize_with = "ordered_map")]
+    pub skills: IdMap,
+    pub recipe: Recipe,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+pub struct Recipe {
+    #[serde(serialize_with = "ordered_map")]
+    pub inputs: IdMap,
+}
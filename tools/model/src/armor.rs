@@ -0,0 +1,119 @@
+use crate::serde::ordered_map;
+use crate::{IdMap, LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Set {
+    pub game_id: isize,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    pub rarity: u8,
+    #[deprecated(
+        since = "1.41.2-wilds",
+        note = "Will be replaced by `set_bonus_id` on or after 2026-05-01"
+    )]
+    pub set_bonus: Option<Bonus>,
+    pub set_bonus_id: Option<isize>,
+    #[deprecated(
+        since = "1.41.2-wilds",
+        note = "Will be replaced by `group_bonus_id` on or after 2026-05-01"
+    )]
+    pub group_bonus: Option<Bonus>,
+    pub group_bonus_id: Option<isize>,
+    pub pieces: Vec<Armor>,
+    pub model_id: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct Bonus {
+    pub skill_id: isize,
+    pub ranks: Vec<BonusRank>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
+pub struct BonusRank {
+    pub pieces: u8,
+    pub skill_level: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Armor {
+    pub kind: PartKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    pub defense: Defense,
+    pub resistances: Resistances,
+    pub slots: Vec<u8>,
+    #[serde(serialize_with = "ordered_map")]
+    pub skills: IdMap,
+    pub crafting: Crafting,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Defense {
+    pub base: u16,
+    pub max: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Resistances {
+    pub fire: i8,
+    pub water: i8,
+    pub thunder: i8,
+    pub ice: i8,
+    pub dragon: i8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Default, Deserialize, JsonSchema)]
+pub struct Crafting {
+    pub price: usize,
+    #[serde(serialize_with = "ordered_map")]
+    pub inputs: IdMap,
+}
+
+#[derive(
+    Debug, Deserialize, Serialize, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum PartKind {
+    Head,
+    Chest,
+    Arms,
+    Waist,
+    Legs,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Upgrade {
+    pub rarity: u8,
+    pub steps: Vec<UpgradeStep>,
+}
+
+impl Upgrade {
+    pub fn get_total_defense_bonus(&self) -> u16 {
+        self.steps.iter().fold(0, |sum, v| sum + v.extra_defense)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UpgradeStep {
+    pub level: u8,
+    pub extra_defense: u16,
+    pub point_cost: usize,
+    pub zenny_cost: usize,
+}
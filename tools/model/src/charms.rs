@@ -0,0 +1,11 @@
+use crate::serde::ordered_map;
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Charm {
+    pub game_id: isize,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
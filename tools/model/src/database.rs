@@ -0,0 +1,223 @@
+use crate::accessories::Accessory;
+use crate::amulets::Amulet;
+use crate::armor::{Set, Upgrade};
+use crate::charms::Charm;
+use crate::items::Item;
+use crate::locations::Stage;
+use crate::monsters::large::parts::PartName;
+use crate::monsters::large::LargeMonster;
+use crate::monsters::Species;
+use crate::skills::Skill;
+use crate::weapons::hunting_horn::{EchoBubble, EchoWave, Melody, Song};
+use crate::weapons::series::Series;
+use crate::weapons::Weapon;
+use schemars::{schema_for, Schema};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("parse: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+macro_rules! database {
+    (
+        $( $field:ident: $kind:ty => $const:ident = $path:literal ),+ $(,)?
+    ) => {
+        /// An in-memory copy of the merged data set. Each field holds the contents of one of the
+        /// files in the `merged/` output directory.
+        #[derive(Debug, Default, Clone, PartialEq)]
+        pub struct Database {
+            $( pub $field: Vec<$kind>, )+
+        }
+
+        impl Database {
+            $( pub const $const: &str = $path; )+
+
+            /// The path of every file making up the database, relative to the `merged/` directory.
+            pub const FILES: &[&str] = &[$( Self::$const ),+];
+
+            /// Loads a database from a `merged/` directory. Missing files are treated as empty.
+            pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+                let dir = dir.as_ref();
+
+                Ok(Self {
+                    $( $field: read_section(&dir.join(Self::$const))?, )+
+                })
+            }
+
+            /// Writes the database to a `merged/` directory. Empty sections are skipped, so that
+            /// a partial run doesn't clobber files produced by an earlier one.
+            pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result {
+                let dir = dir.as_ref();
+                $( write_section(&self.$field, &dir.join(Self::$const))?; )+
+
+                Ok(())
+            }
+
+            /// Serializes every non-empty section, paired with the path of the file it's written to
+            /// by [`Database::write()`].
+            pub fn sections(&self) -> Result<Vec<(&'static str, serde_json::Value)>> {
+                let mut sections = Vec::new();
+
+                $(
+                    if !self.$field.is_empty() {
+                        sections.push((Self::$const, serde_json::to_value(&self.$field)?));
+                    }
+                )+
+
+                Ok(sections)
+            }
+
+            /// Generates a JSON Schema for every file in the database, paired with the path of the
+            /// file it describes.
+            pub fn schemas() -> Vec<(&'static str, Schema)> {
+                vec![$( (Self::$const, schema_for!(Vec<$kind>)) ),+]
+            }
+        }
+    };
+}
+
+database! {
+    accessories: Accessory => ACCESSORIES = "Accessory.json",
+    amulets: Amulet => AMULETS = "Amulet.json",
+    armor: Set => ARMOR = "Armor.json",
+    armor_upgrades: Upgrade => ARMOR_UPGRADES = "ArmorUpgrade.json",
+    charms: Charm => CHARMS = "Charm.json",
+    items: Item => ITEMS = "Item.json",
+    large_monsters: LargeMonster => LARGE_MONSTERS = "LargeMonsters.json",
+    part_names: PartName => PART_NAMES = "PartNames.json",
+    skills: Skill => SKILLS = "Skill.json",
+    species: Species => SPECIES = "Species.json",
+    stages: Stage => STAGES = "Stage.json",
+    weapon_series: Series => WEAPON_SERIES = "WeaponSeries.json",
+    bows: Weapon => BOWS = "weapons/Bow.json",
+    charge_blades: Weapon => CHARGE_BLADES = "weapons/ChargeBlade.json",
+    dual_blades: Weapon => DUAL_BLADES = "weapons/DualBlades.json",
+    great_swords: Weapon => GREAT_SWORDS = "weapons/GreatSword.json",
+    gunlances: Weapon => GUNLANCES = "weapons/Gunlance.json",
+    hammers: Weapon => HAMMERS = "weapons/Hammer.json",
+    heavy_bowguns: Weapon => HEAVY_BOWGUNS = "weapons/HeavyBowgun.json",
+    hunting_horns: Weapon => HUNTING_HORNS = "weapons/HuntingHorn.json",
+    insect_glaives: Weapon => INSECT_GLAIVES = "weapons/InsectGlaive.json",
+    lances: Weapon => LANCES = "weapons/Lance.json",
+    light_bowguns: Weapon => LIGHT_BOWGUNS = "weapons/LightBowgun.json",
+    long_swords: Weapon => LONG_SWORDS = "weapons/LongSword.json",
+    switch_axes: Weapon => SWITCH_AXES = "weapons/SwitchAxe.json",
+    sword_shields: Weapon => SWORD_SHIELDS = "weapons/SwordShield.json",
+    echo_waves: EchoWave => ECHO_WAVES = "weapons/HuntingHornEchoWaves.json",
+    echo_bubbles: EchoBubble => ECHO_BUBBLES = "weapons/HuntingHornEchoBubbles.json",
+    songs: Song => SONGS = "weapons/HuntingHornSongs.json",
+    melodies: Melody => MELODIES = "weapons/HuntingHornMelodies.json",
+}
+
+impl Database {
+    /// The name of the directory holding the database, relative to the configured output
+    /// directory.
+    pub const DIRECTORY: &str = "merged";
+
+    /// The name of the directory holding the generated JSON Schema files, relative to the
+    /// configured output directory.
+    pub const SCHEMA_DIRECTORY: &str = "schemas";
+
+    /// Writes a `.schema.json` file for every file in the database to `dir`.
+    pub fn write_schemas<P: AsRef<Path>>(dir: P) -> Result {
+        let dir = dir.as_ref();
+
+        for (file, schema) in Self::schemas() {
+            write_file(&dir.join(schema_path(file)), &schema)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compares freshly generated schemas against the ones in `dir`, returning the path of every
+    /// schema file that is missing or out of date.
+    pub fn check_schemas<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let mut stale = Vec::new();
+
+        for (file, schema) in Self::schemas() {
+            let path = dir.join(schema_path(file));
+
+            let current = match fs::read_to_string(&path) {
+                Ok(contents) => Some(contents),
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+                Err(error) => return Err(error.into()),
+            };
+
+            if current.as_deref() != Some(serde_json::to_string_pretty(&schema)?.as_str()) {
+                stale.push(path);
+            }
+        }
+
+        Ok(stale)
+    }
+
+    /// Returns every weapon in the database, regardless of its kind.
+    pub fn weapons(&self) -> impl Iterator<Item = &Weapon> {
+        [
+            &self.bows,
+            &self.charge_blades,
+            &self.dual_blades,
+            &self.great_swords,
+            &self.gunlances,
+            &self.hammers,
+            &self.heavy_bowguns,
+            &self.hunting_horns,
+            &self.insect_glaives,
+            &self.lances,
+            &self.light_bowguns,
+            &self.long_swords,
+            &self.switch_axes,
+            &self.sword_shields,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+fn read_section<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(file)?)
+}
+
+fn write_section<T: Serialize>(values: &[T], path: &Path) -> Result {
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    write_file(path, values)
+}
+
+fn write_file<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result {
+    if let Some(parent) = path.parent()
+        && !parent.exists()
+    {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// Converts the path of a database file to the path of its schema, e.g. `weapons/Bow.json` to
+/// `weapons/Bow.schema.json`.
+fn schema_path(file: &str) -> String {
+    let stem = file.strip_suffix(".json").unwrap_or(file);
+    format!("{stem}.schema.json")
+}
@@ -0,0 +1,146 @@
+use crate::serde::ordered_map;
+use crate::{IconColor, LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub type ItemId = isize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Item {
+    pub game_id: ItemId,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    pub kind: ItemKind,
+    pub rarity: u8,
+    pub max_count: u8,
+    pub sell_price: usize,
+    pub buy_price: usize,
+    pub recipes: Vec<Recipe>,
+    pub out_box: bool,
+    pub icon: IconKind,
+    pub icon_id: u8,
+    pub icon_color: IconColor,
+    pub icon_color_id: u8,
+    pub foundry: Option<Foundry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Recipe {
+    pub amount: u8,
+    pub inputs: Vec<ItemId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ItemKind {
+    Consumable,
+    Tool,
+    Material,
+    BowgunAmmo,
+    BowCoating,
+    Point,
+    Mystery,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconKind {
+    MysteryArtian,
+    MysteryMaterial,
+    Question,
+    Mushroom,
+    Egg,
+    Honey,
+    Plant,
+    Potion,
+    Powder,
+    Whetstone,
+    Pill,
+    Fish,
+    Meat,
+    Barrel,
+    Bomb,
+    TrapTool,
+    Trap,
+    Gem,
+    Smoke,
+    FishingRod,
+    Binoculars,
+    Knife,
+    Grill,
+    Voucher,
+    Certificate,
+    Coin,
+    Nut,
+    AmmoBasic,
+    Phial,
+    Web,
+    Seed,
+    Ore,
+    Bug,
+    Poop,
+    Medulla,
+    Bone,
+    Scale,
+    Hide,
+    Claw,
+    Shell,
+    Tail,
+    Wing,
+    Skull,
+    Plate,
+    Crystal,
+    ArmorSphere,
+    MysteryDecoration,
+    CampingKit,
+    SlingerAmmo,
+    CaptureNet,
+    AmmoSlug,
+    AmmoSpecial,
+    AmmoUtility,
+    AmmoHeavy,
+    Curative,
+    Drug,
+    Extract,
+    Mantle,
+    CookingCheese,
+    CookingMushroom,
+    CookingShellfish,
+    CookingGarlic,
+    CookingEgg,
+    Sprout,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Foundry {
+    Material(FoundryMaterial),
+    Output(FoundryOutput),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FoundryMaterial {
+    pub armor_sphere_value: u16,
+    pub ore_value: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FoundryOutput {
+    pub cost: u16,
+}
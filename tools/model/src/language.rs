@@ -0,0 +1,102 @@
+use schemars::JsonSchema;
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// A map of RFC 639 language codes to a string value. Used to hold translations for an object
+/// field.
+pub type LanguageMap = HashMap<Language, String>;
+
+/// Language list from https://github.com/dtlnor/RE_MSG/blob/main/LanguagesEnum.md
+#[derive(
+    Debug, PartialEq, Eq, Deserialize, Copy, Clone, Serialize, Hash, Ord, PartialOrd, JsonSchema,
+)]
+pub enum Language {
+    #[serde(rename = "")]
+    Disabled,
+    #[serde(rename = "ja")]
+    Japanese,
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "it")]
+    Italian,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "ru")]
+    Russian,
+    #[serde(rename = "pl")]
+    Polish,
+    #[serde(rename = "nl")]
+    Dutch,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "pt-BR")]
+    BrazilianPortuguese,
+    #[serde(rename = "ko")]
+    Korean,
+    #[serde(rename = "zh-Hant")]
+    TraditionalChinese,
+    #[serde(rename = "zh-Hans")]
+    SimplifiedChinese,
+    #[serde(rename = "fi")]
+    Finnish,
+    #[serde(rename = "sv")]
+    Swedish,
+    #[serde(rename = "da")]
+    Danish,
+    #[serde(rename = "no")]
+    Norwegian,
+    #[serde(rename = "cs")]
+    Czech,
+    #[serde(rename = "hu")]
+    Hungarian,
+    #[serde(rename = "sk")]
+    Slovak,
+    #[serde(rename = "ar")]
+    Arabic,
+    #[serde(rename = "tr")]
+    Turkish,
+    #[serde(rename = "bg")]
+    Bulgarian,
+    #[serde(rename = "el")]
+    Greek,
+    #[serde(rename = "ro")]
+    Romanian,
+    #[serde(rename = "th")]
+    Thai,
+    #[serde(rename = "uk")]
+    Ukrainian,
+    #[serde(rename = "vi")]
+    Vietnamese,
+    #[serde(rename = "id")]
+    Indonesian,
+    #[serde(skip_deserializing, rename = "")]
+    Fiction,
+    #[serde(rename = "hi")]
+    Hindi,
+    #[serde(rename = "es-419")]
+    LatinAmericanSpanish,
+}
+
+impl FromStr for Language {
+    type Err = UnknownLanguage;
+
+    /// Parses a language code, such as `en` or `pt-BR`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(UnknownLanguage(s.to_owned()));
+        }
+
+        Self::deserialize(s.into_deserializer())
+            .map_err(|_: serde::de::value::Error| UnknownLanguage(s.to_owned()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unknown language {0:?}")]
+pub struct UnknownLanguage(pub String);
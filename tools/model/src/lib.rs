@@ -0,0 +1,78 @@
+use ::serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::HashMap;
+
+pub mod accessories;
+pub mod amulets;
+pub mod armor;
+pub mod charms;
+pub mod items;
+pub mod locations;
+pub mod monsters;
+pub mod skills;
+pub mod weapons;
+
+mod database;
+pub use database::*;
+
+mod language;
+pub use language::*;
+
+mod serde;
+
+/// A map of object IDs to a level or quantity indicator. Used for things like skill ranks granted
+/// by decorations, or inputs in recipes.
+pub type IdMap = HashMap<isize, u8>;
+
+/// A map of language codes to a string that has been split into [`Span`]s.
+pub type RichTextMap = HashMap<Language, Vec<Span>>;
+
+/// A map of language codes to every form of a string that contains `<LSNR>` tags, one for each
+/// option the tags offer.
+pub type VariantMap = HashMap<Language, Vec<String>>;
+
+/// A run of text that's shown with the same formatting in-game. Joining the text of every span
+/// gives the plain string.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Eq, PartialEq, JsonSchema)]
+pub struct Span {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "serde::is_default")]
+    pub bold: bool,
+    /// The color from the game's `<COLOR>` tag, as written in the string (e.g. `FF0000`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum HunterRank {
+    Low,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconColor {
+    None,
+    White,
+    Gray,
+    Rose,
+    Pink,
+    Red,
+    Vermilion,
+    Orange,
+    Brown,
+    Ivory,
+    Yellow,
+    Lemon,
+    SageGreen,
+    MossGreen,
+    Green,
+    Emerald,
+    Sky,
+    Blue,
+    Ultramarine,
+    BluePurple,
+    Purple,
+    DarkPurple,
+}
@@ -0,0 +1,43 @@
+use crate::serde::ordered_map;
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub type StageId = isize;
+pub type GimmickId = isize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Stage {
+    pub game_id: StageId,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    pub areas: u16,
+    pub camps: Vec<Camp>,
+    pub bitmask_value: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Camp {
+    pub game_id: GimmickId,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    pub area: u16,
+    pub floor: u16,
+    pub risk: Risk,
+    pub position: Position,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Risk {
+    Dangerous,
+    Insecure,
+    Safe,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, JsonSchema)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
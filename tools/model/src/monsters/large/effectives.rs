@@ -0,0 +1,87 @@
+use crate::serde::optional_ordered_map;
+use crate::weapons::{Element, Status};
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Weakness {
+    #[serde(flatten)]
+    pub kind: SpecialKind,
+    pub level: u8,
+    #[serde(serialize_with = "optional_ordered_map")]
+    pub condition: Option<LanguageMap>,
+}
+
+impl Weakness {
+    pub fn element(element: Element) -> Self {
+        Self {
+            kind: SpecialKind::Element(element),
+            level: 1,
+            condition: None,
+        }
+    }
+
+    pub fn status(status: Status, level: u8) -> Self {
+        Self {
+            level,
+            kind: SpecialKind::Status(status),
+            condition: None,
+        }
+    }
+
+    pub fn effect(effect: Effect) -> Self {
+        Self {
+            kind: SpecialKind::Effect(effect),
+            level: 1,
+            condition: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Resistance {
+    #[serde(flatten)]
+    pub kind: SpecialKind,
+}
+
+impl Resistance {
+    pub fn status(status: Status) -> Self {
+        Self {
+            kind: SpecialKind::Status(status),
+        }
+    }
+
+    pub fn effect(effect: Effect) -> Self {
+        Self {
+            kind: SpecialKind::Effect(effect),
+        }
+    }
+
+    pub fn element(element: Element) -> Self {
+        Self {
+            kind: SpecialKind::Element(element),
+        }
+    }
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, JsonSchema,
+)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SpecialKind {
+    Element(Element),
+    Status(Status),
+    Effect(Effect),
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, JsonSchema,
+)]
+#[serde(tag = "effect", rename_all = "lowercase")]
+pub enum Effect {
+    Noise,
+    Flash,
+    Stun,
+    Exhaust,
+}
@@ -0,0 +1,98 @@
+use crate::locations::StageId;
+use crate::monsters::large::effectives::{Resistance, SpecialKind, Weakness};
+use crate::monsters::large::parts::Part;
+use crate::monsters::large::rewards::Reward;
+use crate::monsters::large::size::Size;
+use crate::monsters::{MonsterId, SpeciesKind};
+use crate::serde::ordered_map;
+use crate::{LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+pub mod effectives;
+pub mod parts;
+pub mod rewards;
+pub mod size;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LargeMonster {
+    pub game_id: MonsterId,
+    pub species: SpeciesKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub features: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_features: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub features_variants: VariantMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub tips: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_tips: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub tips_variants: VariantMap,
+    pub variants: Vec<Variant>,
+    pub size: Size,
+    pub base_health: u16,
+    pub locations: Vec<StageId>,
+    pub weaknesses: Vec<Weakness>,
+    pub resistances: Vec<Resistance>,
+    pub rewards: Vec<Reward>,
+    pub parts: Vec<Part>,
+}
+
+impl LargeMonster {
+    pub fn find_weakness_mut(&mut self, kind: SpecialKind) -> Option<&mut Weakness> {
+        self.weaknesses.iter_mut().find(|v| v.kind == kind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Variant {
+    pub kind: VariantKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Ord, PartialOrd, Eq, PartialEq, EnumIter, Copy, Clone, JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum VariantKind {
+    Alpha,
+    Tempered,
+    ArchTempered,
+    Frenzied,
+}
@@ -0,0 +1,180 @@
+use crate::serde::ordered_map;
+use crate::weapons::insect_glaive::KinsectEssenceKind;
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use strum::IntoStaticStr;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Part {
+    #[serde(flatten)]
+    pub kind: PartKind,
+    pub base_health: Option<u16>,
+    pub kinsect_essence: KinsectEssenceKind,
+    pub multipliers: Multipliers,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+pub struct Multipliers {
+    pub slash: f32,
+    pub blunt: f32,
+    pub pierce: f32,
+    pub fire: f32,
+    pub water: f32,
+    pub thunder: f32,
+    pub ice: f32,
+    pub dragon: f32,
+    pub stun: f32,
+}
+
+/// Parts are ordered by their in-game identifier rather than by name, which is why the original
+/// discriminants are kept here even though the enum is serialized by name.
+///
+/// [`IntoStaticStr`] gives the same name the part serializes to, for places that need it as a plain
+/// string.
+#[derive(
+    Debug,
+    Deserialize,
+    Serialize,
+    Copy,
+    Clone,
+    Hash,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    JsonSchema,
+    IntoStaticStr,
+)]
+#[serde(rename_all = "kebab-case", tag = "part")]
+#[strum(serialize_all = "kebab-case")]
+#[repr(isize)]
+pub enum PartKind {
+    // region Variants
+    StupidBarrelPuncher = -1,
+    Invalid = 486590176,
+    FullBody = 1733044864,
+    Head = -212024896,
+    UpperBody = -1382295680,
+    Body = -2054210560,
+    Tail = 2000370944,
+    TailTip = 1886418560,
+    Neck = -1466497792,
+    Torso = 1210068992,
+    Stomach = 1603494400,
+    Back = 18080514,
+    FrontLegs = 1777993216,
+    LeftFrontLeg = -891913216,
+    RightFrontLeg = 1920497920,
+    HindLegs = 1429619328,
+    LeftHindLeg = 304214656,
+    RightHindLeg = 591465408,
+    LeftLeg = 731472640,
+    RightLeg = -142058256,
+    LeftLegFrontAndRear = 102373496,
+    RightLegFrontAndRear = -5591398,
+    LeftWing = -240678336,
+    RightWing = 665420480,
+    Ass = -941150464,
+    Nail = -226704768,
+    LeftNail = 1750977664,
+    RightNail = 63041352,
+    Tongue = -526417856,
+    Petal = 1000875456,
+    Veil = -279541920,
+    Saw = 655333504,
+    Feather = -1137775744,
+    Tentacle = 499612832,
+    Umbrella = -1564619520,
+    LeftFrontArm = 1177888256,
+    RightFrontArm = -1885998720,
+    LeftSideArm = -1584832512,
+    RightSideArm = 1154422144,
+    LeftHindArm = -1605643392,
+    RightHindArm = 1925104512,
+    Head2 = 517550944,
+    Chest = -1314889600,
+    Mantle = 509608864,
+    MantleUnder = 789930048,
+    PoisonousThorn = -1222144512,
+    Antennae = -945112512,
+    LeftWingLegs = -1235127936,
+    RightWingLegs = 702074176,
+    WaterfilmRightHead = -101670456,
+    WaterfilmLeftHead = 1730846080,
+    WaterfilmRightBody = 1917146240,
+    WaterfilmLeftBody = -727805760,
+    WaterfilmRightFrontLeg = -15677196,
+    WaterfilmLeftFrontLeg = -445884256,
+    WaterfilmTail = -1410796160,
+    WaterfilmLeftTail = 1725614208,
+    Mouth = -1110329472,
+    Trunk = 1481421312,
+    LeftWingBlade = 767347712,
+    RightWingBlade = -1392586368,
+    FrozenCoreHead = 1395139584,
+    FrozenCoreTail = -912870400,
+    FrozenCoreWaist = 876321664,
+    FrozenBigcoreBefore = 1063213696,
+    FrozenBigcoreAfter = -1328528384,
+    Nose = -643264000,
+    HeadWear = 6538,
+    HeadHide = 30311,
+    WingArm = 10580,
+    WingArmWear = 23560,
+    LeftWingArmWear = 2383,
+    RightWingArmWear = 2323,
+    LeftWingArm = 22650,
+    RightWingArm = 30763,
+    LeftWingArmHide = 10831,
+    RightWingArmHide = 21608,
+    Chelicerae = 15433,
+    BothWings = 30838,
+    BothWingsBlade = 24658,
+    BothLeg = 15859,
+    Arm = 12265,
+    Leg = 23097,
+    Hide = 28141,
+    SharpCorners = 10456,
+    NeedleHair = 23256,
+    ParalysisCorners = 31285,
+    HeadOil = 8217,
+    UmbrellaOil = 1199,
+    TorsoOil = 19946,
+    ArmOil = 10275,
+    WaterfilmRightTail = 31953,
+    TailHair = 2015,
+    StomachSecond = 10869,
+    HeadSecond = 20534,
+    PoisonousThornSecond = 5823,
+    TailThird = 11977,
+    TailFifth = 9871,
+    DorsalFin = 1809,
+    HeadFirst = 26403,
+    Corner = 11138,
+    Fang = 25689,
+    FangFirst = 6609,
+    FangSecond = 29797,
+    LeftFrontLegarmor = 27651,
+    RightFrontLegarmor = 8246,
+    HeadArmor = 17094,
+    LeftWingArmArmor = 24769,
+    RightWingArmArmor = 15310,
+    Periscope = 20830,
+    Equipment = 6654,
+    EquipmentHead = 11039,
+    EquipmentChest = 7334,
+    EquipmentBack = 16835,
+    EquipmentLeft = 21841,
+    EquipmentRight = 15641,
+    EquipmentTail = 26497,
+    // endregion
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PartName {
+    #[serde(flatten)]
+    pub kind: PartKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
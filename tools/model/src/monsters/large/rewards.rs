@@ -0,0 +1,32 @@
+use crate::items::ItemId;
+use crate::monsters::large::parts::PartKind;
+use crate::HunterRank;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Reward {
+    pub rank: HunterRank,
+    #[serde(flatten)]
+    pub source: RewardSource,
+    pub item_id: ItemId,
+    pub amount: u8,
+    pub chance: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum RewardSource {
+    Carve,
+    CarveSevered,
+    EndemicCapture,
+    TargetReward,
+    BrokenPart(PartKind),
+    WoundDestroyed,
+    CarveRotten,
+    SlingerGather,
+    CarveRottenSevered,
+    TemperedWoundDestroyed,
+    CarveCrystallized,
+    BrokenFragment,
+}
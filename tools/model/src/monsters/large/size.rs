@@ -0,0 +1,13 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+pub struct Size {
+    pub base: f32,
+    pub mini: f32,
+    pub mini_multiplier: f32,
+    pub silver: f32,
+    pub silver_multiplier: f32,
+    pub gold: f32,
+    pub gold_multiplier: f32,
+}
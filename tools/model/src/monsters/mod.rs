@@ -0,0 +1,44 @@
+use crate::serde::ordered_map;
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub mod large;
+
+pub type MonsterId = isize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Species {
+    pub kind: SpeciesKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
+
+#[derive(
+    Debug, Deserialize, Serialize, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, JsonSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpeciesKind {
+    None,
+    FlyingWyvern,
+    Fish,
+    Herbivore,
+    Lynian,
+    Neopteron,
+    Carapaceon,
+    FangedBeast,
+    BirdWyvern,
+    PiscineWyvern,
+    Leviathan,
+    BruteWyvern,
+    FangedWyvern,
+    Amphibian,
+    Temnoceran,
+    SnakeWyvern,
+    ElderDragon,
+    Cephalopod,
+    Construct,
+    Wingdrake,
+    DemiElder,
+    Machine,
+}
@@ -0,0 +1,102 @@
+use crate::serde::{is_default, ordered_map};
+use crate::{LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Skill {
+    pub game_id: isize,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "LanguageMap::is_empty"
+    )]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    pub ranks: Vec<Rank>,
+    pub kind: SkillKind,
+    pub icon: IconKind,
+    pub icon_id: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Rank {
+    pub level: u8,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    #[serde(
+        default,
+        skip_serializing_if = "LanguageMap::is_empty",
+        serialize_with = "ordered_map"
+    )]
+    pub names: LanguageMap,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub set_pieces_required: u8,
+    /// The modifier values from the game files, which are used to fill in numeric inserts (such as
+    /// `{0}`) in `descriptions`. What each value means depends on the skill.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<i32>,
+    /// The IDs of the skills that this rank enables. Usually just the rank's own skill.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub open_skill_ids: Vec<isize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkillKind {
+    Armor,
+    Set,
+    Group,
+    Weapon,
+}
+
+impl SkillKind {
+    pub fn is_armor_bonus(&self) -> bool {
+        matches!(self, Self::Set | Self::Group)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconKind {
+    None,
+    Attack,
+    Affinity,
+    Element,
+    Handicraft,
+    Ranged,
+    Defense,
+    Health,
+    Stamina,
+    Offense,
+    Utility,
+    Item,
+    Gathering,
+    Group,
+    Set,
+}
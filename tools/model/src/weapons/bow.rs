@@ -0,0 +1,20 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Bow {
+    pub coatings: Vec<Coating>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum Coating {
+    CloseRange,
+    Power,
+    Pierce,
+    Paralysis,
+    Poison,
+    Sleep,
+    Blast,
+    Exhaust,
+}
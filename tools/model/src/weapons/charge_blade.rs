@@ -0,0 +1,17 @@
+use crate::weapons::Sharpness;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ChargeBlade {
+    pub phial: PhialKind,
+    pub sharpness: Sharpness,
+    pub handicraft: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PhialKind {
+    Impact,
+    Element,
+}
@@ -0,0 +1,19 @@
+use crate::weapons::Sharpness;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Gunlance {
+    pub shell: ShellKind,
+    pub shell_level: u8,
+    pub sharpness: Sharpness,
+    pub handicraft: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ShellKind {
+    Normal,
+    Wide,
+    Long,
+}
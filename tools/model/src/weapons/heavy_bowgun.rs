@@ -0,0 +1,41 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HeavyBowgun {
+    pub ammo: Vec<Ammo>,
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Hash, Ord, PartialOrd, Eq, PartialEq, Copy, Clone, JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AmmoKind {
+    Normal,
+    Pierce,
+    Spread,
+    Slicing,
+    Sticky,
+    Cluster,
+    Wyvern,
+    Poison,
+    Paralysis,
+    Sleep,
+    Flaming,
+    Water,
+    Freeze,
+    Thunder,
+    Dragon,
+    Recover,
+    Demon,
+    Armor,
+    Exhaust,
+    Tranq,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Ammo {
+    pub kind: AmmoKind,
+    pub level: u8,
+    pub capacity: u8,
+}
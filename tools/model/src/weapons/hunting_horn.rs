@@ -0,0 +1,99 @@
+use crate::serde::ordered_map;
+use crate::weapons::Sharpness;
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub type EchoWaveId = u8;
+pub type EchoBubbleId = u8;
+pub type MelodyId = u8;
+pub type SongEffectId = u16;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HuntingHorn {
+    pub sharpness: Sharpness,
+    pub handicraft: Vec<u8>,
+    pub melody_id: MelodyId,
+    pub echo_wave_id: Option<EchoWaveId>,
+    pub echo_bubble_id: Option<EchoBubbleId>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Hash, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Note {
+    None,
+    Purple,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Aqua,
+    White,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Melody {
+    pub game_id: MelodyId,
+    pub notes: [Note; 3],
+    pub songs: Vec<SongEffectId>,
+}
+
+impl Melody {
+    pub fn can_play(&self, song: &Song) -> bool {
+        song.notes.iter().all(|note| self.notes.contains(note))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Song {
+    pub effect_id: SongEffectId,
+    pub notes: Vec<Note>,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EchoWave {
+    pub game_id: EchoWaveId,
+    pub kind: EchoWaveKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EchoWaveKind {
+    None,
+    Blunt,
+    Slash,
+    Fire,
+    Water,
+    Thunder,
+    Ice,
+    Dragon,
+    Poison,
+    Paralyze,
+    Sleep,
+    Blast,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EchoBubble {
+    pub game_id: EchoBubbleId,
+    pub kind: EchoBubbleKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EchoBubbleKind {
+    None,
+    Evasion,
+    Regen,
+    Stamina,
+    Damage,
+    Defense,
+    Immunity,
+}
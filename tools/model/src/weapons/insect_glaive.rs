@@ -0,0 +1,28 @@
+use crate::weapons::Sharpness;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct InsectGlaive {
+    pub sharpness: Sharpness,
+    pub handicraft: Vec<u8>,
+    pub kinsect_level: u8,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum KinsectEssenceKind {
+    Red,
+    Orange,
+    White,
+    Green,
+    #[serde(serialize_with = "serialize_as_null", untagged)]
+    None,
+}
+
+fn serialize_as_null<S>(serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_none()
+}
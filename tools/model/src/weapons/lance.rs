@@ -0,0 +1,9 @@
+use crate::weapons::Sharpness;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Lance {
+    pub sharpness: Sharpness,
+    pub handicraft: Vec<u8>,
+}
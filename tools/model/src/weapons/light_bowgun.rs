@@ -0,0 +1,35 @@
+use crate::weapons::heavy_bowgun::{self, AmmoKind};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LightBowgun {
+    pub ammo: Vec<Ammo>,
+    pub special_ammo: SpecialAmmo,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SpecialAmmo {
+    Wyvernblast,
+    Adhesive,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Ammo {
+    pub kind: AmmoKind,
+    pub level: u8,
+    pub capacity: u8,
+    pub rapid: bool,
+}
+
+impl From<heavy_bowgun::Ammo> for Ammo {
+    fn from(value: heavy_bowgun::Ammo) -> Self {
+        Self {
+            kind: value.kind,
+            level: value.level,
+            capacity: value.capacity,
+            rapid: false,
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::serde::ordered_map;
+use crate::weapons::series::SeriesId;
+use crate::{IdMap, LanguageMap, RichTextMap, VariantMap};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub mod bow;
+pub mod charge_blade;
+pub mod dual_blades;
+pub mod great_sword;
+pub mod gunlance;
+pub mod hammer;
+pub mod heavy_bowgun;
+pub mod hunting_horn;
+pub mod insect_glaive;
+pub mod lance;
+pub mod light_bowgun;
+pub mod long_sword;
+pub mod series;
+pub mod switch_axe;
+pub mod sword_shield;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Weapon {
+    pub game_id: u32,
+    #[serde(flatten)]
+    pub kind: WeaponKind,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+    #[serde(serialize_with = "ordered_map")]
+    pub descriptions: LanguageMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "RichTextMap::is_empty"
+    )]
+    pub rich_descriptions: RichTextMap,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "VariantMap::is_empty"
+    )]
+    pub descriptions_variants: VariantMap,
+    pub rarity: u8,
+    pub attack_raw: u8,
+    pub affinity: i8,
+    pub defense: u8,
+    pub slots: Vec<u8>,
+    pub specials: Vec<Special>,
+    pub crafting: Crafting,
+    #[serde(serialize_with = "ordered_map")]
+    pub skills: IdMap,
+    pub series_id: Option<SeriesId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum WeaponKind {
+    Bow(bow::Bow),
+    ChargeBlade(charge_blade::ChargeBlade),
+    Gunlance(gunlance::Gunlance),
+    Hammer(hammer::Hammer),
+    HeavyBowgun(heavy_bowgun::HeavyBowgun),
+    Lance(lance::Lance),
+    LightBowgun(light_bowgun::LightBowgun),
+    GreatSword(great_sword::GreatSword),
+    InsectGlaive(insect_glaive::InsectGlaive),
+    SwordShield(sword_shield::SwordShield),
+    SwitchAxe(switch_axe::SwitchAxe),
+    LongSword(long_sword::LongSword),
+    DualBlades(dual_blades::DualBlades),
+    HuntingHorn(hunting_horn::HuntingHorn),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Special {
+    #[serde(flatten)]
+    pub kind: SpecialKind,
+    pub raw: u8,
+    pub hidden: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SpecialKind {
+    Element(Element),
+    Status(Status),
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, JsonSchema,
+)]
+#[serde(tag = "element", rename_all = "lowercase")]
+pub enum Element {
+    Fire,
+    Water,
+    Thunder,
+    Ice,
+    Dragon,
+}
+
+#[derive(
+    Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, JsonSchema,
+)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum Status {
+    Paralysis,
+    Poison,
+    Sleep,
+    Blastblight,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema)]
+pub struct Crafting {
+    pub zenny_cost: u16,
+    #[serde(serialize_with = "ordered_map")]
+    pub inputs: IdMap,
+    pub previous_id: Option<u32>,
+    pub branches: Vec<u32>,
+    pub is_shortcut: bool,
+    pub column: u8,
+    pub row: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Sharpness {
+    pub red: u16,
+    pub orange: u16,
+    pub yellow: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub white: u16,
+    pub purple: u16,
+}
@@ -0,0 +1,13 @@
+use crate::serde::ordered_map;
+use crate::LanguageMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub type SeriesId = isize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Series {
+    pub game_id: SeriesId,
+    #[serde(serialize_with = "ordered_map")]
+    pub names: LanguageMap,
+}
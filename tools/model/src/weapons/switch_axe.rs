@@ -0,0 +1,27 @@
+use crate::weapons::Sharpness;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SwitchAxe {
+    pub sharpness: Sharpness,
+    pub handicraft: Vec<u8>,
+    pub phial: Phial,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Phial {
+    #[serde(flatten)]
+    pub kind: PhialKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, Eq, PartialEq, JsonSchema)]
+#[serde(tag = "kind", content = "raw", rename_all = "lowercase")]
+pub enum PhialKind {
+    Power,
+    Element,
+    Dragon(u8),
+    Exhaust(u8),
+    Paralyze(u8),
+    Poison(u8),
+}
@@ -0,0 +1,232 @@
+"""Writes the small `.msg.*` fixtures used by the msg reader's tests, along with the JSON that
+REMSG_Converter's msg2json should produce for each of them. That JSON follows the converter's output
+format, but is written by this script rather than by the converter; dumps made by the converter
+itself go in `converter/`.
+
+The files follow the layout REMSG_Converter reads and writes: a header, the language, attribute and
+entry tables, then a pool of null-terminated UTF-16LE strings, which is encrypted for versions
+above 12. Run this from anywhere to regenerate the fixtures next to it.
+"""
+
+import json
+import struct
+from pathlib import Path
+
+KEY = bytes([
+    0xCF, 0xCE, 0xFB, 0xF8, 0xEC, 0x0A, 0x33, 0x66, 0x93, 0xA9, 0x1D, 0x93, 0x50, 0x39, 0x5F, 0x09,
+])
+
+INTEGER, FLOAT, STRING = 0, 1, 2
+
+
+def murmur3(data: bytes, seed: int = 0xFFFFFFFF) -> int:
+    c1, c2 = 0xCC9E2D51, 0x1B873593
+    h = seed
+    rounded = len(data) & ~3
+
+    for i in range(0, rounded, 4):
+        k = int.from_bytes(data[i:i + 4], "little")
+        k = (k * c1) & 0xFFFFFFFF
+        k = ((k << 15) | (k >> 17)) & 0xFFFFFFFF
+        k = (k * c2) & 0xFFFFFFFF
+        h ^= k
+        h = ((h << 13) | (h >> 19)) & 0xFFFFFFFF
+        h = (h * 5 + 0xE6546B64) & 0xFFFFFFFF
+
+    k = 0
+    tail = data[rounded:]
+    for i, byte in enumerate(tail):
+        k |= byte << (8 * i)
+    if tail:
+        k = (k * c1) & 0xFFFFFFFF
+        k = ((k << 15) | (k >> 17)) & 0xFFFFFFFF
+        k = (k * c2) & 0xFFFFFFFF
+        h ^= k
+
+    h ^= len(data)
+    h ^= h >> 16
+    h = (h * 0x85EBCA6B) & 0xFFFFFFFF
+    h ^= h >> 13
+    h = (h * 0xC2B2AE35) & 0xFFFFFFFF
+    h ^= h >> 16
+
+    return h
+
+
+def encrypt(data: bytes) -> bytes:
+    out = bytearray()
+    prev = 0
+
+    for index, byte in enumerate(data):
+        current = byte ^ prev ^ KEY[index & 0xF]
+        out.append(current)
+        prev = current
+
+    return bytes(out)
+
+
+def guid_bytes(guid: str) -> bytes:
+    parts = guid.split("-")
+    return (
+        int(parts[0], 16).to_bytes(4, "little")
+        + int(parts[1], 16).to_bytes(2, "little")
+        + int(parts[2], 16).to_bytes(2, "little")
+        + bytes.fromhex(parts[3] + parts[4])
+    )
+
+
+def align(buffer: bytearray, alignment: int):
+    while len(buffer) % alignment:
+        buffer.append(0)
+
+
+def write_msg(version: int, languages, headers, entries) -> bytes:
+    encrypted = version > 12
+    by_hash = version > 15
+
+    # String pool, laid out after every table. Offsets are patched in once its position is known.
+    strings = {}
+    pool = bytearray()
+
+    def string(value: str) -> int:
+        if value not in strings:
+            strings[value] = len(pool)
+            pool.extend(value.encode("utf-16-le") + b"\0\0")
+        return strings[value]
+
+    out = bytearray()
+    patches = []  # (position in `out`, offset into the pool)
+
+    out += struct.pack("<I4sQIII", version, b"GMSG", 0x10, len(entries), len(headers), len(languages))
+    align(out, 8)
+
+    data_offset_at = len(out)
+    if encrypted:
+        out += bytes(8)
+    unknown_offset_at = len(out)
+    out += bytes(8)
+    table_offsets_at = len(out)
+    out += bytes(8 * 3)
+    entry_offsets_at = len(out)
+    out += bytes(8 * len(entries))
+
+    def put(at: int, value: int):
+        out[at:at + 8] = struct.pack("<Q", value)
+
+    put(table_offsets_at, len(out))
+    for language in languages:
+        out += struct.pack("<i", language)
+    align(out, 8)
+
+    put(table_offsets_at + 8, len(out))
+    for kind, _ in headers:
+        out += struct.pack("<i", kind)
+    align(out, 8)
+
+    put(table_offsets_at + 16, len(out))
+    for _, name in headers:
+        patches.append((len(out), string(name)))
+        out += bytes(8)
+
+    attribute_offsets = []
+    for index, entry in enumerate(entries):
+        put(entry_offsets_at + 8 * index, len(out))
+        out += guid_bytes(entry["guid"])
+        out += struct.pack("<I", entry["crc?"])
+        out += struct.pack("<I", murmur3(entry["name"].encode("utf-16-le")) if by_hash else index)
+        patches.append((len(out), string(entry["name"])))
+        out += bytes(8)
+        attribute_offsets.append(len(out))
+        out += bytes(8)
+        for value in entry["content"]:
+            patches.append((len(out), string(value)))
+            out += bytes(8)
+
+    for index, entry in enumerate(entries):
+        put(attribute_offsets[index], len(out))
+        for (kind, _), value in zip(headers, entry["attributes"]):
+            if kind == INTEGER:
+                out += struct.pack("<q", value)
+            elif kind == FLOAT:
+                out += struct.pack("<d", value)
+            else:
+                patches.append((len(out), string(value)))
+                out += bytes(8)
+
+    put(unknown_offset_at, len(out))
+    out += bytes(8)
+
+    data_offset = len(out)
+    if encrypted:
+        put(data_offset_at, data_offset)
+    for at, offset in patches:
+        put(at, data_offset + offset)
+
+    return bytes(out) + (encrypt(bytes(pool)) if encrypted else bytes(pool))
+
+
+ENTRIES = [
+    {
+        "name": "Fixture_Name_000",
+        "guid": "0d1f3a5c-7e92-4b04-a6c8-e0f21a3b5c7d",
+        "crc?": 305419896,
+        "attributes": [42, 0.25, "first"],
+        "content": ["最初", "First <REF Fixture_Name_001>", ""],
+    },
+    {
+        "name": "Fixture_Name_001",
+        "guid": "81c2e3d4-f5a6-4778-89ab-cdef01234567",
+        "crc?": 2882400001,
+        "attributes": [-7, -1.5, ""],
+        "content": ["二番目", "Second\r\nline", "Deuxième"],
+    },
+]
+
+HEADERS = [(INTEGER, "Order"), (FLOAT, "Scale"), (STRING, "Note")]
+
+
+def to_json(version: int, languages, headers, entries) -> str:
+    def entry(index: int, value) -> dict:
+        out = {"name": value["name"], "guid": value["guid"], "crc?": value["crc?"]}
+
+        if version > 15:
+            out["hash"] = murmur3(value["name"].encode("utf-16-le"))
+        else:
+            out["index"] = index
+
+        out["attributes"] = value["attributes"]
+        out["content"] = value["content"]
+        return out
+
+    return json.dumps({
+        "version": version,
+        "attributeHeaders": [{"valueType": kind, "name": name} for kind, name in headers],
+        "languages": languages,
+        "entries": [entry(index, value) for index, value in enumerate(entries)],
+    }, ensure_ascii=False, indent=4) + "\n"
+
+
+def write(root: Path, version: int, headers, entries):
+    languages = [0, 1, 2]
+    path = root / f"Fixture.msg.{version}"
+
+    path.write_bytes(write_msg(version, languages, headers, entries))
+    path.with_name(path.name + ".json").write_text(
+        to_json(version, languages, headers, entries), encoding="utf-8"
+    )
+
+
+def main():
+    root = Path(__file__).parent
+    without_attributes = [{**v, "attributes": []} for v in ENTRIES]
+
+    # Version 12 is the last unencrypted version, and still keys entries by index.
+    write(root, 12, [], without_attributes)
+    # Version 14 has an encrypted string pool, but entries are still keyed by index.
+    write(root, 14, [], without_attributes)
+    # Version 23 (used by Wilds) is encrypted, keys entries by hash, and has attributes.
+    write(root, 23, HEADERS, ENTRIES)
+
+
+if __name__ == "__main__":
+    main()
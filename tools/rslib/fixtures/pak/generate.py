@@ -0,0 +1,107 @@
+"""Writes the small `.pak` fixture used by the pak reader's tests, along with a file list naming the
+paths it contains.
+
+The archive uses version 4.1 with no feature flags, and holds one entry per supported compression
+type plus one individually encrypted entry. Run this from anywhere to regenerate the fixtures next
+to it.
+"""
+
+import struct
+import zlib
+from pathlib import Path
+
+NONE, DEFLATE, ZSTD = 0, 1, 2
+
+
+def murmur3(data: bytes, seed: int = 0xFFFFFFFF) -> int:
+    c1, c2 = 0xCC9E2D51, 0x1B873593
+    h = seed
+    rounded = len(data) & ~3
+
+    for i in range(0, rounded, 4):
+        k = int.from_bytes(data[i:i + 4], "little")
+        k = (k * c1) & 0xFFFFFFFF
+        k = ((k << 15) | (k >> 17)) & 0xFFFFFFFF
+        k = (k * c2) & 0xFFFFFFFF
+        h ^= k
+        h = ((h << 13) | (h >> 19)) & 0xFFFFFFFF
+        h = (h * 5 + 0xE6546B64) & 0xFFFFFFFF
+
+    k = 0
+    tail = data[rounded:]
+    for i, byte in enumerate(tail):
+        k |= byte << (8 * i)
+    if tail:
+        k = (k * c1) & 0xFFFFFFFF
+        k = ((k << 15) | (k >> 17)) & 0xFFFFFFFF
+        k = (k * c2) & 0xFFFFFFFF
+        h ^= k
+
+    h ^= len(data)
+    h ^= h >> 16
+    h = (h * 0x85EBCA6B) & 0xFFFFFFFF
+    h ^= h >> 13
+    h = (h * 0xC2B2AE35) & 0xFFFFFFFF
+    h ^= h >> 16
+
+    return h
+
+
+def path_hash(path: str) -> tuple[int, int]:
+    return (
+        murmur3(path.lower().encode("utf-16-le")),
+        murmur3(path.upper().encode("utf-16-le")),
+    )
+
+
+def deflate(data: bytes) -> bytes:
+    compressor = zlib.compressobj(9, zlib.DEFLATED, -15)
+    return compressor.compress(data) + compressor.flush()
+
+
+def zstd(data: bytes) -> bytes:
+    """A single-segment zstd frame holding one raw (stored) block."""
+    assert len(data) < 256
+    header = struct.pack("<IBB", 0xFD2FB528, 0x20, len(data))
+    block = ((len(data) << 3) | 1).to_bytes(3, "little")
+    return header + block + data
+
+
+FILES = [
+    ("natives/STM/Fixture/Stored.txt", NONE, False, b"Stored without compression\n"),
+    ("natives/STM/Fixture/Deflate.txt", DEFLATE, False, b"Compressed with deflate, deflate, deflate\n"),
+    ("natives/STM/Fixture/Zstd.txt", ZSTD, False, b"Compressed with zstd\n"),
+    ("natives/STM/Fixture/Encrypted.txt", NONE, True, b"Not actually encrypted\n"),
+]
+
+
+def main():
+    root = Path(__file__).parent
+    table = bytearray()
+    data = bytearray()
+    data_start = 16 + 48 * len(FILES)
+
+    for path, compression, encrypted, contents in FILES:
+        stored = {NONE: contents, DEFLATE: deflate(contents), ZSTD: zstd(contents)}[compression]
+        attributes = compression | ((1 << 16) if encrypted else 0)
+        lower, upper = path_hash(path)
+
+        table += struct.pack(
+            "<IIQQQQQ", lower, upper, data_start + len(data), len(stored), len(contents),
+            attributes, 0,
+        )
+        data += stored
+
+    header = b"KPKA" + struct.pack("<BBHII", 4, 1, 0, len(FILES), 0)
+    (root / "Fixture.pak").write_bytes(header + table + data)
+
+    # Listed in a different case and with backslashes, both of which the reader should ignore. The
+    # last path isn't in the archive.
+    paths = [path for path, *_ in FILES] + ["natives/STM/Fixture/Missing.txt"]
+    paths[0] = paths[0].replace("/", "\\")
+    paths[1] = paths[1].upper()
+    (root / "Fixture.list").write_text("\n".join(paths) + "\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,5 +1,10 @@
+use crate::dump::DumpFormat;
+use crate::projection::Projection;
+use mhdb_model::Language;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::{fs, io};
@@ -10,6 +15,9 @@ pub struct Config {
     pub io: Io,
     pub user: Files,
     pub msg: Files,
+
+    #[serde(default)]
+    pub merger: Merger,
 }
 
 impl Config {
@@ -46,15 +54,33 @@ impl Config {
 
 #[derive(Debug, Deserialize)]
 pub struct Tools {
-    pub msg: PathBuf,
+    /// Path to REMSG_Converter. If not set, `.msg.23` files are decoded natively.
+    pub msg: Option<PathBuf>,
     pub rsz_layouts: PathBuf,
+
+    /// Path to the baseline of RSZ fields used by each `user` target, which `extractor schema`
+    /// compares against. It's meant to be committed, so it lives outside the output directory.
+    #[serde(default = "Tools::default_rsz_schema")]
+    pub rsz_schema: PathBuf,
+
+    /// Path to a list of known file paths. Required if `io.data` contains any `.pak` files, since
+    /// archives only store a hash of each path.
+    pub pak_list: Option<PathBuf>,
+}
+
+impl Tools {
+    fn default_rsz_schema() -> PathBuf {
+        PathBuf::from("rsz-schema.json")
+    }
 }
 
 impl Default for Tools {
     fn default() -> Self {
         Self {
-            msg: PathBuf::from("tools/REMSG_Converter/REMSG_Converter.exe"),
+            msg: None,
             rsz_layouts: PathBuf::from("rszmhwilds.json"),
+            rsz_schema: Self::default_rsz_schema(),
+            pak_list: None,
         }
     }
 }
@@ -63,6 +89,10 @@ impl Default for Tools {
 pub struct Io {
     pub data: Vec<String>,
     pub output: PathBuf,
+
+    /// The format extracted files are written in.
+    #[serde(default)]
+    pub format: DumpFormat,
 }
 
 impl Default for Io {
@@ -70,10 +100,42 @@ impl Default for Io {
         Self {
             data: Vec::new(),
             output: PathBuf::from("output"),
+            format: DumpFormat::default(),
         }
     }
 }
 
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct Merger {
+    /// How localized strings are laid out in the merged output. Ignored if `language` is set.
+    pub strings: StringLayout,
+
+    /// If set, every localized field in the merged output is flattened to a plain string in this
+    /// language.
+    pub language: Option<Language>,
+
+    /// If `true`, descriptions are also written as lists of spans that keep the bold and colored
+    /// text shown in-game, which is otherwise stripped.
+    pub rich_text: bool,
+
+    /// The option to keep from each `<LSNR>` tag in a string, by language. Languages that aren't
+    /// listed keep the first option.
+    pub listener_options: HashMap<Language, usize>,
+}
+
+#[derive(Debug, Deserialize, Default, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StringLayout {
+    /// Localized fields hold every translation, keyed by language code.
+    #[default]
+    Embedded,
+
+    /// Localized fields are moved out of the data files and into one `strings/<lang>.json` file
+    /// per language.
+    Split,
+}
+
 #[derive(Debug, Deserialize, Default)]
 pub struct Files {
     pub input_prefix: Option<PathBuf>,
@@ -82,6 +144,8 @@ pub struct Files {
 
 #[derive(Debug, Deserialize)]
 pub struct Target {
+    /// Used to select the target with the extractor's `--target` option.
+    pub name: Option<String>,
     pub files: Vec<String>,
     pub output_prefix: Option<PathBuf>,
 
@@ -95,13 +159,17 @@ impl Target {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Transform {
     #[serde(rename = "match", with = "serde_regex")]
     pub pattern: Regex,
 
     #[serde(default)]
-    pub rsz: Vec<u8>,
+    pub rsz: Vec<RszSelector>,
+
+    /// Trims and reshapes each extracted dump (e.g. `select = "_Values[*]"`).
+    #[serde(flatten)]
+    pub projection: Projection,
 }
 
 impl Transform {
@@ -110,6 +178,34 @@ impl Transform {
     }
 }
 
+/// Selects one of the fields of a `.user` file's root object to extract, instead of the whole
+/// object. Written as either an index (e.g. `2`) or a table naming the field (e.g.
+/// `{ field = "_Values", type = "app.user_data.ExampleData.cData" }`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum RszSelector {
+    /// The field at an index. Shifts if the game reorders the object's fields.
+    Index(u8),
+
+    /// The field with a name. If `type_name` is set, the field must also hold an object (or an
+    /// array of objects) of that type.
+    Field {
+        field: String,
+
+        #[serde(rename = "type")]
+        type_name: Option<String>,
+    },
+}
+
+impl Display for RszSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "{index}"),
+            Self::Field { field, .. } => write!(f, "{field}"),
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("io: {0}")]
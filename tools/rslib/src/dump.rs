@@ -0,0 +1,182 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// The format extracted files are written in. Readers detect the format from a dump's extension,
+/// so dumps of different formats can be mixed in the same output directory.
+#[derive(Debug, Deserialize, Default, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DumpFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+
+    /// JSON without any whitespace.
+    JsonCompact,
+
+    /// MessagePack, with struct fields written by name.
+    Msgpack,
+
+    Cbor,
+}
+
+impl DumpFormat {
+    /// Every file extension a dump can have.
+    pub const EXTENSIONS: [&'static str; 3] = ["json", "msgpack", "cbor"];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json | Self::JsonCompact => "json",
+            Self::Msgpack => "msgpack",
+            Self::Cbor => "cbor",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "json" => Some(Self::Json),
+            "msgpack" => Some(Self::Msgpack),
+            "cbor" => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+
+    /// Returns the format of the dump at `path`, based on its extension.
+    pub fn detect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        path.extension()
+            .and_then(|v| v.to_str())
+            .and_then(Self::from_extension)
+            .ok_or_else(|| Error::UnknownFormat(path.to_owned()))
+    }
+
+    /// Serializes `value` to the file at `path`, streaming it through a buffer. Dumps of the same
+    /// file in any other format are removed (see [`remove_other_formats()`]).
+    pub fn write<P: AsRef<Path>, T: Serialize + ?Sized>(&self, path: P, value: &T) -> Result<()> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        match self {
+            Self::Json => serde_json::to_writer_pretty(&mut writer, value)?,
+            Self::JsonCompact => serde_json::to_writer(&mut writer, value)?,
+            Self::Msgpack => rmp_serde::encode::write_named(&mut writer, value)?,
+            Self::Cbor => {
+                ciborium::into_writer(value, &mut writer).map_err(|e| Error::Cbor(e.to_string()))?
+            }
+        }
+
+        writer.flush()?;
+        remove_other_formats(path)?;
+
+        Ok(())
+    }
+}
+
+/// Reads the dump at `path`, in the format matching its extension.
+pub fn read<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
+    let path = path.as_ref();
+    let format = DumpFormat::detect(path)?;
+    let reader = BufReader::new(File::open(path)?);
+
+    Ok(match format {
+        DumpFormat::Json | DumpFormat::JsonCompact => serde_json::from_reader(reader)?,
+        DumpFormat::Msgpack => rmp_serde::from_read(reader)?,
+        DumpFormat::Cbor => {
+            // ciborium won't read an integer into a float field, which JSON and MessagePack allow,
+            // so the dump is read into a JSON value first to get the same behavior.
+            let value: serde_json::Value =
+                ciborium::from_reader(reader).map_err(|e| Error::Cbor(e.to_string()))?;
+
+            serde_json::from_value(value)?
+        }
+    })
+}
+
+/// Finds the dump for `path` in any format, by swapping its extension for each of
+/// [`DumpFormat::EXTENSIONS`]. Writing a dump removes the same file's dumps in other formats, so
+/// there's normally only one. If more than one exists anyway (e.g. one was copied in by hand), the
+/// first in [`DumpFormat::EXTENSIONS`] order wins.
+pub fn locate<P: AsRef<Path>>(path: P) -> Option<PathBuf> {
+    let path = path.as_ref();
+
+    DumpFormat::EXTENSIONS
+        .iter()
+        .map(|v| path.with_extension(v))
+        .find(|v| v.is_file())
+}
+
+/// Removes the dumps of the file at `path` in every format other than the one `path` is in, so
+/// that a stale dump left over from a previous format can't be read instead of it.
+pub fn remove_other_formats<P: AsRef<Path>>(path: P) -> Result<()> {
+    let path = path.as_ref();
+    let current = path.extension().and_then(|v| v.to_str());
+
+    for extension in DumpFormat::EXTENSIONS {
+        if Some(extension) == current {
+            continue;
+        }
+
+        let other = path.with_extension(extension);
+
+        if other.is_file() {
+            fs::remove_file(other)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrites a glob ending in `.json` so that it matches dumps in any format.
+pub fn any_format_glob(glob: &str) -> String {
+    match glob.strip_suffix(".json") {
+        Some(stem) => format!("{stem}.{{{}}}", DumpFormat::EXTENSIONS.join(",")),
+        None => glob.to_owned(),
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Could not detect the dump format of {}", .0.display())]
+    UnknownFormat(PathBuf),
+
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("msgpack: {0}")]
+    MsgpackEncode(#[from] rmp_serde::encode::Error),
+
+    #[error("msgpack: {0}")]
+    MsgpackDecode(#[from] rmp_serde::decode::Error),
+
+    #[error("cbor: {0}")]
+    Cbor(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writing_replaces_other_formats() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let json = dir.path().join("Item.json");
+        let cbor = dir.path().join("Item.cbor");
+
+        DumpFormat::Json.write(&json, &[1, 2, 3]).unwrap();
+        DumpFormat::Cbor.write(&cbor, &[4, 5, 6]).unwrap();
+
+        assert!(!json.exists());
+        assert_eq!(locate(&json), Some(cbor.clone()));
+        assert_eq!(read::<Vec<i32>, _>(&cbor).unwrap(), [4, 5, 6]);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod msg;
+pub mod pak;
 pub mod user;
\ No newline at end of file
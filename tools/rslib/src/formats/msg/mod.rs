@@ -1,11 +1,24 @@
+use mhdb_model::Language;
 use serde::{Deserialize, Serialize};
-use serde_repr::Deserialize_repr;
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_with::serde_as;
 use std::cell::OnceCell;
 use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
 
-#[derive(Debug, Deserialize)]
+mod reader;
+
+/// The contents of a `.msg.*` file. Serializes to the same JSON shape that REMSG_Converter
+/// produces, so dumps created natively and dumps created by the converter are interchangeable.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Msg {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub attribute_headers: Vec<AttributeHeader>,
     pub languages: Vec<LanguageCode>,
     pub entries: Vec<MsgEntry>,
     #[serde(skip)]
@@ -17,6 +30,22 @@ pub struct Msg {
 }
 
 impl Msg {
+    /// Reads and decodes a `.msg.*` file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        Self::parse(&data)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        reader::parse(data)
+    }
+
     pub fn get_language_index(&self, language: LanguageCode) -> Option<usize> {
         let lookup = self.lang_map.get_or_init(|| {
             self.languages
@@ -72,11 +101,19 @@ impl Msg {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde_as]
 pub struct MsgEntry {
     pub name: String,
     pub guid: String,
+    #[serde(rename = "crc?", default)]
+    pub crc: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<u32>,
+    #[serde(default)]
+    pub attributes: Vec<AttributeValue>,
     #[serde_as(as = "Vec<NoneAsEmptyString>")]
     pub content: Vec<String>,
 }
@@ -93,7 +130,55 @@ impl MsgEntry {
     }
 }
 
-#[derive(Debug, Deserialize_repr, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AttributeHeader {
+    #[serde(rename = "valueType")]
+    pub kind: AttributeKind,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, Eq, PartialEq)]
+#[serde(from = "i32", into = "i32")]
+pub enum AttributeKind {
+    Integer,
+    Float,
+    String,
+    /// Seen as `-1` in most files. The value is still stored, but its meaning is unknown.
+    Unknown(i32),
+}
+
+impl From<i32> for AttributeKind {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Integer,
+            1 => Self::Float,
+            2 => Self::String,
+            v => Self::Unknown(v),
+        }
+    }
+}
+
+impl From<AttributeKind> for i32 {
+    fn from(value: AttributeKind) -> Self {
+        match value {
+            AttributeKind::Integer => 0,
+            AttributeKind::Float => 1,
+            AttributeKind::String => 2,
+            AttributeKind::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Null,
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+#[derive(Debug, Deserialize_repr, Serialize_repr, Copy, Clone, Eq, PartialEq, Hash)]
 #[repr(isize)]
 pub enum LanguageCode {
     Disabled = -1,
@@ -132,81 +217,48 @@ pub enum LanguageCode {
     LatinAmericanSpanish,
 }
 
-/// A map of RFC 639 language codes to a string value. Used to hold translations for an object
-/// field.
-pub type LanguageMap = HashMap<Language, String>;
+impl TryFrom<i32> for LanguageCode {
+    type Error = Error;
 
-/// Language list from https://github.com/dtlnor/RE_MSG/blob/main/LanguagesEnum.md
-#[derive(Debug, PartialEq, Eq, Deserialize, Copy, Clone, Serialize, Hash, Ord, PartialOrd)]
-pub enum Language {
-    #[serde(rename = "")]
-    Disabled,
-    #[serde(rename = "ja")]
-    Japanese,
-    #[serde(rename = "en")]
-    English,
-    #[serde(rename = "fr")]
-    French,
-    #[serde(rename = "it")]
-    Italian,
-    #[serde(rename = "de")]
-    German,
-    #[serde(rename = "es")]
-    Spanish,
-    #[serde(rename = "ru")]
-    Russian,
-    #[serde(rename = "pl")]
-    Polish,
-    #[serde(rename = "nl")]
-    Dutch,
-    #[serde(rename = "pt")]
-    Portuguese,
-    #[serde(rename = "pt-BR")]
-    BrazilianPortuguese,
-    #[serde(rename = "ko")]
-    Korean,
-    #[serde(rename = "zh-Hant")]
-    TraditionalChinese,
-    #[serde(rename = "zh-Hans")]
-    SimplifiedChinese,
-    #[serde(rename = "fi")]
-    Finnish,
-    #[serde(rename = "sv")]
-    Swedish,
-    #[serde(rename = "da")]
-    Danish,
-    #[serde(rename = "no")]
-    Norwegian,
-    #[serde(rename = "cs")]
-    Czech,
-    #[serde(rename = "hu")]
-    Hungarian,
-    #[serde(rename = "sk")]
-    Slovak,
-    #[serde(rename = "ar")]
-    Arabic,
-    #[serde(rename = "tr")]
-    Turkish,
-    #[serde(rename = "bg")]
-    Bulgarian,
-    #[serde(rename = "el")]
-    Greek,
-    #[serde(rename = "ro")]
-    Romanian,
-    #[serde(rename = "th")]
-    Thai,
-    #[serde(rename = "uk")]
-    Ukrainian,
-    #[serde(rename = "vi")]
-    Vietnamese,
-    #[serde(rename = "id")]
-    Indonesian,
-    #[serde(skip_deserializing, rename = "")]
-    Fiction,
-    #[serde(rename = "hi")]
-    Hindi,
-    #[serde(rename = "es-419")]
-    LatinAmericanSpanish,
+    fn try_from(value: i32) -> Result<Self> {
+        Ok(match value {
+            -1 => Self::Disabled,
+            0 => Self::Japanese,
+            1 => Self::English,
+            2 => Self::French,
+            3 => Self::Italian,
+            4 => Self::German,
+            5 => Self::Spanish,
+            6 => Self::Russian,
+            7 => Self::Polish,
+            8 => Self::Dutch,
+            9 => Self::Portuguese,
+            10 => Self::BrazilianPortuguese,
+            11 => Self::Korean,
+            12 => Self::TraditionalChinese,
+            13 => Self::SimplifiedChinese,
+            14 => Self::Finnish,
+            15 => Self::Swedish,
+            16 => Self::Danish,
+            17 => Self::Norwegian,
+            18 => Self::Czech,
+            19 => Self::Hungarian,
+            20 => Self::Slovak,
+            21 => Self::Arabic,
+            22 => Self::Turkish,
+            23 => Self::Bulgarian,
+            24 => Self::Greek,
+            25 => Self::Romanian,
+            26 => Self::Thai,
+            27 => Self::Ukrainian,
+            28 => Self::Vietnamese,
+            29 => Self::Indonesian,
+            30 => Self::Fiction,
+            31 => Self::Hindi,
+            32 => Self::LatinAmericanSpanish,
+            v => return Err(Error::UnknownLanguage(v)),
+        })
+    }
 }
 
 impl From<&LanguageCode> for Language {
@@ -302,3 +354,24 @@ impl From<&Language> for LanguageCode {
         (*value).into()
     }
 }
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Not a msg file (bad magic)")]
+    BadMagic,
+
+    #[error("Offset {0} is outside of the file")]
+    OutOfBounds(usize),
+
+    #[error("Invalid UTF-16 string at offset {0}")]
+    InvalidString(usize),
+
+    #[error("Unknown language code {0}")]
+    UnknownLanguage(i32),
+
+}
@@ -0,0 +1,353 @@
+use crate::formats::msg::{
+    AttributeHeader, AttributeKind, AttributeValue, Error, LanguageCode, Msg, MsgEntry, Result,
+};
+use std::cell::OnceCell;
+
+const MAGIC: &[u8; 4] = b"GMSG";
+
+/// Key used to scramble the string pool of encrypted files. Taken from REMSG_Converter.
+const KEY: [u8; 16] = [
+    0xCF, 0xCE, 0xFB, 0xF8, 0xEC, 0x0A, 0x33, 0x66, 0x93, 0xA9, 0x1D, 0x93, 0x50, 0x39, 0x5F, 0x09,
+];
+
+/// A date-stamped version used by a handful of older titles, which doesn't follow the same rules as
+/// the numbered versions.
+const DATED_VERSION: u32 = 0x2022033D;
+
+pub(super) fn parse(data: &[u8]) -> Result<Msg> {
+    let mut cursor = Cursor::new(data);
+
+    let version = cursor.u32()?;
+
+    if cursor.bytes(4)? != MAGIC {
+        return Err(Error::BadMagic);
+    }
+
+    let _header_offset = cursor.u64()?;
+    let entry_count = cursor.u32()?;
+    let attribute_count = cursor.u32()?;
+    let language_count = cursor.u32()?;
+    cursor.align(8);
+
+    let data_offset = if is_encrypted(version) {
+        Some(cursor.offset()?)
+    } else {
+        None
+    };
+
+    if version != DATED_VERSION {
+        let _unknown_data_offset = cursor.u64()?;
+    }
+
+    let language_offset = cursor.offset()?;
+    let attribute_offset = cursor.offset()?;
+    let attribute_name_offset = cursor.offset()?;
+
+    let entry_offsets = (0..entry_count)
+        .map(|_| cursor.offset())
+        .collect::<Result<Vec<_>>>()?;
+
+    // Strings are referenced by their absolute offset in the file, so the string pool is decrypted
+    // in a copy of the whole buffer rather than on its own.
+    let pool = match data_offset {
+        Some(offset) => {
+            let mut pool = data.to_vec();
+            decrypt(pool.get_mut(offset..).ok_or(Error::OutOfBounds(offset))?);
+            pool
+        }
+        None => data.to_vec(),
+    };
+
+    let mut cursor = Cursor::new(data);
+
+    cursor.seek(language_offset)?;
+    let languages = (0..language_count)
+        .map(|_| LanguageCode::try_from(cursor.i32()?))
+        .collect::<Result<Vec<_>>>()?;
+
+    cursor.seek(attribute_offset)?;
+    let attribute_kinds = (0..attribute_count)
+        .map(|_| cursor.i32().map(AttributeKind::from))
+        .collect::<Result<Vec<_>>>()?;
+
+    cursor.seek(attribute_name_offset)?;
+    let attribute_headers = attribute_kinds
+        .into_iter()
+        .map(|kind| {
+            Ok(AttributeHeader {
+                kind,
+                name: read_string(&pool, cursor.offset()?)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let entries = entry_offsets
+        .into_iter()
+        .map(|offset| {
+            read_entry(
+                data,
+                &pool,
+                offset,
+                version,
+                &attribute_headers,
+                languages.len(),
+            )
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Msg {
+        version,
+        attribute_headers,
+        languages,
+        entries,
+        guid_map: OnceCell::new(),
+        name_map: OnceCell::new(),
+        lang_map: OnceCell::new(),
+    })
+}
+
+fn read_entry(
+    data: &[u8],
+    pool: &[u8],
+    offset: usize,
+    version: u32,
+    attribute_headers: &[AttributeHeader],
+    language_count: usize,
+) -> Result<MsgEntry> {
+    let mut cursor = Cursor::new(data);
+    cursor.seek(offset)?;
+
+    let guid = format_guid(cursor.bytes(16)?);
+    let crc = cursor.u32()?;
+
+    let (hash, index) = if is_entry_by_hash(version) {
+        (Some(cursor.u32()?), None)
+    } else {
+        (None, Some(cursor.u32()?))
+    };
+
+    let name = read_string(pool, cursor.offset()?)?;
+    let attribute_offset = cursor.offset()?;
+
+    let content = (0..language_count)
+        .map(|_| read_string(pool, cursor.offset()?))
+        .collect::<Result<Vec<_>>>()?;
+
+    cursor.seek(attribute_offset)?;
+
+    let attributes = attribute_headers
+        .iter()
+        .map(|header| {
+            Ok(match header.kind {
+                AttributeKind::Integer => AttributeValue::Integer(cursor.i64()?),
+                AttributeKind::Float => AttributeValue::Float(cursor.f64()?),
+                AttributeKind::String => {
+                    AttributeValue::String(read_string(pool, cursor.offset()?)?)
+                }
+                AttributeKind::Unknown(_) => {
+                    cursor.u64()?;
+                    AttributeValue::Null
+                }
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(MsgEntry {
+        name,
+        guid,
+        crc,
+        hash,
+        index,
+        attributes,
+        content,
+    })
+}
+
+fn is_encrypted(version: u32) -> bool {
+    version > 12 && version != DATED_VERSION
+}
+
+fn is_entry_by_hash(version: u32) -> bool {
+    version > 15 && version != DATED_VERSION
+}
+
+fn decrypt(data: &mut [u8]) {
+    let mut prev = 0u8;
+
+    for (index, byte) in data.iter_mut().enumerate() {
+        let current = *byte;
+        *byte = current ^ prev ^ KEY[index & 0xF];
+        prev = current;
+    }
+}
+
+/// Reads a null-terminated UTF-16LE string starting at `offset`.
+fn read_string(pool: &[u8], offset: usize) -> Result<String> {
+    let bytes = pool.get(offset..).ok_or(Error::OutOfBounds(offset))?;
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+
+    String::from_utf16(&units).map_err(|_| Error::InvalidString(offset))
+}
+
+/// Formats a GUID stored in Microsoft's mixed-endian layout as a hyphenated string.
+fn format_guid(bytes: &[u8]) -> String {
+    format!(
+        "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        bytes[3],
+        bytes[2],
+        bytes[1],
+        bytes[0],
+        bytes[5],
+        bytes[4],
+        bytes[7],
+        bytes[6],
+        bytes[8],
+        bytes[9],
+        bytes[10],
+        bytes[11],
+        bytes[12],
+        bytes[13],
+        bytes[14],
+        bytes[15],
+    )
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn seek(&mut self, position: usize) -> Result<()> {
+        if position > self.data.len() {
+            return Err(Error::OutOfBounds(position));
+        }
+
+        self.position = position;
+        Ok(())
+    }
+
+    fn align(&mut self, alignment: usize) {
+        self.position = self.position.next_multiple_of(alignment);
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + len)
+            .ok_or(Error::OutOfBounds(self.position))?;
+
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        // The slice is always exactly `N` bytes long, so the conversion can't fail.
+        Ok(self.bytes(N)?.try_into().unwrap())
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    fn offset(&mut self) -> Result<usize> {
+        let value = self.u64()?;
+        usize::try_from(value).map_err(|_| Error::OutOfBounds(self.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::formats::msg::{Error, Msg};
+    use serde_json::Value;
+    use std::fs;
+    use std::path::Path;
+
+    /// Parses a fixture and compares the result with the JSON REMSG_Converter writes for the same
+    /// file. Most fixtures are generated by `fixtures/msg/generate.py`; the ones in
+    /// `fixtures/msg/converter` were dumped by the converter itself.
+    fn assert_matches_converter(data: &[u8], json: &str) {
+        let msg = Msg::parse(data).unwrap();
+        let expected: Value = serde_json::from_str(json).unwrap();
+
+        assert_eq!(serde_json::to_value(&msg).unwrap(), expected);
+    }
+
+    #[test]
+    fn parses_unencrypted_file() {
+        assert_matches_converter(
+            include_bytes!("../../../fixtures/msg/Fixture.msg.12"),
+            include_str!("../../../fixtures/msg/Fixture.msg.12.json"),
+        );
+    }
+
+    #[test]
+    fn parses_encrypted_file() {
+        assert_matches_converter(
+            include_bytes!("../../../fixtures/msg/Fixture.msg.14"),
+            include_str!("../../../fixtures/msg/Fixture.msg.14.json"),
+        );
+    }
+
+    #[test]
+    fn parses_hashed_entries_with_attributes() {
+        assert_matches_converter(
+            include_bytes!("../../../fixtures/msg/Fixture.msg.23"),
+            include_str!("../../../fixtures/msg/Fixture.msg.23.json"),
+        );
+    }
+
+    #[test]
+    fn parses_converter_dumps() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/msg/converter");
+
+        for entry in fs::read_dir(dir).unwrap() {
+            let json = entry.unwrap().path();
+
+            if json.extension().is_none_or(|v| v != "json") {
+                continue;
+            }
+
+            let data = json.with_extension("");
+            println!("{}", data.display());
+
+            assert_matches_converter(
+                &fs::read(&data).unwrap(),
+                &fs::read_to_string(&json).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut data = include_bytes!("../../../fixtures/msg/Fixture.msg.23").to_vec();
+        data[4..8].copy_from_slice(b"XMSG");
+
+        assert!(matches!(Msg::parse(&data), Err(Error::BadMagic)));
+    }
+}
@@ -0,0 +1,432 @@
+use flate2::read::DeflateDecoder;
+use ruzstd::decoding::StreamingDecoder;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 4] = b"KPKA";
+
+/// Set in the header's feature flags if the entry table is encrypted.
+const FEATURE_ENCRYPTED_TABLE: u16 = 0x08;
+
+/// The seed used for both halves of a path hash.
+const HASH_SEED: u32 = 0xFFFF_FFFF;
+
+/// A `.pak` archive. Only the header and entry table are read up front; file contents are read from
+/// disk as they're requested.
+///
+/// Archives don't store file names, only a hash of each file's path. Use [`FileList`] to find out
+/// which known paths an archive contains.
+///
+/// Only a subset of the format is supported:
+/// - Versions 4.0 and 4.1 with no feature flags set. Archives with an encrypted entry table fail to
+///   open with [`Error::EncryptedTable`], and any other feature flag with
+///   [`Error::UnsupportedFeatures`]. The archives the game ships with all have an encrypted
+///   table, so in practice this only reads unencrypted archives such as ones repacked by modding
+///   tools; the game's own have to be unpacked with ree-pak-gui first.
+/// - Uncompressed, deflate, and zstd entries. Individually encrypted entries are listed, but fail
+///   to read with [`Error::EncryptedEntry`].
+#[derive(Debug)]
+pub struct Pak {
+    path: PathBuf,
+    entries: HashMap<u64, Entry>,
+}
+
+impl Pak {
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self> {
+        let path = path.into();
+        let mut reader = BufReader::new(File::open(&path)?);
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+
+        if &magic != MAGIC {
+            return Err(Error::BadMagic);
+        }
+
+        let major = reader.u8()?;
+        let minor = reader.u8()?;
+
+        if major != 4 || minor > 1 {
+            return Err(Error::UnsupportedVersion(major, minor));
+        }
+
+        let features = reader.u16()?;
+
+        if features & FEATURE_ENCRYPTED_TABLE != 0 {
+            return Err(Error::EncryptedTable);
+        } else if features != 0 {
+            return Err(Error::UnsupportedFeatures(features));
+        }
+
+        let file_count = reader.u32()?;
+        let _fingerprint = reader.u32()?;
+
+        let entries = (0..file_count)
+            .map(|_| Entry::read(&mut reader))
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Self { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the archive contains a file at `name` (e.g.
+    /// `natives/STM/GameDesign/Common/Item/itemData.user.3`).
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(&hash_path(name))
+    }
+
+    /// Returns the entry table record for the file at `name`, if the archive contains it.
+    pub fn entry(&self, name: &str) -> Option<&Entry> {
+        self.entries.get(&hash_path(name))
+    }
+
+    /// Returns every path in `list` that's stored in the archive.
+    pub fn files<'a>(&'a self, list: &'a FileList) -> impl Iterator<Item = &'a str> {
+        list.entries
+            .iter()
+            .filter(|(_, hash)| self.entries.contains_key(hash))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Reads and decompresses the file at `name`.
+    pub fn read(&self, name: &str) -> Result<Vec<u8>> {
+        let entry = self
+            .entries
+            .get(&hash_path(name))
+            .ok_or_else(|| Error::NotFound(name.to_owned()))?;
+
+        if entry.encrypted {
+            return Err(Error::EncryptedEntry(name.to_owned()));
+        }
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(entry.offset))?;
+
+        let mut raw = BufReader::new(file).take(entry.compressed_size);
+        let mut data = Vec::with_capacity(entry.size as usize);
+
+        match entry.compression {
+            Compression::None => raw.read_to_end(&mut data)?,
+            Compression::Deflate => DeflateDecoder::new(raw).read_to_end(&mut data)?,
+            Compression::Zstd => StreamingDecoder::new(raw)
+                .map_err(|e| Error::Zstd(e.to_string()))?
+                .read_to_end(&mut data)?,
+        };
+
+        if data.len() as u64 != entry.size {
+            return Err(Error::SizeMismatch(name.to_owned()));
+        }
+
+        Ok(data)
+    }
+
+    /// Reads the file at `name` and writes it to `output`, creating any missing parent directories.
+    pub fn unpack<P: AsRef<Path>>(&self, name: &str, output: P) -> Result<()> {
+        let output = output.as_ref();
+        let data = self.read(name)?;
+
+        if let Some(parent) = output.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(output, data)?;
+        Ok(())
+    }
+}
+
+/// A file's record in an archive's entry table.
+#[derive(Debug)]
+pub struct Entry {
+    /// The offset of the file's (possibly compressed) contents from the start of the archive.
+    pub offset: u64,
+    pub compressed_size: u64,
+    pub size: u64,
+    pub checksum: u64,
+    pub encrypted: bool,
+    compression: Compression,
+}
+
+impl Entry {
+    fn read<R: Read>(reader: &mut R) -> Result<(u64, Self)> {
+        let hash_lower = reader.u32()?;
+        let hash_upper = reader.u32()?;
+        let offset = reader.u64()?;
+        let compressed_size = reader.u64()?;
+        let size = reader.u64()?;
+        let attributes = reader.u64()?;
+        let checksum = reader.u64()?;
+
+        let compression = match attributes & 0xF {
+            0 => Compression::None,
+            1 => Compression::Deflate,
+            2 => Compression::Zstd,
+            v => return Err(Error::UnsupportedCompression(v as u8)),
+        };
+
+        let entry = Self {
+            offset,
+            compressed_size,
+            size,
+            checksum,
+            encrypted: (attributes >> 16) & 0xFF != 0,
+            compression,
+        };
+
+        Ok((combine_hashes(hash_lower, hash_upper), entry))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Compression {
+    None,
+    Deflate,
+    Zstd,
+}
+
+/// A list of known file paths, one per line, such as the lists that ship with ree-pak-gui. Paths
+/// are relative to the root of the archive (e.g. `natives/STM/GameDesign/Common/Item/itemData.user.3`).
+#[derive(Debug, Default)]
+pub struct FileList {
+    entries: Vec<(String, u64)>,
+}
+
+impl FileList {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+
+        let entries = contents
+            .lines()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| {
+                let name = v.replace('\\', "/");
+                let hash = hash_path(&name);
+
+                (name, hash)
+            })
+            .collect();
+
+        Ok(Self { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Returns the hash an archive uses to identify the file at `path`. The lower half is a hash of the
+/// lowercase path, and the upper half is a hash of the uppercase path.
+pub fn hash_path(path: &str) -> u64 {
+    let hash = |path: String| {
+        let bytes: Vec<u8> = path.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        murmur3(&bytes, HASH_SEED)
+    };
+
+    combine_hashes(hash(path.to_lowercase()), hash(path.to_uppercase()))
+}
+
+fn combine_hashes(lower: u32, upper: u32) -> u64 {
+    ((upper as u64) << 32) | lower as u64
+}
+
+/// 32-bit MurmurHash3 (x86 variant).
+fn murmur3(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xCC9E_2D51;
+    const C2: u32 = 0x1B87_3593;
+
+    let scramble = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+    let mut hash = seed;
+    let chunks = data.chunks_exact(4);
+    let tail = chunks.remainder();
+
+    for chunk in chunks {
+        // Chunks are always exactly 4 bytes long, so the conversion can't fail.
+        hash ^= scramble(u32::from_le_bytes(chunk.try_into().unwrap()));
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xE654_6B64);
+    }
+
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .rev()
+            .fold(0u32, |k, byte| (k << 8) | *byte as u32);
+
+        hash ^= scramble(k);
+    }
+
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85EB_CA6B);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xC2B2_AE35);
+    hash ^= hash >> 16;
+
+    hash
+}
+
+trait ReadExt: Read {
+    fn u8(&mut self) -> Result<u8> {
+        let mut buf = [0; 1];
+        self.read_exact(&mut buf)?;
+        Ok(buf[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let mut buf = [0; 2];
+        self.read_exact(&mut buf)?;
+        Ok(u16::from_le_bytes(buf))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let mut buf = [0; 4];
+        self.read_exact(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let mut buf = [0; 8];
+        self.read_exact(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+}
+
+impl<R: Read + ?Sized> ReadExt for R {}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Not a pak file (bad magic)")]
+    BadMagic,
+
+    #[error("Unsupported pak version {0}.{1}")]
+    UnsupportedVersion(u8, u8),
+
+    #[error("Pak files with an encrypted entry table are not supported")]
+    EncryptedTable,
+
+    #[error("Unsupported pak features {0:#x}")]
+    UnsupportedFeatures(u16),
+
+    #[error("Unsupported compression type {0}")]
+    UnsupportedCompression(u8),
+
+    #[error("File not found in pak: {0}")]
+    NotFound(String),
+
+    #[error("Encrypted files are not supported: {0}")]
+    EncryptedEntry(String),
+
+    #[error("zstd: {0}")]
+    Zstd(String),
+
+    #[error("Decompressed size does not match the entry table: {0}")]
+    SizeMismatch(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/pak/Fixture.pak");
+    const FIXTURE_LIST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/pak/Fixture.list");
+
+    /// Writes a copy of the fixture with its feature flags replaced by `features` into `dir`, and
+    /// returns its path.
+    fn with_features(dir: &Path, name: &str, features: u16) -> PathBuf {
+        let mut data = fs::read(FIXTURE).unwrap();
+        data[6..8].copy_from_slice(&features.to_le_bytes());
+
+        let path = dir.join(format!("{name}.pak"));
+        fs::write(&path, data).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn reads_every_supported_compression() {
+        let pak = Pak::open(FIXTURE).unwrap();
+
+        assert_eq!(pak.len(), 4);
+        assert_eq!(
+            pak.read("natives/STM/Fixture/Stored.txt").unwrap(),
+            b"Stored without compression\n"
+        );
+        assert_eq!(
+            pak.read("natives/STM/Fixture/Deflate.txt").unwrap(),
+            b"Compressed with deflate, deflate, deflate\n"
+        );
+        assert_eq!(
+            pak.read("natives/STM/Fixture/Zstd.txt").unwrap(),
+            b"Compressed with zstd\n"
+        );
+    }
+
+    #[test]
+    fn finds_listed_files() {
+        let pak = Pak::open(FIXTURE).unwrap();
+        let list = FileList::load(FIXTURE_LIST).unwrap();
+
+        assert_eq!(list.len(), 5);
+        assert_eq!(pak.files(&list).count(), 4);
+        assert!(pak.contains("NATIVES/STM/FIXTURE/ZSTD.TXT"));
+        assert_eq!(
+            pak.entry("natives/STM/Fixture/Zstd.txt").map(|v| v.size),
+            Some(21)
+        );
+        assert!(!pak.contains("natives/STM/Fixture/Missing.txt"));
+        assert!(matches!(
+            pak.read("natives/STM/Fixture/Missing.txt"),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_encrypted_entries() {
+        let pak = Pak::open(FIXTURE).unwrap();
+
+        assert!(matches!(
+            pak.read("natives/STM/Fixture/Encrypted.txt"),
+            Err(Error::EncryptedEntry(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_features() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let encrypted = with_features(dir.path(), "encrypted-table", FEATURE_ENCRYPTED_TABLE);
+        assert!(matches!(Pak::open(&encrypted), Err(Error::EncryptedTable)));
+
+        let unknown = with_features(dir.path(), "unknown-features", 0x10);
+        assert!(matches!(
+            Pak::open(&unknown),
+            Err(Error::UnsupportedFeatures(0x10))
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+use rsz::layout::{LayoutMap, TypeId};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+const USER_MAGIC: &[u8; 4] = b"USR\0";
+const RSZ_MAGIC: &[u8; 4] = b"RSZ\0";
+
+/// An RSZ layouts file (e.g. `rszmhwilds.json`), which describes the types that can appear in
+/// `.user.3` files.
+#[derive(Debug)]
+pub struct Layouts {
+    _raw: String,
+    map: LayoutMap<'static>,
+    version: String,
+}
+
+impl Layouts {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let raw = fs::read_to_string(path)?;
+        let map: LayoutMap = serde_json::from_str(&raw)?;
+
+        // SAFETY:
+        // - The referenced string is owned by the struct, and won't be dropped until the struct is.
+        // - We never hand out a mutable reference to the underlying string, so references will never be invalid.
+        let map: LayoutMap<'static> = unsafe { std::mem::transmute(map) };
+        let version = Self::read_version(path, &raw);
+
+        Ok(Self {
+            _raw: raw,
+            map,
+            version,
+        })
+    }
+
+    /// Identifies the layouts file by the first 16 characters of its SHA-256 hash. If `get-rsz.py`
+    /// wrote a `.version` file next to it, its contents are included as well.
+    fn read_version(path: &Path, raw: &str) -> String {
+        let hash = format!("{:x}", Sha256::digest(raw.as_bytes()));
+        let hash = &hash[..16];
+
+        let mut version_path = path.as_os_str().to_owned();
+        version_path.push(".version");
+
+        match fs::read_to_string(version_path) {
+            Ok(version) => format!("{} ({hash})", version.trim()),
+            Err(_) => hash.to_owned(),
+        }
+    }
+
+    pub fn map<'a>(&'a self) -> &'a LayoutMap<'a> {
+        &self.map
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Compares the type IDs and CRCs stored in the `.user.3` file at `path` against the layouts.
+    /// Each type is only reported once, even if the file holds more than one instance of it.
+    pub fn check<P: AsRef<Path>>(&self, path: P) -> Result<Vec<Problem>> {
+        let mut problems = BTreeMap::new();
+
+        for instance in scan(path)? {
+            // Instance 0 is always a null placeholder.
+            if instance.type_id == 0 {
+                continue;
+            }
+
+            let problem = match self.map.get_layout(instance.type_id) {
+                None => Problem::UnknownType {
+                    type_id: instance.type_id,
+                },
+                Some(layout) if layout.crc as u32 != instance.crc => Problem::CrcMismatch {
+                    type_id: instance.type_id,
+                    name: layout.name.to_owned(),
+                    expected: layout.crc as u32,
+                    found: instance.crc,
+                },
+                Some(_) => continue,
+            };
+
+            problems.insert(instance.type_id, problem);
+        }
+
+        Ok(problems.into_values().collect())
+    }
+}
+
+/// A type found in a `.user.3` file that the layouts don't describe correctly.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Problem {
+    /// The type isn't in the layouts at all. The file can't be extracted.
+    UnknownType { type_id: TypeId },
+
+    /// The type's definition has changed since the layouts were generated, usually because of a
+    /// game update. The file may extract, but fields are likely to be read incorrectly.
+    CrcMismatch {
+        type_id: TypeId,
+        name: String,
+        expected: u32,
+        found: u32,
+    },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownType { type_id } => write!(f, "unknown type {type_id:x}"),
+            Self::CrcMismatch {
+                type_id,
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{name} ({type_id:x}) has CRC {found:x}, but the layouts expect {expected:x}"
+            ),
+        }
+    }
+}
+
+/// The type of one of the instances in a `.user.3` file.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Instance {
+    pub type_id: TypeId,
+    pub crc: u32,
+}
+
+/// Reads the type of every instance in the `.user.3` file at `path`, without parsing any of their
+/// fields.
+pub fn scan<P: AsRef<Path>>(path: P) -> Result<Vec<Instance>> {
+    let data = fs::read(path)?;
+
+    if data.get(..4) != Some(USER_MAGIC) {
+        return Err(Error::BadMagic);
+    }
+
+    let rsz = read_u64(&data, 32)? as usize;
+
+    if data.get(rsz..).and_then(|v| v.get(..4)) != Some(RSZ_MAGIC) {
+        return Err(Error::BadMagic);
+    }
+
+    let instance_count = read_u32(&data, rsz + 12)? as usize;
+    let instance_offset = rsz.saturating_add(read_u64(&data, rsz + 24)? as usize);
+
+    (0..instance_count)
+        .map(|index| {
+            let offset = instance_offset.saturating_add(index * 8);
+
+            Ok(Instance {
+                type_id: read_u32(&data, offset)?,
+                crc: read_u32(&data, offset + 4)?,
+            })
+        })
+        .collect()
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset.saturating_add(4))
+        .map(|v| u32::from_le_bytes(v.try_into().unwrap()))
+        .ok_or(Error::OutOfBounds(offset))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    data.get(offset..offset.saturating_add(8))
+        .map(|v| u64::from_le_bytes(v.try_into().unwrap()))
+        .ok_or(Error::OutOfBounds(offset))
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Could not parse RSZ layouts: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Not a .user.3 file (bad magic)")]
+    BadMagic,
+
+    #[error("Unexpected end of file while reading offset {0:#x}")]
+    OutOfBounds(usize),
+}
@@ -1,3 +1,8 @@
 pub mod config;
+pub mod dump;
 pub mod formats;
+pub mod layouts;
+pub mod processors;
+pub mod projection;
+pub mod schema;
 pub mod tools;
@@ -0,0 +1,453 @@
+//! The merger's processors, along with the extracted files each of them reads.
+//!
+//! The processors themselves live in `merger`. Their inputs are listed here instead so that the
+//! extractor and `mhdb` can work out which files a processor needs without depending on the merger.
+
+use crate::dump;
+use clap::ValueEnum;
+use serde::Deserialize;
+
+/// The directory holding extracted `.msg` files, relative to the output directory.
+pub const MSG_DIRECTORY: &str = "msg";
+
+#[derive(Debug, Deserialize, ValueEnum, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Processor {
+    Accessories,
+    Items,
+    Charms,
+    Amulets,
+    Armor,
+    Skill,
+    Weapons,
+    Bow,
+    ChargeBlade,
+    Gunlance,
+    Hammer,
+    HeavyBowgun,
+    Lance,
+    LightBowgun,
+    GreatSword,
+    InsectGlaive,
+    SwordShield,
+    SwitchAxe,
+    LongSword,
+    DualBlades,
+    HuntingHorn,
+    Monsters,
+    Locations,
+    WeaponSeries,
+}
+
+impl Processor {
+    /// Returns the extracted files the processor reads, as globs relative to the configured output
+    /// directory. Globs match dumps in any format.
+    pub fn inputs(&self) -> Vec<String> {
+        use Processor::*;
+
+        let mut inputs = match self {
+            Accessories => inputs::accessories::all(),
+            Items => inputs::items::all(),
+            Charms => inputs::charms::all(),
+            Amulets => inputs::amulets::all(),
+            Armor => inputs::armor::all(),
+            Skill => inputs::skills::all(),
+            Monsters => inputs::monsters::all(),
+            Locations => inputs::locations::all(),
+            Weapons => Self::value_variants()
+                .iter()
+                .filter(|v| v.is_weapon())
+                .flat_map(|v| v.inputs())
+                .collect(),
+            weapon => inputs::weapons::all(*weapon),
+        };
+
+        // Reference placeholders can point at an entry in any msg file.
+        if self.resolves_references() {
+            inputs.push(format!("{MSG_DIRECTORY}/**/*.json"));
+        }
+
+        let mut globs = Vec::with_capacity(inputs.len());
+
+        for glob in inputs.iter().map(|v| dump::any_format_glob(v)) {
+            if !globs.contains(&glob) {
+                globs.push(glob);
+            }
+        }
+
+        globs
+    }
+
+    /// Returns the processors whose merged output this processor reads or modifies. If any of them
+    /// run, this processor needs to run as well, and they always run along with this processor, so
+    /// their output is never read back from disk.
+    ///
+    /// Skills fill in the set and group bonuses on merged armor, and monster locations are resolved
+    /// against the merged stage list.
+    pub fn dependencies(&self) -> &'static [Processor] {
+        match self {
+            Self::Skill => &[Self::Armor],
+            Self::Monsters => &[Self::Locations],
+            _ => &[],
+        }
+    }
+
+    /// Returns `true` if the processor fills in placeholders, which needs the reference index built
+    /// from every msg file.
+    pub fn resolves_references(&self) -> bool {
+        use Processor::*;
+
+        match self {
+            Accessories | Items | Amulets | Armor | Skill | Monsters => true,
+            Charms | Locations | Weapons | WeaponSeries => false,
+            weapon => weapon.is_weapon(),
+        }
+    }
+
+    pub fn is_weapon(&self) -> bool {
+        use Processor::*;
+
+        matches!(
+            self,
+            Bow | ChargeBlade
+                | Gunlance
+                | Hammer
+                | HeavyBowgun
+                | Lance
+                | LightBowgun
+                | GreatSword
+                | InsectGlaive
+                | SwordShield
+                | SwitchAxe
+                | LongSword
+                | DualBlades
+                | HuntingHorn
+                | WeaponSeries
+        )
+    }
+}
+
+/// Paths to the extracted files read by each processor, relative to the configured output
+/// directory.
+pub mod inputs {
+    pub mod accessories {
+        pub const DATA: &str = "user/AccessoryData.json";
+        pub const STRINGS: &str = "msg/Accessory.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [DATA, STRINGS].map(String::from).to_vec()
+        }
+    }
+
+    pub mod amulets {
+        pub const DATA: &str = "user/AmuletData.json";
+        pub const STRINGS: &str = "msg/Amulet.json";
+        pub const RECIPES: &str = "user/AmuletRecipeData.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [DATA, STRINGS, RECIPES].map(String::from).to_vec()
+        }
+    }
+
+    pub mod armor {
+        pub const SERIES_DATA: &str = "user/ArmorSeriesData.json";
+        pub const ARMOR_DATA: &str = "user/ArmorData.json";
+        pub const RECIPE_DATA: &str = "user/ArmorRecipeData.json";
+        pub const UPGRADE_DATA: &str = "user/ArmorUpgradeData.json";
+
+        pub const SERIES_STRINGS: &str = "msg/ArmorSeries.json";
+        pub const ARMOR_STRINGS: &str = "msg/Armor.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [
+                SERIES_DATA,
+                ARMOR_DATA,
+                RECIPE_DATA,
+                UPGRADE_DATA,
+                SERIES_STRINGS,
+                ARMOR_STRINGS,
+            ]
+            .map(String::from)
+            .to_vec()
+        }
+    }
+
+    pub mod charms {
+        pub const DATA: &str = "user/Charm.json";
+        pub const STRINGS: &str = "msg/Charm.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [DATA, STRINGS].map(String::from).to_vec()
+        }
+    }
+
+    pub mod items {
+        pub const DATA: &str = "user/itemData.json";
+
+        pub const FOUNDRY_MATERIAL_DATA: &str =
+            "user/facilities/foundry/SmallWorkshopItemData.json";
+        pub const FOUNDRY_SPHERE_DATA: &str = "user/facilities/foundry/SmallWorkshopDrillData.json";
+        pub const FOUNDRY_ORE_DATA: &str = "user/facilities/foundry/SmallWorkshopRefineData.json";
+
+        pub const RECIPES: &str = "user/ItemRecipe.json";
+        pub const STRINGS: &str = "msg/Item.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [
+                DATA,
+                FOUNDRY_MATERIAL_DATA,
+                FOUNDRY_SPHERE_DATA,
+                FOUNDRY_ORE_DATA,
+                RECIPES,
+                STRINGS,
+            ]
+            .map(String::from)
+            .to_vec()
+        }
+    }
+
+    pub mod locations {
+        pub const STAGE_ID_DATA: &str = "user/locations/Stage.json";
+        pub const GIMMICK_ID_DATA: &str = "user/GmID.json";
+
+        pub const DARK_AREA_DATA: &str = "user/locations/DarkAreaSetting.json";
+        pub const GIMMICK_DATA: &str = "user/GimmickBasicData.json";
+        pub const GIMMICK_TEXT_DATA: &str = "user/GimmickTextData.json";
+
+        pub const CAMP_PATH_PREFIX: &str = "user/locations/camps";
+
+        pub const STAGE_STRINGS: &str = "msg/RefEnvironment.json";
+        pub const GIMMICK_STRINGS: &str = "msg/Gimmick.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            vec![
+                STAGE_ID_DATA.to_owned(),
+                GIMMICK_ID_DATA.to_owned(),
+                DARK_AREA_DATA.to_owned(),
+                GIMMICK_DATA.to_owned(),
+                GIMMICK_TEXT_DATA.to_owned(),
+                format!("{CAMP_PATH_PREFIX}/*_AaaUniqueParam.json"),
+                STAGE_STRINGS.to_owned(),
+                GIMMICK_STRINGS.to_owned(),
+            ]
+        }
+    }
+
+    pub mod monsters {
+        pub const REFS_FIELD: &str = "msg/RefEnvironment.json";
+
+        pub const MONSTER_DATA: &str = "user/monsters/EnemyData.json";
+        pub const MONSTER_STRINGS: &str = "msg/EnemyText.json";
+
+        pub const SPECIES_STRINGS: &str = "msg/EnemySpeciesName.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [
+                vec![
+                    REFS_FIELD.to_owned(),
+                    MONSTER_DATA.to_owned(),
+                    MONSTER_STRINGS.to_owned(),
+                    SPECIES_STRINGS.to_owned(),
+                ],
+                effectives::all(),
+                identifiers::all(),
+                locations::all(),
+                parts::all(),
+                rewards::all(),
+                size::all(),
+            ]
+            .concat()
+        }
+
+        pub mod effectives {
+            pub const ELEMENT_DATA: &str = "user/monsters/EnemyWeakAttrData.json";
+            pub const CONDITION_PRESET_DATA: &str = "user/monsters/EmParamBadConditionPreset.json";
+            pub const CONDITIONS_DATA: &str = "user/monsters/EmParamBadCondition2.json";
+            pub const WEAK_CONDITION_DATA: &str =
+                "user/monsters/EnemyReportMeasureFreeInfoData.json";
+
+            pub const WEAK_CONDITION_STRINGS: &str = "msg/EnemyReportMeasureFreeInfoText.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                [
+                    ELEMENT_DATA,
+                    CONDITION_PRESET_DATA,
+                    CONDITIONS_DATA,
+                    WEAK_CONDITION_DATA,
+                    WEAK_CONDITION_STRINGS,
+                ]
+                .map(String::from)
+                .to_vec()
+            }
+        }
+
+        pub mod identifiers {
+            pub const DATA: &str = "user/monsters/EmID.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                vec![DATA.to_owned()]
+            }
+        }
+
+        pub mod locations {
+            pub const DATA: &str = "user/monsters/EnemyReportBossData.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                vec![DATA.to_owned()]
+            }
+        }
+
+        pub mod parts {
+            pub const DATA_PREFIX: &str = "user/monsters/parts";
+            pub const DATA_SUFFIX: &str = "_Param_Parts.json";
+            pub const TYPE_DATA: &str = "user/monsters/EnemyPartsTypeData.json";
+            pub const BREAK_REWARDS_SUFFIX: &str = "_Param_PartsBreakReward.json";
+
+            pub const STRINGS: &str = "msg/EnemyPartsTypeName.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                vec![
+                    format!("{DATA_PREFIX}/*{DATA_SUFFIX}"),
+                    format!("{DATA_PREFIX}/*{BREAK_REWARDS_SUFFIX}"),
+                    TYPE_DATA.to_owned(),
+                    STRINGS.to_owned(),
+                ]
+            }
+        }
+
+        pub mod rewards {
+            pub const DATA_PREFIX: &str = "user/monsters/rewards";
+            pub const DATA_SUFFIX: &str = "_0.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                vec![format!("{DATA_PREFIX}/*{DATA_SUFFIX}")]
+            }
+        }
+
+        pub mod size {
+            pub const DATA: &str = "user/monsters/EmCommonSize.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                vec![DATA.to_owned()]
+            }
+        }
+    }
+
+    pub mod skills {
+        pub const SKILL_DATA: &str = "user/SkillCommonData.json";
+        pub const RANK_DATA: &str = "user/SkillData.json";
+
+        pub const SKILL_STRINGS: &str = "msg/SkillCommon.json";
+        pub const RANK_STRINGS: &str = "msg/Skill.json";
+
+        pub(crate) fn all() -> Vec<String> {
+            [SKILL_DATA, RANK_DATA, SKILL_STRINGS, RANK_STRINGS]
+                .map(String::from)
+                .to_vec()
+        }
+    }
+
+    pub mod weapons {
+        use crate::processors::Processor;
+
+        /// Returns the name the game uses for a weapon processor's files (e.g. `Tachi` for long
+        /// swords), or `None` for processors that don't merge a weapon kind.
+        pub fn prefix(processor: Processor) -> Option<&'static str> {
+            use Processor::*;
+
+            let prefix = match processor {
+                Bow => "Bow",
+                ChargeBlade => "ChargeAxe",
+                Gunlance => "GunLance",
+                Hammer => "Hammer",
+                HeavyBowgun => "HeavyBowgun",
+                Lance => "Lance",
+                LightBowgun => "LightBowgun",
+                GreatSword => "LongSword",
+                InsectGlaive => "Rod",
+                SwordShield => "ShortSword",
+                SwitchAxe => "SlashAxe",
+                LongSword => "Tachi",
+                DualBlades => "TwinSword",
+                HuntingHorn => "Whistle",
+                _ => return None,
+            };
+
+            Some(prefix)
+        }
+
+        pub fn data_path(prefix: &str) -> String {
+            format!("user/weapons/{prefix}.json")
+        }
+
+        pub fn recipe_path(prefix: &str) -> String {
+            format!("user/weapons/{prefix}Recipe.json")
+        }
+
+        pub fn tree_path(prefix: &str) -> String {
+            format!("user/weapons/{prefix}Tree_2.json")
+        }
+
+        pub fn series_path(prefix: &str) -> String {
+            format!("user/weapons/{prefix}Tree_4.json")
+        }
+
+        pub fn strings_path(prefix: &str) -> String {
+            format!("msg/{prefix}.json")
+        }
+
+        /// Returns the extracted files read by a single weapon processor (or by
+        /// [`Processor::WeaponSeries`]).
+        pub(crate) fn all(processor: Processor) -> Vec<String> {
+            if processor == Processor::WeaponSeries {
+                return series::all();
+            }
+
+            let Some(prefix) = prefix(processor) else {
+                return Vec::new();
+            };
+
+            let mut inputs = vec![
+                data_path(prefix),
+                recipe_path(prefix),
+                tree_path(prefix),
+                series_path(prefix),
+                strings_path(prefix),
+                // Series IDs are resolved using the ID table read by the series processor.
+                series::ID_DATA.to_owned(),
+            ];
+
+            if processor == Processor::HuntingHorn {
+                inputs.extend(hunting_horn::all());
+            }
+
+            inputs
+        }
+
+        pub mod hunting_horn {
+            pub const TONES: &str = "user/weapons/Wp05MusicSkillToneTable.json";
+
+            pub const SONGS: &str = "user/weapons/Wp05MusicSkillToneColorTable.json";
+            pub const SONG_STRINGS: &str = "msg/MusicSkillDataText_Wp05.json";
+
+            pub const WAVE_STRINGS: &str = "msg/HighFreqDataText_Wp05.json";
+            pub const BUBBLE_STRINGS: &str = "msg/HibikiDataText_Wp05.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                [TONES, SONGS, SONG_STRINGS, WAVE_STRINGS, BUBBLE_STRINGS]
+                    .map(String::from)
+                    .to_vec()
+            }
+        }
+
+        pub mod series {
+            pub const DATA: &str = "user/weapons/WeaponSeriesData.json";
+            pub const ID_DATA: &str = "user/weapons/WeaponSeries.json";
+            pub const STRINGS: &str = "msg/WeaponSeries.json";
+
+            pub(crate) fn all() -> Vec<String> {
+                [DATA, ID_DATA, STRINGS].map(String::from).to_vec()
+            }
+        }
+    }
+}
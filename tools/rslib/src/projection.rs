@@ -0,0 +1,285 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Trims and reshapes a JSON dump. Applied in order: `select`, then `fields`, then `rename`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Projection {
+    /// Replaces the dump with the values matched by a query. If the query contains a wildcard, the
+    /// result is an array of every match.
+    pub select: Option<Query>,
+
+    /// Keeps only these fields of each object, in this order. Nested values can be pulled up to the
+    /// top level with a path (e.g. `_Param._Value`), which is used as the new field's name.
+    pub fields: Vec<Query>,
+
+    /// Renames fields, keyed by their current name.
+    pub rename: BTreeMap<String, String>,
+}
+
+impl Projection {
+    pub fn is_empty(&self) -> bool {
+        self.select.is_none() && self.fields.is_empty() && self.rename.is_empty()
+    }
+
+    pub fn apply(&self, value: Value) -> Result<Value> {
+        let value = match &self.select {
+            Some(query) => query.get(&value)?,
+            None => value,
+        };
+
+        if self.fields.is_empty() && self.rename.is_empty() {
+            return Ok(value);
+        }
+
+        match value {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|v| self.project(v))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            value => self.project(value),
+        }
+    }
+
+    fn project(&self, value: Value) -> Result<Value> {
+        let Value::Object(mut object) = value else {
+            return Err(Error::NotAnObject);
+        };
+
+        if !self.fields.is_empty() {
+            let value = Value::Object(object);
+            object = Map::new();
+
+            for query in &self.fields {
+                object.insert(query.to_string(), query.get(&value)?);
+            }
+        }
+
+        Ok(Value::Object(
+            object
+                .into_iter()
+                .map(|(key, value)| match self.rename.get(&key) {
+                    Some(name) => (name.to_owned(), value),
+                    None => (key, value),
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// A path into a JSON value, such as `_Values[*]._ItemId` or `_Data[0]`. `[*]` matches every
+/// element of an array.
+#[derive(Debug, Clone, Eq, PartialEq, DeserializeFromStr, SerializeDisplay)]
+pub struct Query {
+    source: String,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    All,
+}
+
+impl Query {
+    /// Returns `true` if the query can match more than one value.
+    pub fn is_multiple(&self) -> bool {
+        self.steps.contains(&Step::All)
+    }
+
+    /// Returns every value matched by the query.
+    pub fn find<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        self.steps.iter().fold(vec![value], |values, step| {
+            values
+                .into_iter()
+                .flat_map(|value| -> Vec<&Value> {
+                    match (step, value) {
+                        (Step::Key(key), Value::Object(object)) => {
+                            object.get(key).into_iter().collect()
+                        }
+                        (Step::Index(index), Value::Array(values)) => {
+                            values.get(*index).into_iter().collect()
+                        }
+                        (Step::All, Value::Array(values)) => values.iter().collect(),
+                        _ => Vec::new(),
+                    }
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the matched values as an array if the query contains a wildcard, or the single
+    /// matched value otherwise.
+    pub fn get(&self, value: &Value) -> Result<Value> {
+        let matches = self.find(value);
+
+        if self.is_multiple() {
+            return Ok(Value::Array(matches.into_iter().cloned().collect()));
+        }
+
+        matches
+            .first()
+            .map(|v| (*v).clone())
+            .ok_or_else(|| Error::NoMatch(self.to_string()))
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidQuery(s.to_owned());
+        let mut steps = Vec::new();
+
+        for segment in s.split('.') {
+            let (key, mut rest) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+
+            if key.contains(']') {
+                return Err(invalid());
+            } else if !key.is_empty() {
+                steps.push(Step::Key(key.to_owned()));
+            } else if rest.is_empty() {
+                return Err(invalid());
+            }
+
+            while !rest.is_empty() {
+                let end = rest.find(']').ok_or_else(invalid)?;
+                let index = rest.strip_prefix('[').ok_or_else(invalid)?;
+
+                steps.push(match &index[..end - 1] {
+                    "*" => Step::All,
+                    v => Step::Index(v.parse().map_err(|_| invalid())?),
+                });
+
+                rest = &rest[end + 1..];
+            }
+        }
+
+        Ok(Self {
+            source: s.to_owned(),
+            steps,
+        })
+    }
+}
+
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Invalid query `{0}`")]
+    InvalidQuery(String),
+
+    #[error("Query `{0}` did not match anything")]
+    NoMatch(String),
+
+    #[error("Only objects can have their fields selected or renamed")]
+    NotAnObject,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn query(s: &str) -> Query {
+        s.parse().unwrap()
+    }
+
+    fn dump() -> Value {
+        json!({
+            "_Values": [
+                { "_ItemId": 1, "_Param": { "_Value": 10 }, "_Rare": 2 },
+                { "_ItemId": 2, "_Param": { "_Value": 20 }, "_Rare": 3 },
+            ],
+        })
+    }
+
+    #[test]
+    fn parses_valid_queries() {
+        for s in [
+            "_Values",
+            "_Values[0]",
+            "_Values[*]._ItemId",
+            "[0][1]",
+            "a.b[2][*].c",
+        ] {
+            assert_eq!(query(s).to_string(), s);
+        }
+
+        assert!(query("_Values[*]._ItemId").is_multiple());
+        assert!(!query("_Values[1]._ItemId").is_multiple());
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        for s in [
+            "", "a..b", ".a", "a]", "a[0", "a[]", "a[x]", "a[-1]", "a[0]b", "a[0]]",
+        ] {
+            assert!(
+                matches!(s.parse::<Query>(), Err(Error::InvalidQuery(v)) if v == s),
+                "{s:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn gets_nested_values() {
+        let dump = dump();
+
+        assert_eq!(query("_Values[1]._Param._Value").get(&dump).unwrap(), 20);
+        assert_eq!(
+            query("_Values[*]._ItemId").get(&dump).unwrap(),
+            json!([1, 2])
+        );
+        assert_eq!(query("_Values[*]._Missing").get(&dump).unwrap(), json!([]));
+        assert!(matches!(
+            query("_Values[5]").get(&dump),
+            Err(Error::NoMatch(v)) if v == "_Values[5]"
+        ));
+        assert!(query("_Values._ItemId").find(&dump).is_empty());
+    }
+
+    #[test]
+    fn applies_select_fields_and_rename() {
+        let projection = Projection {
+            select: Some(query("_Values[*]")),
+            fields: vec![query("_ItemId"), query("_Param._Value")],
+            rename: BTreeMap::from([(String::from("_Param._Value"), String::from("value"))]),
+        };
+
+        assert_eq!(
+            projection.apply(dump()).unwrap(),
+            json!([{ "_ItemId": 1, "value": 10 }, { "_ItemId": 2, "value": 20 }])
+        );
+    }
+
+    #[test]
+    fn rejects_fields_of_non_objects() {
+        let projection = Projection {
+            select: Some(query("_Values[*]._ItemId")),
+            fields: vec![query("_Value")],
+            ..Default::default()
+        };
+
+        assert!(matches!(projection.apply(dump()), Err(Error::NotAnObject)));
+
+        let missing = Projection {
+            fields: vec![query("_Missing")],
+            ..Default::default()
+        };
+
+        assert!(matches!(missing.apply(json!({})), Err(Error::NoMatch(_))));
+    }
+}
@@ -0,0 +1,193 @@
+use crate::layouts::{self, Layouts};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// The fields of every RSZ type that appears in a set of `.user.3` files, keyed by type name. Used
+/// to spot fields that were added or changed by a game update, since the merger silently ignores
+/// fields it doesn't know about.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Schema(BTreeMap<String, BTreeMap<String, Field>>);
+
+/// The type of a single field, as named by the game engine.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    #[serde(rename = "type")]
+    pub type_name: String,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub array: bool,
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.type_name)?;
+
+        if self.array {
+            write!(f, "[]")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Schema {
+    /// Builds the schema of the `.user.3` file at `path`, using `layouts` to look up the fields of
+    /// each instance's type. Types that aren't in the layouts are skipped; see [`Layouts::check()`].
+    pub fn from_file<P: AsRef<Path>>(layouts: &Layouts, path: P) -> layouts::Result<Self> {
+        let mut schema = Self::default();
+
+        for instance in layouts::scan(path)? {
+            // Instance 0 is always a null placeholder.
+            if instance.type_id == 0 {
+                continue;
+            }
+
+            let Some(layout) = layouts.map().get_layout(instance.type_id) else {
+                continue;
+            };
+
+            if schema.0.contains_key(layout.name) {
+                continue;
+            }
+
+            let fields = layout
+                .fields
+                .iter()
+                .map(|v| {
+                    let field = Field {
+                        type_name: v.original_type_name.to_owned(),
+                        array: v.is_array,
+                    };
+
+                    (v.name.to_owned(), field)
+                })
+                .collect();
+
+            schema.0.insert(layout.name.to_owned(), fields);
+        }
+
+        Ok(schema)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Adds every type in `other` to this schema.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    /// Lists everything in this schema that differs from `baseline`.
+    pub fn diff(&self, baseline: &Self) -> Vec<Change> {
+        let mut changes = Vec::new();
+
+        for (type_name, fields) in &self.0 {
+            let Some(old_fields) = baseline.0.get(type_name) else {
+                changes.push(Change::NewType {
+                    type_name: type_name.clone(),
+                    fields: fields.len(),
+                });
+
+                continue;
+            };
+
+            for (name, field) in fields {
+                match old_fields.get(name) {
+                    None => changes.push(Change::NewField {
+                        type_name: type_name.clone(),
+                        field: name.clone(),
+                        kind: field.clone(),
+                    }),
+                    Some(old) if old != field => changes.push(Change::ChangedField {
+                        type_name: type_name.clone(),
+                        field: name.clone(),
+                        from: old.clone(),
+                        to: field.clone(),
+                    }),
+                    Some(_) => (),
+                }
+            }
+
+            for name in old_fields.keys().filter(|v| !fields.contains_key(*v)) {
+                changes.push(Change::RemovedField {
+                    type_name: type_name.clone(),
+                    field: name.clone(),
+                });
+            }
+        }
+
+        for type_name in baseline.0.keys().filter(|v| !self.0.contains_key(*v)) {
+            changes.push(Change::RemovedType {
+                type_name: type_name.clone(),
+            });
+        }
+
+        changes
+    }
+}
+
+/// A difference between two schemas.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Change {
+    /// A type that no file used before.
+    NewType {
+        type_name: String,
+        fields: usize,
+    },
+
+    /// A type that no file uses anymore.
+    RemovedType {
+        type_name: String,
+    },
+
+    NewField {
+        type_name: String,
+        field: String,
+        kind: Field,
+    },
+
+    RemovedField {
+        type_name: String,
+        field: String,
+    },
+
+    /// A field whose type changed.
+    ChangedField {
+        type_name: String,
+        field: String,
+        from: Field,
+        to: Field,
+    },
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NewType { type_name, fields } => {
+                write!(f, "+ {type_name} (new type, {fields} field(s))")
+            }
+            Self::RemovedType { type_name } => write!(f, "- {type_name} (no longer used)"),
+            Self::NewField {
+                type_name,
+                field,
+                kind,
+            } => write!(f, "+ {type_name}.{field}: {kind}"),
+            Self::RemovedField { type_name, field } => write!(f, "- {type_name}.{field}"),
+            Self::ChangedField {
+                type_name,
+                field,
+                from,
+                to,
+            } => write!(f, "~ {type_name}.{field}: {from} -> {to}"),
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use crate::config::Transform;
+use sha2::{Digest, Sha256};
 use std::ffi::OsStr;
 use std::fmt::Debug;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
@@ -26,10 +30,20 @@ where
     }
 }
 
-fn is_output_newer(input: &Path, output: &Path) -> Result<bool> {
+/// Returns `true` if `output` exists and was modified no earlier than `input`.
+pub fn is_output_newer(input: &Path, output: &Path) -> Result<bool> {
     Ok(output.exists() && input.metadata()?.modified()? <= output.metadata()?.modified()?)
 }
 
+/// Returns the hex-encoded SHA-256 hash of the file at `path`.
+pub fn hash_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 #[macro_export]
 macro_rules! maybe_prefix {
     ($prefix:expr, $path:expr) => {
@@ -57,10 +71,47 @@ pub enum Error {
     #[error("Parsing failed: {0}")]
     Parser(#[from] rsz::Error),
 
+    #[error("Msg parsing failed: {0}")]
+    MsgParser(#[from] crate::formats::msg::Error),
+
     #[error("Serialization failed: {0}")]
     Serializer(#[from] serde_json::Error),
+
+    #[error("RSZ layouts: {0}")]
+    Layouts(#[from] crate::layouts::Error),
+
+    #[error("Could not write dump: {0}")]
+    Dump(#[from] crate::dump::Error),
+
+    #[error("Projection failed: {0}")]
+    Projection(#[from] crate::projection::Error),
+
+    #[error("Document does not contain an RSZ element at {0}")]
+    MissingRszIndex(u8),
+
+    #[error("{object} does not contain an RSZ field named `{field}` (found: {available})")]
+    MissingRszField {
+        object: String,
+        field: String,
+        available: String,
+    },
+
+    #[error("RSZ field `{field}` should hold {expected}, but holds {found}")]
+    RszTypeMismatch {
+        field: String,
+        expected: String,
+        found: String,
+    },
 }
 
 pub trait Extractor: Sync {
-    fn extract(&self, in_path: &Path, out_path: &Path, indexes: &[u8]) -> Result<Vec<PathBuf>>;
+    fn extract(
+        &self,
+        in_path: &Path,
+        out_path: &Path,
+        transform: Option<&Transform>,
+    ) -> Result<Vec<PathBuf>>;
+
+    /// Returns the paths that [`Extractor::extract()`] would write to, without extracting anything.
+    fn outputs(&self, out_path: &Path, transform: Option<&Transform>) -> Result<Vec<PathBuf>>;
 }
@@ -1,27 +1,47 @@
+use crate::config::Transform;
+use crate::dump::DumpFormat;
+use crate::formats::msg::Msg;
 use crate::maybe_prefix;
 use crate::tools::{is_output_newer, run_command, Extractor, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 pub struct MsgExtractor {
-    tool_path: PathBuf,
+    /// Path to REMSG_Converter. If `None`, files are decoded using [`Msg::load()`] instead.
+    tool_path: Option<PathBuf>,
     input_prefix: Option<PathBuf>,
     output_prefix: Option<PathBuf>,
     force: bool,
+
+    /// The format dumps are written in. Ignored if [`MsgExtractor::run()`] isn't given an output
+    /// path, since the converter always writes JSON next to the input file.
+    format: DumpFormat,
 }
 
 impl MsgExtractor {
     pub fn new<P: Into<PathBuf>>(path: P) -> Self {
         Self {
-            tool_path: path.into(),
+            tool_path: Some(path.into()),
+            ..Self::native()
+        }
+    }
+
+    pub fn native() -> Self {
+        Self {
+            tool_path: None,
             input_prefix: None,
             output_prefix: None,
             force: false,
+            format: DumpFormat::default(),
         }
     }
 
-    pub fn create(tool_path: &Path, input_prefix: Option<&Path>) -> Self {
-        let extractor = Self::new(tool_path);
+    pub fn create(tool_path: Option<&Path>, input_prefix: Option<&Path>) -> Self {
+        let extractor = match tool_path {
+            Some(v) => Self::new(v),
+            None => Self::native(),
+        };
+
         match input_prefix {
             Some(v) => extractor.with_input_prefix(v),
             None => extractor,
@@ -43,6 +63,11 @@ impl MsgExtractor {
         self
     }
 
+    pub fn with_format(mut self, format: DumpFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     pub fn run<I, O>(&self, input: I, output: Option<O>) -> Result<PathBuf>
     where
         I: AsRef<Path>,
@@ -60,13 +85,24 @@ impl MsgExtractor {
             return Ok(output.to_owned());
         }
 
-        run_command(
-            &self.tool_path,
-            ["-i", &input.to_string_lossy(), "-m", "json"],
-        )?;
+        let Some(tool_path) = &self.tool_path else {
+            let msg = Msg::load(input)?;
+            self.format.write(output, &msg)?;
+
+            return Ok(output.to_owned());
+        };
+
+        run_command(tool_path, ["-i", &input.to_string_lossy(), "-m", "json"])?;
 
         if tool_out_path != output {
-            fs::copy(&tool_out_path, output)?;
+            if self.format == DumpFormat::Json {
+                fs::copy(&tool_out_path, output)?;
+                crate::dump::remove_other_formats(output)?;
+            } else {
+                let msg: serde_json::Value = crate::dump::read(&tool_out_path)?;
+                self.format.write(output, &msg)?;
+            }
+
             fs::remove_file(&tool_out_path)?;
         }
 
@@ -75,8 +111,18 @@ impl MsgExtractor {
 }
 
 impl Extractor for MsgExtractor {
-    fn extract(&self, in_path: &Path, out_path: &Path, _indexes: &[u8]) -> Result<Vec<PathBuf>> {
+    fn extract(
+        &self,
+        in_path: &Path,
+        out_path: &Path,
+        _transform: Option<&Transform>,
+    ) -> Result<Vec<PathBuf>> {
         let result = self.run(in_path, Some(out_path))?;
         Ok(vec![result])
     }
+
+    fn outputs(&self, out_path: &Path, _transform: Option<&Transform>) -> Result<Vec<PathBuf>> {
+        let out_path = maybe_prefix!(&self.output_prefix, out_path);
+        Ok(vec![out_path.to_owned()])
+    }
 }
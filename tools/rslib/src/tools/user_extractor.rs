@@ -1,36 +1,38 @@
+use crate::config::{RszSelector, Transform};
+use crate::dump::DumpFormat;
+use crate::layouts::Layouts;
 use crate::maybe_prefix;
+use crate::projection::Projection;
 use crate::tools::{is_output_newer, Error, Extractor, Result};
-use rsz::layout::LayoutMap;
-use rsz::User;
-use std::fs::File;
+use rsz::{Field, Object, User, Value};
+use serde::Serialize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct UserExtractor {
-    _raw_layout_map: String,
-    layout_map: LayoutMap<'static>,
+    layouts: Arc<Layouts>,
     input_prefix: Option<PathBuf>,
     output_prefix: Option<PathBuf>,
     force: bool,
+    format: DumpFormat,
 }
 
 impl UserExtractor {
     pub fn new(rsz_layouts_path: &Path) -> Result<Self> {
-        let raw = std::io::read_to_string(File::open(rsz_layouts_path)?)?;
-        let layout_map: LayoutMap = serde_json::from_str(&raw)?;
-
-        // SAFETY:
-        // - The referenced string is owned by the struct, and won't be dropped until the struct is.
-        // - We never hand out a mutable reference to the underlying string, so references will never be invalid.
-        let layout_map: LayoutMap<'static> = unsafe { std::mem::transmute(layout_map) };
+        Ok(Self::from_layouts(Arc::new(Layouts::load(
+            rsz_layouts_path,
+        )?)))
+    }
 
-        Ok(Self {
-            _raw_layout_map: raw,
-            layout_map,
+    pub fn from_layouts(layouts: Arc<Layouts>) -> Self {
+        Self {
+            layouts,
             input_prefix: None,
             output_prefix: None,
             force: false,
-        })
+            format: DumpFormat::default(),
+        }
     }
 
     pub fn create(rsz_layouts_path: &Path, input_prefix: Option<&Path>) -> Result<Self> {
@@ -57,11 +59,19 @@ impl UserExtractor {
         self
     }
 
-    pub fn run<I: Into<Option<u8>>>(
+    pub fn with_format(mut self, format: DumpFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Extracts `input` to `output`. If `selector` is given, only the selected field of the root
+    /// object is written. If `projection` is given, it's applied to the dump before it's written.
+    pub fn run(
         &self,
         input: &Path,
         output: &Path,
-        rsz_index: I,
+        selector: Option<&RszSelector>,
+        projection: Option<&Projection>,
     ) -> Result<PathBuf> {
         let input = maybe_prefix!(&self.input_prefix, input);
         let output = maybe_prefix!(&self.output_prefix, output);
@@ -70,36 +80,81 @@ impl UserExtractor {
             return Ok(output.to_owned());
         }
 
-        let doc = User::load(input, &self.layout_map)?;
-        let out_file = File::create(output)?;
-
-        match rsz_index.into() {
-            Some(index) => {
-                let target = doc.content.root_objects[0]
-                    .fields
-                    .get(index as usize)
-                    .unwrap_or_else(|| {
-                        panic!("Document does not contain an RSZ element at {index}");
-                    });
+        let doc = User::load(input, self.layouts.map())?;
 
-                serde_json::to_writer_pretty(out_file, &target.value)
+        match selector {
+            Some(selector) => {
+                let target = select(&doc.content.root_objects[0], selector)?;
+                self.write(output, &target.value, projection)
             }
-            None => serde_json::to_writer_pretty(out_file, &doc.content.root_objects),
+            None => self.write(output, &doc.content.root_objects, projection),
         }?;
 
         Ok(output.to_owned())
     }
 
-    pub fn run_indexes(
+    pub fn run_selectors(
         &self,
         input: &Path,
         output: &Path,
-        rsz_indexes: &[u8],
+        selectors: &[RszSelector],
+        projection: Option<&Projection>,
     ) -> Result<Vec<PathBuf>> {
-        let mut result_paths = Vec::with_capacity(rsz_indexes.len());
+        let outputs = Self::selected_outputs(output, selectors)?;
+        let mut result_paths = Vec::with_capacity(selectors.len());
+
+        for (selector, output) in selectors.iter().zip(outputs) {
+            result_paths.push(self.run(input, &output, Some(selector), projection)?);
+        }
+
+        Ok(result_paths)
+    }
+
+    /// Writes `value` to `output`. Dumps are streamed straight to disk unless they need to be
+    /// projected, which requires the whole dump to be held in memory as JSON.
+    fn write<T: Serialize>(
+        &self,
+        output: &Path,
+        value: &T,
+        projection: Option<&Projection>,
+    ) -> Result<()> {
+        match projection.filter(|v| !v.is_empty()) {
+            Some(projection) => {
+                let value = projection.apply(serde_json::to_value(value)?)?;
+                self.format.write(output, &value)?;
+            }
+            None => self.format.write(output, value)?,
+        }
+
+        Ok(())
+    }
+
+    /// Returns the paths a file is extracted to, given the transform that applies to it. Unlike
+    /// [`Extractor::outputs()`], this doesn't need an extractor (or the RSZ layouts) to be loaded.
+    pub fn output_paths(out_path: &Path, transform: Option<&Transform>) -> Result<Vec<PathBuf>> {
+        let selectors = transform.map(|v| v.rsz.as_slice()).unwrap_or_default();
+
+        if selectors.is_empty() {
+            Ok(vec![out_path.to_owned()])
+        } else {
+            Self::selected_outputs(out_path, selectors)
+        }
+    }
+
+    /// Returns the path each of `selectors` is written to. If more than one field is extracted,
+    /// every field other than index 0 gets its own file with the index or field name appended to
+    /// the name (e.g. `BowTree_2.json`, or `BowTree_TreeData.json` for a field named `_TreeData`).
+    fn selected_outputs(output: &Path, selectors: &[RszSelector]) -> Result<Vec<PathBuf>> {
+        selectors
+            .iter()
+            .map(|selector| {
+                let suffix = match selector {
+                    _ if selectors.len() == 1 => return Ok(output.to_owned()),
+                    RszSelector::Index(0) => return Ok(output.to_owned()),
+                    RszSelector::Index(index) => index.to_string(),
+                    RszSelector::Field { field, .. } => field.trim_start_matches('_').to_owned(),
+                };
 
-        for rsz_index in rsz_indexes {
-            let output = if rsz_indexes.len() > 1 && *rsz_index > 0 {
                 let mut name = output
                     .file_stem()
                     .ok_or(Error::PathManipulation("could not extract file stem"))?
@@ -108,27 +163,86 @@ impl UserExtractor {
                     .to_string();
 
                 name.push('_');
-                name.push_str(&rsz_index.to_string());
+                name.push_str(&suffix);
+
+                let mut path = output.with_file_name(name);
+
+                if let Some(extension) = output.extension() {
+                    path.set_extension(extension);
+                }
 
-                &output.with_file_name(name).with_extension("json")
-            } else {
-                output
-            };
+                Ok(path)
+            })
+            .collect()
+    }
+}
 
-            result_paths.push(self.run(input, output, *rsz_index)?);
+/// Finds the field of `object` chosen by `selector`.
+fn select<'a>(object: &'a Object, selector: &RszSelector) -> Result<&'a Field> {
+    let (field, type_name) = match selector {
+        RszSelector::Index(index) => {
+            return object
+                .fields
+                .get(*index as usize)
+                .ok_or(Error::MissingRszIndex(*index));
         }
+        RszSelector::Field { field, type_name } => (field, type_name),
+    };
+
+    let Some(target) = object.fields.iter().find(|v| &v.name == field) else {
+        let available: Vec<&str> = object.fields.iter().map(|v| v.name.as_str()).collect();
+
+        return Err(Error::MissingRszField {
+            object: object.name.clone(),
+            field: field.clone(),
+            available: available.join(", "),
+        });
+    };
+
+    if let Some(expected) = type_name {
+        let found = value_type_name(&target.value);
+
+        if found != Some(expected.as_str()) {
+            return Err(Error::RszTypeMismatch {
+                field: field.clone(),
+                expected: expected.clone(),
+                found: found.unwrap_or("a non-object value").to_owned(),
+            });
+        }
+    }
 
-        Ok(result_paths)
+    Ok(target)
+}
+
+/// Returns the type name of an object, or of the objects in an array.
+fn value_type_name(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(object) => Some(&object.name),
+        Value::Array(values) => values.first().and_then(value_type_name),
+        _ => None,
     }
 }
 
 impl Extractor for UserExtractor {
-    fn extract(&self, in_path: &Path, out_path: &Path, indexes: &[u8]) -> Result<Vec<PathBuf>> {
-        if indexes.is_empty() {
-            let result = self.run(in_path, out_path, None)?;
+    fn extract(
+        &self,
+        in_path: &Path,
+        out_path: &Path,
+        transform: Option<&Transform>,
+    ) -> Result<Vec<PathBuf>> {
+        let selectors = transform.map(|v| v.rsz.as_slice()).unwrap_or_default();
+        let projection = transform.map(|v| &v.projection);
+
+        if selectors.is_empty() {
+            let result = self.run(in_path, out_path, None, projection)?;
             Ok(vec![result])
         } else {
-            self.run_indexes(in_path, out_path, indexes)
+            self.run_selectors(in_path, out_path, selectors, projection)
         }
     }
+
+    fn outputs(&self, out_path: &Path, transform: Option<&Transform>) -> Result<Vec<PathBuf>> {
+        let out_path = maybe_prefix!(&self.output_prefix, out_path);
+        Self::output_paths(out_path, transform)
+    }
 }